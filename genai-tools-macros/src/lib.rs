@@ -1,6 +1,8 @@
 use proc_macro::TokenStream;
 use quote::quote;
-use syn::{parse_macro_input, FnArg, ItemFn, Type, PathArguments, GenericArgument};
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::{parse_macro_input, FnArg, ItemFn, LitStr, Pat, Type, PathArguments, GenericArgument, Token};
 
 // Helper function to convert snake_case to UpperCamelCase
 fn to_upper_camel_case(input: &str) -> String {
@@ -16,68 +18,199 @@ fn to_upper_camel_case(input: &str) -> String {
         .collect()
 }
 
-/// The main macro for defining tool functions.
-#[proc_macro_attribute]
-pub fn tool_function(args: TokenStream, input: TokenStream) -> TokenStream {
-    let input_fn = parse_macro_input!(input as ItemFn);
+/// Arguments accepted by `#[tool_function(...)]`.
+#[derive(Default)]
+struct ToolArgs {
+    name: Option<String>,
+    description: Option<String>,
+    output_schema: bool,
+    flatten: bool,
+    collect: bool,
+    init: Option<syn::Path>,
+    one_of: Vec<Vec<LitStr>>,
+    server_only: Vec<LitStr>,
+    example_fn: Option<syn::Path>,
+    no_schema_meta: bool,
+    cost: Option<proc_macro2::TokenStream>,
+    meta: Vec<(String, LitStr)>,
+    progress: bool,
+    terminal: bool,
+    cpu_bound: bool,
+    register_fn: Option<syn::Ident>,
+    no_register_fn: bool,
+}
 
-    // Parse the arguments using syn 2.0 style
-    let mut tool_name = None;
-    let mut tool_description = None;
-
-    if !args.is_empty() {
-        let args_str = args.to_string();
-        
-        // Simple parsing for name = "..." and description = "..."
-        for part in args_str.split(',') {
-            let part = part.trim();
-            if let Some(name_value) = part.strip_prefix("name") {
-                if let Some(value) = extract_string_literal(name_value) {
-                    tool_name = Some(value);
-                }
-            } else if let Some(desc_value) = part.strip_prefix("description") {
-                if let Some(value) = extract_string_literal(desc_value) {
-                    tool_description = Some(value);
-                }
+impl ToolArgs {
+    fn parse(&mut self, meta: syn::meta::ParseNestedMeta) -> syn::Result<()> {
+        if meta.path.is_ident("name") {
+            self.name = Some(meta.value()?.parse::<LitStr>()?.value());
+        } else if meta.path.is_ident("description") {
+            self.description = Some(meta.value()?.parse::<LitStr>()?.value());
+        } else if meta.path.is_ident("output_schema") {
+            self.output_schema = true;
+        } else if meta.path.is_ident("flatten") {
+            self.flatten = true;
+        } else if meta.path.is_ident("register_fn") {
+            self.register_fn = Some(meta.value()?.parse::<LitStr>()?.parse::<syn::Ident>()?);
+        } else if meta.path.is_ident("no_register_fn") {
+            self.no_register_fn = true;
+        } else if meta.path.is_ident("progress") {
+            self.progress = true;
+        } else if meta.path.is_ident("terminal") {
+            self.terminal = true;
+        } else if meta.path.is_ident("cpu_bound") {
+            self.cpu_bound = true;
+        } else if meta.path.is_ident("collect") {
+            self.collect = true;
+        } else if meta.path.is_ident("init") {
+            self.init = Some(meta.value()?.parse::<syn::Path>()?);
+        } else if meta.path.is_ident("cost") {
+            let cost = meta.value()?.parse::<LitStr>()?;
+            self.cost = Some(parse_cost(&cost)?);
+        } else if meta.path.is_ident("schema_meta") {
+            self.no_schema_meta = !meta.value()?.parse::<syn::LitBool>()?.value;
+        } else if meta.path.is_ident("example_fn") {
+            self.example_fn = Some(meta.value()?.parse::<syn::Path>()?);
+        } else if meta.path.is_ident("one_of") {
+            let content;
+            syn::parenthesized!(content in meta.input);
+            let fields: Punctuated<LitStr, Token![,]> = Punctuated::parse_terminated(&content)?;
+            if fields.len() < 2 {
+                return Err(meta.error("one_of needs at least two field names"));
             }
+            self.one_of.push(fields.into_iter().collect());
+        } else if meta.path.is_ident("meta") {
+            meta.parse_nested_meta(|entry| {
+                let key = entry
+                    .path
+                    .get_ident()
+                    .ok_or_else(|| entry.error("metadata keys must be identifiers"))?
+                    .to_string();
+                if self.meta.iter().any(|(existing, _)| *existing == key) {
+                    return Err(entry.error(format!("duplicate metadata key `{}`", key)));
+                }
+                self.meta.push((key, entry.value()?.parse::<LitStr>()?));
+                Ok(())
+            })?;
+        } else if meta.path.is_ident("server_only") {
+            let content;
+            syn::parenthesized!(content in meta.input);
+            let fields: Punctuated<LitStr, Token![,]> = Punctuated::parse_terminated(&content)?;
+            self.server_only.extend(fields);
+        } else {
+            return Err(meta.error("unsupported tool_function argument"));
         }
+        Ok(())
     }
+}
 
-    let tool_name = tool_name.unwrap_or_else(|| input_fn.sig.ident.to_string());
-    let tool_description = tool_description.unwrap_or_else(|| format!("Tool function: {}", tool_name));
+/// Parse a `cost = "..."` value into a `genai_tools::CostHint` expression.
+fn parse_cost(cost: &LitStr) -> syn::Result<proc_macro2::TokenStream> {
+    let value = cost.value();
+    let millis = if let Some(millis) = value.strip_suffix("ms") {
+        millis.parse::<u64>().ok()
+    } else if let Some(secs) = value.strip_suffix('s') {
+        secs.parse::<u64>().ok().and_then(|secs| secs.checked_mul(1000))
+    } else {
+        None
+    };
 
-    // Validate the function signature
-    if input_fn.sig.asyncness.is_none() {
-        return syn::Error::new_spanned(&input_fn.sig, "Tool functions must be async")
-            .to_compile_error()
-            .into();
+    match (value.as_str(), millis) {
+        ("cheap", _) => Ok(quote! { genai_tools::CostHint::Cheap }),
+        ("expensive", _) => Ok(quote! { genai_tools::CostHint::Expensive }),
+        (_, Some(millis)) => Ok(quote! {
+            genai_tools::CostHint::Estimated(::core::time::Duration::from_millis(#millis))
+        }),
+        _ => Err(syn::Error::new_spanned(
+            cost,
+            "cost must be \"cheap\", \"expensive\", or a duration like \"250ms\" or \"3s\"",
+        )),
     }
+}
 
-    if input_fn.sig.inputs.len() != 1 {
-        return syn::Error::new_spanned(
-            &input_fn.sig.inputs,
-            "Tool functions must take exactly one parameter",
+/// The main macro for defining tool functions.
+///
+/// Supported arguments:
+/// - `name = "..."`: the tool name (defaults to the function name)
+/// - `description = "..."`: the tool description
+/// - `register_fn = "..."`: the name of the generated constructor (defaults to
+///   `{fn}_tool`, e.g. `get_weather_tool`)
+/// - `no_register_fn`: don't generate the constructor; build the tool from its
+///   unit struct (`GetWeatherTool`) instead
+/// - `output_schema`: advertise a JSON schema for the output type, which must
+///   then implement `schemars::JsonSchema`
+/// - `flatten`: treat a single argument as a named field rather than the params type
+/// - `collect`: submit the tool to the global inventory used by
+///   `ToolRegistry::from_inventory()`
+/// - `init = path::to::setup`: an `async fn setup() -> Result<(), E>` that must
+///   complete (via `ToolRegistry::init_all()`) before the tool can be called
+/// - `one_of("a", "b", ...)`: require exactly one of the named parameters, by
+///   their serde names; may be repeated for several independent groups. The
+///   names are checked at compile time when the arguments are synthesized into
+///   a params struct, and otherwise only at runtime, when the schema is
+///   generated
+/// - `server_only("a", ...)`: parameters the model may not set; they are
+///   removed from the schema and dropped from incoming arguments by the
+///   registry, so they should have a `#[serde(default)]` or be supplied by
+///   `ToolRegistry::register_with_defaults` or middleware
+/// - `progress`: the function's last argument is a `genai_tools::ProgressReporter`
+///   rather than a parameter; see `ToolRegistry::execute_call_with_progress`
+/// - `terminal`: the tool's result is meant for the user rather than the
+///   model; exposed as `ToolHandler::is_terminal()` and honored by
+///   `ToolRegistry::run_until_done`
+/// - `cpu_bound`: the tool blocks while it computes, so the registry runs it
+///   on tokio's blocking thread pool; exposed as `ToolHandler::is_cpu_bound()`
+///   and requires the `blocking` feature of `genai-tools`
+/// - `cost = "..."`: a scheduling hint exposed as `ToolHandler::cost_hint()`;
+///   `"cheap"`, `"expensive"`, or an estimated duration like `"250ms"` or `"3s"`
+/// - `schema_meta = false`: drop the `$schema` and `title` keywords from this
+///   tool's parameter schema, for providers that reject them
+/// - `meta(key = "value", ...)`: free-form string tags, such as an icon or a
+///   UI group, exposed as `ToolHandler::metadata()` and included in
+///   `ToolRegistry::export_definitions()`
+/// - `example_fn = path::to::make_example`: a `fn make_example() -> Params`
+///   whose result is serialized into the parameter schema's `examples` and
+///   exposed as `ToolHandler::examples()`; the params type must then also
+///   implement `serde::Serialize`
+///
+/// The function must be an `async fn`, or a plain `fn` returning a future:
+/// `impl Future<Output = Result<T, E>>`, `Pin<Box<dyn Future<Output = Result<T, E>> + Send>>`,
+/// or `genai_tools::BoxFuture<'static, Result<T, E>>`. Either way the result is
+/// a `Result<T, E>` with `T: Serialize` and `E` convertible to a boxed error.
+///
+/// A function taking exactly one argument uses that argument's type as the
+/// params type. A function taking any other number of arguments (or marked
+/// `flatten`) gets a synthesized params struct named `{UpperCamelName}Params`
+/// (e.g. `add_numbers` -> `AddNumbersParams`), with the same visibility as the
+/// function and one public field per argument. Doc comments and
+/// `#[serde(...)]`/`#[schemars(...)]` attributes on the arguments are moved to
+/// the corresponding fields. Such tools also accept their arguments as a
+/// positional JSON array, e.g. `[2, 3]` for `{"a": 2, "b": 3}`.
+#[proc_macro_attribute]
+pub fn tool_function(args: TokenStream, input: TokenStream) -> TokenStream {
+    let mut tool_args = ToolArgs::default();
+    let args_parser = syn::meta::parser(|meta| tool_args.parse(meta));
+    parse_macro_input!(args with args_parser);
+
+    let input_fn = parse_macro_input!(input as ItemFn);
+
+    let ToolArgs { name: tool_name, description: tool_description, output_schema, flatten, collect, init, one_of, server_only, example_fn, no_schema_meta, cost, meta, progress, terminal, cpu_bound, register_fn, no_register_fn } = tool_args;
+
+    if no_register_fn && register_fn.is_some() {
+        return syn::Error::new(
+            proc_macro2::Span::call_site(),
+            "`register_fn` and `no_register_fn` cannot be combined",
         )
         .to_compile_error()
         .into();
     }
 
-    // Extract the parameter type
-    let param_type = match &input_fn.sig.inputs[0] {
-        FnArg::Typed(pat_type) => &pat_type.ty,
-        _ => {
-            return syn::Error::new_spanned(
-                &input_fn.sig.inputs[0],
-                "Tool function parameter must be a typed parameter",
-            )
-            .to_compile_error()
-            .into();
-        }
-    };
+    let tool_name = tool_name.unwrap_or_else(|| input_fn.sig.ident.to_string());
+    let tool_description = tool_description.unwrap_or_else(|| format!("Tool function: {}", tool_name));
 
-    // Extract the return type
+    // Validate the function signature: either `async fn` or a plain fn returning a future
     let return_type = match &input_fn.sig.output {
-        syn::ReturnType::Type(_, ty) => ty,
+        syn::ReturnType::Type(_, ty) => ty.as_ref(),
         _ => {
             return syn::Error::new_spanned(
                 &input_fn.sig,
@@ -87,6 +220,141 @@ pub fn tool_function(args: TokenStream, input: TokenStream) -> TokenStream {
             .into();
         }
     };
+    let return_type = if input_fn.sig.asyncness.is_some() {
+        return_type
+    } else {
+        match future_output(return_type) {
+            Some(output) => output,
+            None => {
+                return syn::Error::new_spanned(
+                    &input_fn.sig,
+                    "Tool functions must be async or return `impl Future<Output = Result<T, E>>`",
+                )
+                .to_compile_error()
+                .into();
+            }
+        }
+    };
+
+    let fn_name = &input_fn.sig.ident;
+    let fn_vis = &input_fn.vis;
+    let camel_name = to_upper_camel_case(&fn_name.to_string());
+
+    // With `progress`, the last argument receives the reporter and is not a parameter
+    if progress && input_fn.sig.inputs.is_empty() {
+        return syn::Error::new_spanned(
+            &input_fn.sig,
+            "Tools marked `progress` must take a `ProgressReporter` as their last argument",
+        )
+        .to_compile_error()
+        .into();
+    }
+    let param_count = input_fn.sig.inputs.len() - usize::from(progress);
+
+    // A single parameter is used as the params type directly; any other arity
+    // (or the `flatten` flag) synthesizes a params struct from the arguments.
+    let flatten = flatten || param_count != 1;
+    let mut emitted_fn = input_fn.clone();
+    let (param_type, params_struct, mut call_args, positional_fields_fn) = if flatten {
+        let params_struct_name = syn::Ident::new(&format!("{}Params", camel_name), fn_name.span());
+        let mut fields = Vec::new();
+        let mut field_names = Vec::new();
+        let mut json_names = Vec::new();
+
+        for input in emitted_fn.sig.inputs.iter_mut().take(param_count) {
+            let pat_type = match input {
+                FnArg::Typed(pat_type) => pat_type,
+                FnArg::Receiver(receiver) => {
+                    return syn::Error::new_spanned(receiver, "Tool functions cannot take `self`")
+                        .to_compile_error()
+                        .into();
+                }
+            };
+            let field_name = match pat_type.pat.as_ref() {
+                Pat::Ident(pat_ident) => pat_ident.ident.clone(),
+                other => {
+                    return syn::Error::new_spanned(
+                        other,
+                        "Flattened tool function arguments must be plain identifiers",
+                    )
+                    .to_compile_error()
+                    .into();
+                }
+            };
+            // Doc comments and serde/schemars attributes move to the struct field
+            let attrs = std::mem::take(&mut pat_type.attrs);
+            match serde_field_name(&field_name, &attrs) {
+                Ok(json_name) => json_names.extend(json_name),
+                Err(e) => return e.to_compile_error().into(),
+            }
+            let ty = &pat_type.ty;
+            fields.push(quote! {
+                #(#attrs)*
+                pub #field_name: #ty
+            });
+            field_names.push(field_name);
+        }
+
+        // Examples are serialized from a params value
+        let serialize_derive = example_fn
+            .as_ref()
+            .map(|_| quote! { #[derive(genai_tools::__private::serde::Serialize)] });
+        let params_doc = format!("Arguments of the `{}` tool, synthesized by `#[tool_function]`.", tool_name);
+        let params_struct = quote! {
+            #[doc = #params_doc]
+            #[derive(genai_tools::__private::serde::Deserialize, genai_tools::__private::schemars::JsonSchema)]
+            #serialize_derive
+            #[serde(crate = "genai_tools::__private::serde")]
+            #[schemars(crate = "genai_tools::__private::schemars")]
+            #fn_vis struct #params_struct_name {
+                #(#fields,)*
+            }
+        };
+        let call_args: Vec<_> = field_names.iter().map(|name| quote! { params.#name }).collect();
+
+        // The synthesized struct's fields are known here, so typos fail at
+        // compile time. Groups name schema properties, i.e. serde names
+        for field in one_of.iter().flatten() {
+            if !json_names.contains(&field.value()) {
+                return syn::Error::new_spanned(field, format!("`{}` is not an argument of this tool", field.value()))
+                    .to_compile_error()
+                    .into();
+            }
+        }
+
+        for field in &server_only {
+            if !json_names.contains(&field.value()) {
+                return syn::Error::new_spanned(field, format!("`{}` is not an argument of this tool", field.value()))
+                    .to_compile_error()
+                    .into();
+            }
+        }
+
+        // Server-only fields can't be filled positionally either
+        let positional_names = json_names
+            .iter()
+            .filter(|name| !server_only.iter().any(|field| field.value() == **name));
+        let positional_fields_fn = quote! {
+            fn positional_fields(&self) -> Option<&'static [&'static str]> {
+                Some(&[#(#positional_names),*])
+            }
+        };
+
+        (quote! { #params_struct_name }, params_struct, call_args, positional_fields_fn)
+    } else {
+        let param_type = match &input_fn.sig.inputs[0] {
+            FnArg::Typed(pat_type) => &pat_type.ty,
+            _ => {
+                return syn::Error::new_spanned(
+                    &input_fn.sig.inputs[0],
+                    "Tool function parameter must be a typed parameter",
+                )
+                .to_compile_error()
+                .into();
+            }
+        };
+        (quote! { #param_type }, quote! {}, vec![quote! { params }], quote! {})
+    };
 
     // Parse Result<T, E> from return type
     let (output_type, error_type) = match extract_result_types(return_type) {
@@ -101,16 +369,168 @@ pub fn tool_function(args: TokenStream, input: TokenStream) -> TokenStream {
         }
     };
 
-    let fn_name = &input_fn.sig.ident;
-    let fn_vis = &input_fn.vis;
-    
     // Convert function name to UpperCamelCase and append "Tool"
-    let struct_name_str = format!("{}Tool", to_upper_camel_case(&fn_name.to_string()));
+    let struct_name_str = format!("{}Tool", camel_name);
     let struct_name = syn::Ident::new(&struct_name_str, fn_name.span());
-    let tool_fn_name = syn::Ident::new(&format!("{}_tool", fn_name), fn_name.span());
+    let tool_fn_name = register_fn.unwrap_or_else(|| syn::Ident::new(&format!("{}_tool", fn_name), fn_name.span()));
+
+    let output_schema_fn = if output_schema {
+        quote! {
+            fn output_schema(&self) -> Option<genai_tools::__private::serde_json::Value> {
+                let schema = genai_tools::__private::schemars::schema_for!(#output_type);
+                Some(genai_tools::__private::serde_json::to_value(schema).expect("Failed to serialize output schema"))
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    let try_schema_fn = if one_of.is_empty() && example_fn.is_none() && server_only.is_empty() && !no_schema_meta {
+        quote! {}
+    } else {
+        let groups = one_of.iter().map(|fields| quote! { &[#(#fields),*] });
+        let add_example = example_fn.as_ref().map(|example_fn| {
+            quote! {
+                let example: #param_type = #example_fn();
+                genai_tools::schema::add_example(&mut schema, genai_tools::__private::serde_json::to_value(example)?);
+            }
+        });
+        let remove_server_only = (!server_only.is_empty()).then(|| {
+            quote! { genai_tools::schema::remove_properties(&mut schema, &[#(#server_only),*]); }
+        });
+        let remove_meta = no_schema_meta.then(|| {
+            quote! { genai_tools::schema::remove_meta(&mut schema); }
+        });
+        quote! {
+            fn try_schema(&self) -> Result<genai_tools::__private::serde_json::Value, genai_tools::__private::serde_json::Error> {
+                let mut schema = genai_tools::schema::params_schema::<#param_type>()?;
+                #(genai_tools::schema::add_one_of(&mut schema, #groups)?;)*
+                #add_example
+                #remove_server_only
+                #remove_meta
+                Ok(schema)
+            }
+        }
+    };
+
+    let (call_expr, call_with_progress_fn) = if progress {
+        call_args.push(quote! { progress });
+        let call_expr = quote! {
+            let progress = genai_tools::ProgressReporter::disabled();
+            #fn_name(#(#call_args),*).await
+        };
+        let call_with_progress_fn = quote! {
+            fn call_with_progress(
+                &self,
+                params: Self::Params,
+                progress: genai_tools::ProgressReporter,
+            ) -> genai_tools::BoxFuture<'_, Result<Self::Output, Self::Error>> {
+                Box::pin(async move {
+                    #fn_name(#(#call_args),*).await
+                })
+            }
+        };
+        (call_expr, call_with_progress_fn)
+    } else {
+        (quote! { #fn_name(#(#call_args),*).await }, quote! {})
+    };
+
+    // Serialized once, since `examples` hands out a borrowed slice
+    let examples_fn = example_fn.map(|example_fn| {
+        quote! {
+            fn examples(&self) -> &[genai_tools::__private::serde_json::Value] {
+                static EXAMPLES: ::std::sync::OnceLock<::std::vec::Vec<genai_tools::__private::serde_json::Value>> =
+                    ::std::sync::OnceLock::new();
+                EXAMPLES.get_or_init(|| {
+                    let example: #param_type = #example_fn();
+                    ::std::vec![genai_tools::__private::serde_json::to_value(example).expect("Failed to serialize example")]
+                })
+            }
+        }
+    });
+
+    let cost_hint_fn = cost.map(|cost| {
+        quote! {
+            fn cost_hint(&self) -> genai_tools::CostHint {
+                #cost
+            }
+        }
+    });
+
+    let metadata_fn = (!meta.is_empty()).then(|| {
+        let (keys, values): (Vec<_>, Vec<_>) = meta.into_iter().unzip();
+        quote! {
+            fn metadata(&self) -> ::std::collections::HashMap<String, String> {
+                ::std::collections::HashMap::from([
+                    #((#keys.to_string(), #values.to_string())),*
+                ])
+            }
+        }
+    });
+
+    let server_only_fn = (!server_only.is_empty()).then(|| {
+        quote! {
+            fn server_only_fields(&self) -> &'static [&'static str] {
+                &[#(#server_only),*]
+            }
+        }
+    });
+
+    // Fails to compile unless the registry can offload the calls
+    let is_cpu_bound_fn = cpu_bound.then(|| {
+        quote! {
+            fn is_cpu_bound(&self) -> bool {
+                genai_tools::__cpu_bound!()
+            }
+        }
+    });
+
+    let is_terminal_fn = terminal.then(|| {
+        quote! {
+            fn is_terminal(&self) -> bool {
+                true
+            }
+        }
+    });
+
+    let init_fns = match init {
+        Some(init_fn) => quote! {
+            fn requires_init(&self) -> bool {
+                true
+            }
+
+            fn init(&self) -> genai_tools::BoxFuture<'_, Result<(), genai_tools::BoxError>> {
+                Box::pin(async move {
+                    #init_fn().await.map_err(::core::convert::Into::into)
+                })
+            }
+        },
+        None => quote! {},
+    };
+
+    let collect_submission = if collect {
+        quote! {
+            genai_tools::__private::inventory::submit! {
+                genai_tools::ToolRegistration::new(|| -> Box<dyn genai_tools::ToolHandler> { Box::new(#struct_name) })
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    let register_fn_item = (!no_register_fn).then(|| {
+        quote! {
+            // Create a function that returns the tool instance for registration
+            #fn_vis fn #tool_fn_name() -> #struct_name {
+                #struct_name
+            }
+        }
+    });
 
     let expanded = quote! {
-        #input_fn
+        #emitted_fn
+
+        #params_struct
 
         #[derive(Clone)]
         #fn_vis struct #struct_name;
@@ -128,32 +548,250 @@ pub fn tool_function(args: TokenStream, input: TokenStream) -> TokenStream {
                 #tool_description
             }
 
+            #try_schema_fn
+
+            #positional_fields_fn
+
+            #server_only_fn
+
+            #output_schema_fn
+
+            #cost_hint_fn
+
+            #metadata_fn
+
+            #examples_fn
+
+            #is_terminal_fn
+
+            #is_cpu_bound_fn
+
+            #init_fns
+
             fn call(&self, params: Self::Params) -> std::pin::Pin<Box<dyn std::future::Future<Output = Result<Self::Output, Self::Error>> + Send + '_>> {
                 Box::pin(async move {
-                    #fn_name(params).await
+                    #call_expr
                 })
             }
-        }
 
-        // Create a function that returns the tool instance for registration
-        #fn_vis fn #tool_fn_name() -> #struct_name {
-            #struct_name
+            #call_with_progress_fn
         }
+
+        #register_fn_item
+
+        #collect_submission
     };
 
     TokenStream::from(expanded)
 }
 
-// Helper function to extract string literals from attribute arguments
-fn extract_string_literal(input: &str) -> Option<String> {
-    let input = input.trim();
-    if let Some(eq_part) = input.strip_prefix("=") {
-        let eq_part = eq_part.trim();
-        if eq_part.starts_with('"') && eq_part.ends_with('"') && eq_part.len() >= 2 {
-            return Some(eq_part[1..eq_part.len()-1].to_string());
+/// Input accepted by `tool_set!`: either a bare list of tool functions, or
+/// `[attrs] [vis] enum Name { tools... }`.
+struct ToolSetInput {
+    attrs: Vec<syn::Attribute>,
+    vis: syn::Visibility,
+    name: syn::Ident,
+    tools: Punctuated<syn::Path, Token![,]>,
+}
+
+impl Parse for ToolSetInput {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let attrs = input.call(syn::Attribute::parse_outer)?;
+        let vis: syn::Visibility = input.parse()?;
+        if !input.peek(Token![enum]) {
+            if !attrs.is_empty() || !matches!(vis, syn::Visibility::Inherited) {
+                return Err(input.error("expected `enum Name { ... }` after attributes or visibility"));
+            }
+            return Ok(Self {
+                attrs,
+                vis: syn::parse_quote!(pub),
+                name: syn::Ident::new("ToolSet", proc_macro2::Span::call_site()),
+                tools: Punctuated::parse_terminated(input)?,
+            });
+        }
+
+        input.parse::<Token![enum]>()?;
+        let name = input.parse()?;
+        let content;
+        syn::braced!(content in input);
+        Ok(Self {
+            attrs,
+            vis,
+            name,
+            tools: Punctuated::parse_terminated(&content)?,
+        })
+    }
+}
+
+/// Declare a closed set of `#[tool_function]` tools as an enum.
+///
+/// Each listed function becomes a variant named after it in UpperCamelCase
+/// (`get_weather` -> `GetWeather`), so matching on the enum is checked for
+/// exhaustiveness at compile time. The bare form generates a public enum
+/// named `ToolSet`; use `enum Name { ... }` (with any visibility) to choose
+/// the name. Tools may be given by path, e.g. `weather::get_weather`.
+///
+/// The generated enum derives `Debug`, `Clone`, `Copy`, `PartialEq`, `Eq` and
+/// `Hash`, and provides:
+/// - `ALL`: every variant, in declaration order
+/// - `name()`: the tool name advertised to the model
+/// - `from_name(&str)`: the variant for a tool name, e.g. from a `ToolCall`
+/// - `handler()`: a boxed handler for the tool
+/// - `register_all(&mut ToolRegistry)` and `registry()`: register every tool
+///
+/// # Example
+///
+/// ```ignore
+/// tool_set! {
+///     pub enum WeatherTools { get_weather, calculate }
+/// }
+///
+/// let registry = WeatherTools::registry();
+/// match WeatherTools::from_name(tool_call.fn_name()) {
+///     Some(WeatherTools::GetWeather) => { /* ... */ }
+///     Some(WeatherTools::Calculate) => { /* ... */ }
+///     None => { /* unknown tool */ }
+/// }
+/// ```
+#[proc_macro]
+pub fn tool_set(input: TokenStream) -> TokenStream {
+    let ToolSetInput { attrs, vis, name, tools } = parse_macro_input!(input as ToolSetInput);
+
+    if tools.is_empty() {
+        return syn::Error::new_spanned(&name, "tool_set! needs at least one tool")
+            .to_compile_error()
+            .into();
+    }
+
+    let mut variants = Vec::new();
+    let mut variant_docs = Vec::new();
+    let mut struct_paths = Vec::new();
+
+    for tool in &tools {
+        let fn_name = &tool.segments.last().expect("paths have at least one segment").ident;
+        let camel_name = to_upper_camel_case(&fn_name.to_string());
+        variants.push(syn::Ident::new(&camel_name, fn_name.span()));
+        variant_docs.push(format!("The `{}` tool.", fn_name));
+
+        let mut struct_path = tool.clone();
+        struct_path.segments.last_mut().unwrap().ident = syn::Ident::new(&format!("{}Tool", camel_name), fn_name.span());
+        struct_paths.push(struct_path);
+    }
+
+    let expanded = quote! {
+        #(#attrs)*
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        #vis enum #name {
+            #(
+                #[doc = #variant_docs]
+                #variants,
+            )*
         }
+
+        impl #name {
+            /// Every tool in the set, in declaration order.
+            pub const ALL: &'static [#name] = &[#(#name::#variants),*];
+
+            /// The tool name advertised to the model.
+            pub fn name(self) -> &'static str {
+                match self {
+                    #(#name::#variants => genai_tools::ToolFunction::name(&#struct_paths),)*
+                }
+            }
+
+            /// Find the tool with the given advertised name.
+            pub fn from_name(name: &str) -> Option<Self> {
+                Self::ALL.iter().copied().find(|tool| tool.name() == name)
+            }
+
+            /// Create a boxed handler for this tool.
+            pub fn handler(self) -> Box<dyn genai_tools::ToolHandler> {
+                match self {
+                    #(#name::#variants => Box::new(#struct_paths),)*
+                }
+            }
+
+            /// Register every tool in the set.
+            pub fn register_all(registry: &mut genai_tools::ToolRegistry) -> &mut genai_tools::ToolRegistry {
+                #(registry.register_function(#struct_paths);)*
+                registry
+            }
+
+            /// Create a registry containing every tool in the set.
+            pub fn registry() -> genai_tools::ToolRegistry {
+                let mut registry = genai_tools::ToolRegistry::new();
+                Self::register_all(&mut registry);
+                registry
+            }
+        }
+    };
+
+    TokenStream::from(expanded)
+}
+
+/// The JSON key of a flattened argument: its `#[serde(rename = "...")]`, if any,
+/// otherwise the identifier without a raw `r#` prefix. `None` if the argument
+/// is `#[serde(skip)]` or `#[serde(skip_deserializing)]`, so the model never sends it.
+fn serde_field_name(ident: &syn::Ident, attrs: &[syn::Attribute]) -> syn::Result<Option<String>> {
+    let mut name = ident.to_string().trim_start_matches("r#").to_string();
+    let mut skipped = false;
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("serde")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("rename") {
+                name = meta.value()?.parse::<LitStr>()?.value();
+            } else if meta.path.is_ident("skip") || meta.path.is_ident("skip_deserializing") {
+                skipped = true;
+            } else if meta.input.peek(Token![=]) {
+                // Skip the values of other serde attributes
+                meta.value()?.parse::<proc_macro2::TokenStream>()?;
+            } else if meta.input.peek(syn::token::Paren) {
+                meta.parse_nested_meta(|_| Ok(()))?;
+            }
+            Ok(())
+        })?;
     }
-    None
+    Ok((!skipped).then_some(name))
+}
+
+/// The `Output` of a future type: `impl Future<Output = T>`,
+/// `Pin<Box<dyn Future<Output = T> + ...>>`, or `BoxFuture<'_, T>`.
+fn future_output(ty: &Type) -> Option<&Type> {
+    let bounds = match ty {
+        Type::ImplTrait(impl_trait) => &impl_trait.bounds,
+        Type::TraitObject(trait_object) => &trait_object.bounds,
+        Type::Path(type_path) => {
+            let segment = type_path.path.segments.last()?;
+            let PathArguments::AngleBracketed(args) = &segment.arguments else {
+                return None;
+            };
+            let mut types = args.args.iter().filter_map(|arg| match arg {
+                GenericArgument::Type(ty) => Some(ty),
+                _ => None,
+            });
+            return match segment.ident.to_string().as_str() {
+                "Pin" | "Box" => future_output(types.next()?),
+                "BoxFuture" => types.last(),
+                _ => None,
+            };
+        }
+        _ => return None,
+    };
+    bounds.iter().find_map(|bound| {
+        let syn::TypeParamBound::Trait(bound) = bound else {
+            return None;
+        };
+        let segment = bound.path.segments.last()?;
+        if segment.ident != "Future" {
+            return None;
+        }
+        let PathArguments::AngleBracketed(args) = &segment.arguments else {
+            return None;
+        };
+        args.args.iter().find_map(|arg| match arg {
+            GenericArgument::AssocType(assoc) if assoc.ident == "Output" => Some(&assoc.ty),
+            _ => None,
+        })
+    })
 }
 
 fn extract_result_types(ty: &Type) -> Option<(&Type, &Type)> {
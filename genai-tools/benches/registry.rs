@@ -0,0 +1,76 @@
+//! Dispatch overhead of the registry.
+//!
+//! Run with `cargo bench`. The tools do no work, so the numbers measure what
+//! the registry adds per call: lookup, argument (de)serialization, cloning and
+//! schema generation.
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use genai::chat::ToolCall;
+use genai_tools::{tool_function, ToolCallLike, ToolRegistry};
+use serde_json::json;
+
+#[derive(Debug, thiserror::Error)]
+#[error("never fails")]
+pub struct NeverFails;
+
+#[tool_function(description = "Return the argument unchanged", flatten)]
+pub async fn echo(
+    /// The value to return
+    value: i64,
+) -> Result<i64, NeverFails> {
+    Ok(value)
+}
+
+fn echo_call(id: usize) -> ToolCall {
+    ToolCall::from_parts(format!("call_{}", id), "echo".to_string(), json!({ "value": id }))
+}
+
+fn runtime() -> tokio::runtime::Runtime {
+    tokio::runtime::Builder::new_current_thread().build().unwrap()
+}
+
+fn execute_call(c: &mut Criterion) {
+    let mut registry = ToolRegistry::new();
+    registry.register_function(echo_tool());
+    let call = echo_call(0);
+
+    let mut group = c.benchmark_group("execute_call");
+    group.throughput(Throughput::Elements(1));
+    group.bench_function("trivial_tool", |b| {
+        b.to_async(runtime()).iter(|| registry.execute_call(&call))
+    });
+    group.finish();
+}
+
+fn execute_calls(c: &mut Criterion) {
+    let mut registry = ToolRegistry::new();
+    registry.register_function(echo_tool());
+
+    let mut group = c.benchmark_group("execute_calls");
+    for batch_size in [1, 10, 100, 1000] {
+        let calls: Vec<ToolCall> = (0..batch_size).map(echo_call).collect();
+        group.throughput(Throughput::Elements(batch_size as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(batch_size), &calls, |b, calls| {
+            b.to_async(runtime()).iter(|| registry.execute_calls(calls))
+        });
+    }
+    group.finish();
+}
+
+fn get_tools(c: &mut Criterion) {
+    let mut group = c.benchmark_group("get_tools");
+    for tool_count in [1, 10, 100] {
+        let mut registry = ToolRegistry::new();
+        for i in 0..tool_count {
+            registry.register_with_defaults(echo_tool(), format!("echo_{}", i), json!({}));
+        }
+        group.throughput(Throughput::Elements(tool_count as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(tool_count), &registry, |b, registry| {
+            b.iter(|| registry.get_tools())
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, execute_call, execute_calls, get_tools);
+criterion_main!(benches);
@@ -8,14 +8,16 @@ pub struct WeatherParams {
     pub city: String,
     /// The country of the city
     pub country: String,
-    /// Temperature unit (C for Celsius, F for Fahrenheit)
+    /// Temperature unit
     pub unit: TemperatureUnit,
 }
 
 #[derive(Debug, Deserialize, JsonSchema, Clone)]
 pub enum TemperatureUnit {
+    /// Degrees Celsius
     #[serde(rename = "C")]
     Celsius,
+    /// Degrees Fahrenheit
     #[serde(rename = "F")]
     Fahrenheit,
 }
@@ -75,12 +77,16 @@ pub struct CalculateParams {
 
 #[derive(Debug, Deserialize, JsonSchema, Clone)]
 pub enum Operation {
+    /// Add `b` to `a`
     #[serde(rename = "add")]
     Add,
+    /// Subtract `b` from `a`
     #[serde(rename = "subtract")]
     Subtract,
+    /// Multiply `a` by `b`
     #[serde(rename = "multiply")]
     Multiply,
+    /// Divide `a` by `b`
     #[serde(rename = "divide")]
     Divide,
 }
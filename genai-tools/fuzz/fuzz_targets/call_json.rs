@@ -0,0 +1,80 @@
+//! Throws arbitrary input at the deserialize-execute-serialize path and
+//! relies on libFuzzer to report any panic. Malformed arguments must come
+//! back as `Err`, never as a crash.
+//!
+//! Run with `cargo +nightly fuzz run call_json` from `genai-tools/`.
+
+#![no_main]
+
+use futures::executor::block_on;
+use genai_tools::{tool_function, ToolHandler, ToolRegistry};
+use libfuzzer_sys::fuzz_target;
+use schemars::JsonSchema;
+use serde::Deserialize;
+use serde_json::value::RawValue;
+use serde_json::Value;
+use std::sync::LazyLock;
+
+type BoxError = Box<dyn std::error::Error + Send + Sync>;
+
+#[derive(Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Priority {
+    Low,
+    High,
+}
+
+#[derive(Deserialize, JsonSchema)]
+pub struct Address {
+    pub street: String,
+    pub postcode: Option<String>,
+}
+
+#[derive(Deserialize, JsonSchema)]
+pub struct OrderParams {
+    pub id: u64,
+    pub customer: String,
+    pub quantity: Option<u32>,
+    #[schemars(length(max = 5))]
+    pub tags: Vec<String>,
+    pub priority: Priority,
+    pub address: Option<Address>,
+}
+
+/// A params struct covering integers, strings, options, arrays, enums and nesting.
+#[tool_function(description = "Place an order")]
+pub async fn place_order(params: OrderParams) -> Result<String, BoxError> {
+    let quantity = params.quantity.unwrap_or(1);
+    Ok(format!("{} x{} for {} ({} tags)", params.id, quantity, params.customer, params.tags.len()))
+}
+
+/// A flattened tool, which also accepts positional arrays.
+#[tool_function(description = "Scale a value")]
+pub async fn scale(value: f64, factor: i32) -> Result<f64, BoxError> {
+    Ok(value * f64::from(factor))
+}
+
+static REGISTRY: LazyLock<ToolRegistry> = LazyLock::new(|| {
+    let mut registry = ToolRegistry::new().with_input_validation(true);
+    registry.register_function(place_order_tool()).register_function(scale_tool());
+    registry
+});
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(text) = std::str::from_utf8(data) else {
+        return;
+    };
+    let tools: [&dyn ToolHandler; 2] = [&place_order_tool(), &scale_tool()];
+
+    if let Ok(arguments) = serde_json::from_str::<&RawValue>(text) {
+        for tool in tools {
+            let _ = block_on(tool.call_raw(arguments));
+            let _ = block_on(REGISTRY.execute_raw("fuzz", tool.name(), arguments));
+        }
+    }
+    if let Ok(arguments) = serde_json::from_str::<Value>(text) {
+        for tool in tools {
+            let _ = block_on(tool.call_json(arguments.clone()));
+        }
+    }
+});
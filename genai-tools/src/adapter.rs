@@ -0,0 +1,97 @@
+//! The one place that knows the layout of genai's `ToolCall` and `ToolResponse`.
+//!
+//! The rest of the crate reads and builds tool calls and responses through
+//! [`ToolCallLike`] and [`ToolResponseLike`], so a genai release that renames
+//! or restructures these types only needs the impls below updated.
+
+use genai::chat::{ToolCall, ToolResponse};
+use serde_json::Value;
+
+/// A tool call requested by the model.
+///
+/// Implemented for genai's `ToolCall`. The registry's `execute_*` methods
+/// accept any implementation, so calls decoded from another source (a queue,
+/// a different client library) can be executed without converting them first.
+pub trait ToolCallLike {
+    /// Build a call from its parts.
+    fn from_parts(call_id: String, fn_name: String, fn_arguments: Value) -> Self
+    where
+        Self: Sized;
+
+    /// The id that the response must echo back.
+    fn call_id(&self) -> &str;
+
+    /// The name of the tool to call.
+    fn fn_name(&self) -> &str;
+
+    /// The arguments as JSON.
+    fn fn_arguments(&self) -> &Value;
+
+    /// Take the call apart into `(call_id, fn_name, fn_arguments)`.
+    fn into_parts(self) -> (String, String, Value)
+    where
+        Self: Sized;
+}
+
+/// The response to a tool call, sent back to the model.
+///
+/// Implemented for genai's `ToolResponse`.
+pub trait ToolResponseLike {
+    /// Build a response from the id of the call it answers and its content.
+    fn from_parts(call_id: String, content: String) -> Self
+    where
+        Self: Sized;
+
+    /// The id of the call this response answers.
+    fn call_id(&self) -> &str;
+
+    /// The content sent to the model.
+    fn content(&self) -> &str;
+
+    /// Point the response at a different call.
+    fn set_call_id(&mut self, call_id: String);
+}
+
+impl ToolCallLike for ToolCall {
+    fn from_parts(call_id: String, fn_name: String, fn_arguments: Value) -> Self {
+        ToolCall {
+            call_id,
+            fn_name,
+            fn_arguments,
+        }
+    }
+
+    fn call_id(&self) -> &str {
+        &self.call_id
+    }
+
+    fn fn_name(&self) -> &str {
+        &self.fn_name
+    }
+
+    fn fn_arguments(&self) -> &Value {
+        &self.fn_arguments
+    }
+
+    fn into_parts(self) -> (String, String, Value) {
+        (self.call_id, self.fn_name, self.fn_arguments)
+    }
+}
+
+impl ToolResponseLike for ToolResponse {
+    fn from_parts(call_id: String, content: String) -> Self {
+        ToolResponse::new(call_id, content)
+    }
+
+    fn call_id(&self) -> &str {
+        &self.call_id
+    }
+
+    fn content(&self) -> &str {
+        &self.content
+    }
+
+    fn set_call_id(&mut self, call_id: String) {
+        self.call_id = call_id;
+    }
+}
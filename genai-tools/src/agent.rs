@@ -0,0 +1,99 @@
+use crate::adapter::ToolCallLike;
+use crate::error::RegistryError;
+use crate::registry::ToolRegistry;
+use crate::traits::BoxError;
+use genai::chat::{ChatMessage, ChatRequest, ChatResponse, ToolCall, ToolResponse};
+use genai::Client;
+
+/// The outcome of [`ToolRegistry::run_until_done`].
+#[derive(Debug)]
+pub struct AgentRun {
+    /// The model's final response, which contains no tool calls.
+    pub response: ChatResponse,
+    /// The full conversation: the original messages, every tool call and tool
+    /// response exchanged, and the final assistant message if it had text.
+    pub messages: Vec<ChatMessage>,
+    /// Responses of the terminal tools that ended the run, to show to the user.
+    ///
+    /// Empty when the model finished on its own. Otherwise `response` is the
+    /// model response whose tool calls included a terminal tool.
+    pub terminal: Vec<ToolResponse>,
+}
+
+impl ToolRegistry {
+    /// Run a chat request, executing tool calls until the model stops making them.
+    ///
+    /// Each round sends the request with every registered tool attached. If the
+    /// response contains tool calls, they are executed, the calls and their
+    /// responses are appended to the conversation, and the request is sent
+    /// again. The loop ends when the model answers without calling a tool, or
+    /// right after a round that called a terminal tool (see
+    /// [`ToolHandler::is_terminal`](crate::ToolHandler::is_terminal)), whose
+    /// responses are then returned in [`AgentRun::terminal`] rather than sent
+    /// back to the model.
+    ///
+    /// At most `max_rounds` requests are sent; if the model is still calling
+    /// tools after that, the run fails with [`RegistryError::RoundLimit`]. A
+    /// failing tool call or chat request also ends the run with its error.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let run = registry
+    ///     .run_until_done(&client, "gpt-4", ChatRequest::from_user("Weather in Tokyo?"), 5)
+    ///     .await?;
+    /// println!("{}", run.response.content_text_as_str().unwrap_or_default());
+    /// ```
+    pub async fn run_until_done(
+        &self,
+        client: &Client,
+        model: &str,
+        request: ChatRequest,
+        max_rounds: usize,
+    ) -> Result<AgentRun, BoxError> {
+        let mut request = self.apply_to_request(request);
+
+        for _ in 0..max_rounds {
+            let response = client.exec_chat(model, request.clone(), None).await?;
+
+            let tool_calls: Vec<ToolCall> = response
+                .tool_calls()
+                .unwrap_or_default()
+                .into_iter()
+                .cloned()
+                .collect();
+
+            if tool_calls.is_empty() {
+                if let Some(text) = response.content_text_as_str() {
+                    request = request.append_message(ChatMessage::assistant(text));
+                }
+                return Ok(AgentRun {
+                    response,
+                    messages: request.messages,
+                    terminal: Vec::new(),
+                });
+            }
+
+            let tool_responses = self.execute_calls(&tool_calls).await?;
+            let terminal: Vec<ToolResponse> = tool_calls
+                .iter()
+                .zip(&tool_responses)
+                .filter(|(call, _)| self.is_terminal(call.fn_name()))
+                .map(|(_, tool_response)| tool_response.clone())
+                .collect();
+            request = request.append_message(tool_calls);
+            for tool_response in tool_responses {
+                request = request.append_message(tool_response);
+            }
+            if !terminal.is_empty() {
+                return Ok(AgentRun {
+                    response,
+                    messages: request.messages,
+                    terminal,
+                });
+            }
+        }
+
+        Err(Box::new(RegistryError::RoundLimit { max_rounds }))
+    }
+}
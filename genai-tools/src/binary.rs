@@ -0,0 +1,48 @@
+use bytes::Bytes;
+use serde::ser::{Serialize, SerializeStruct, Serializer};
+
+/// Raw bytes returned by a tool, such as a downloaded file, with their MIME
+/// type.
+///
+/// Return it as a tool's output type to make the tool callable with
+/// [`ToolRegistry::execute_call_bytes`](crate::ToolRegistry::execute_call_bytes),
+/// which hands the bytes to the caller untouched. Through the JSON paths, such
+/// as [`execute_call`](crate::ToolRegistry::execute_call), the result is only
+/// summarized as `{"mime_type": ..., "size": ...}`, keeping the payload out of
+/// the model's context.
+///
+/// # Example
+///
+/// ```ignore
+/// #[tool_function(description = "Download a report as PDF")]
+/// pub async fn download_report(id: u64) -> Result<BinaryOutput, ReportError> {
+///     let pdf: Vec<u8> = render_pdf(id).await?;
+///     Ok(BinaryOutput::new("application/pdf", pdf))
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BinaryOutput {
+    /// The MIME type of `bytes`, e.g. `image/png`.
+    pub mime_type: String,
+    /// The payload.
+    pub bytes: Bytes,
+}
+
+impl BinaryOutput {
+    /// Wrap `bytes` of the given MIME type.
+    pub fn new(mime_type: impl Into<String>, bytes: impl Into<Bytes>) -> Self {
+        Self {
+            mime_type: mime_type.into(),
+            bytes: bytes.into(),
+        }
+    }
+}
+
+impl Serialize for BinaryOutput {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut summary = serializer.serialize_struct("BinaryOutput", 2)?;
+        summary.serialize_field("mime_type", &self.mime_type)?;
+        summary.serialize_field("size", &self.bytes.len())?;
+        summary.end()
+    }
+}
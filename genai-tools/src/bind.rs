@@ -0,0 +1,193 @@
+use crate::binary::BinaryOutput;
+use crate::cost::CostHint;
+use crate::error::RegistryError;
+use crate::positional::named_arguments;
+use crate::progress::ProgressReporter;
+use crate::traits::{BoxError, BoxFuture, ToolHandler};
+use serde_json::value::RawValue;
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+
+/// Exposes a handler under a new name with some arguments pre-bound.
+///
+/// Bound fields are merged into every call's arguments, overriding whatever the
+/// model sent, and are removed from the advertised schema. Positional
+/// arguments map onto the remaining fields.
+pub(crate) struct BoundTool {
+    name: String,
+    inner: Box<dyn ToolHandler>,
+    defaults: Map<String, Value>,
+    /// The inner tool's examples without the bound fields.
+    examples: Vec<Value>,
+}
+
+impl BoundTool {
+    pub(crate) fn new(name: String, inner: Box<dyn ToolHandler>, defaults: Value) -> Self {
+        let defaults = match defaults {
+            Value::Object(defaults) => defaults,
+            other => panic!("Defaults for tool '{}' must be a JSON object, got {}", name, other),
+        };
+        let examples = inner
+            .examples()
+            .iter()
+            .cloned()
+            .map(|mut example| {
+                if let Some(example) = example.as_object_mut() {
+                    example.retain(|field, _| !defaults.contains_key(field));
+                }
+                example
+            })
+            .collect();
+        Self { name, inner, defaults, examples }
+    }
+
+    fn hide_bound_fields(&self, mut schema: Value) -> Value {
+        if let Some(properties) = schema.get_mut("properties").and_then(Value::as_object_mut) {
+            for field in self.defaults.keys() {
+                properties.remove(field);
+            }
+        }
+        if let Some(required) = schema.get_mut("required").and_then(Value::as_array_mut) {
+            required.retain(|field| !field.as_str().is_some_and(|field| self.defaults.contains_key(field)));
+        }
+        schema
+    }
+
+    fn bind(&self, params: Value) -> Result<Value, BoxError> {
+        let mut params = match self.name_arguments(params)? {
+            Value::Object(params) => params,
+            // Let the inner tool report the type error
+            other => return Ok(other),
+        };
+        for (field, value) in &self.defaults {
+            params.insert(field.clone(), value.clone());
+        }
+        Ok(Value::Object(params))
+    }
+}
+
+impl ToolHandler for BoundTool {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn description(&self) -> &str {
+        self.inner.description()
+    }
+
+    fn schema(&self) -> Value {
+        self.hide_bound_fields(self.inner.schema())
+    }
+
+    fn try_schema(&self) -> Result<Value, serde_json::Error> {
+        self.inner.try_schema().map(|schema| self.hide_bound_fields(schema))
+    }
+
+    fn output_schema(&self) -> Option<Value> {
+        self.inner.output_schema()
+    }
+
+    fn params_type_name(&self) -> &'static str {
+        self.inner.params_type_name()
+    }
+
+    fn error_type_name(&self) -> &'static str {
+        self.inner.error_type_name()
+    }
+
+    fn name_arguments(&self, arguments: Value) -> Result<Value, RegistryError> {
+        match (arguments, self.inner.positional_fields()) {
+            // Positions refer to the fields that are still advertised
+            (arguments @ Value::Array(_), Some(fields)) => {
+                let unbound: Vec<&str> = fields
+                    .iter()
+                    .copied()
+                    .filter(|field| !self.defaults.contains_key(*field))
+                    .collect();
+                named_arguments(&self.name, self.inner.params_type_name(), &unbound, arguments)
+            }
+            (other, _) => Ok(other),
+        }
+    }
+
+    fn property_order(&self) -> Option<&'static [&'static str]> {
+        self.inner.property_order()
+    }
+
+    fn accepted_fields(&self) -> Option<&'static [&'static str]> {
+        self.inner.accepted_fields()
+    }
+
+    fn server_only_fields(&self) -> &'static [&'static str] {
+        self.inner.server_only_fields()
+    }
+
+    fn cost_hint(&self) -> CostHint {
+        self.inner.cost_hint()
+    }
+
+    fn is_cpu_bound(&self) -> bool {
+        self.inner.is_cpu_bound()
+    }
+
+    fn metadata(&self) -> HashMap<String, String> {
+        self.inner.metadata()
+    }
+
+    fn examples(&self) -> &[Value] {
+        &self.examples
+    }
+
+    fn is_terminal(&self) -> bool {
+        self.inner.is_terminal()
+    }
+
+    fn requires_init(&self) -> bool {
+        self.inner.requires_init()
+    }
+
+    fn init(&self) -> BoxFuture<'_, Result<(), BoxError>> {
+        self.inner.init()
+    }
+
+    fn call_json(&self, params: Value) -> BoxFuture<'_, Result<Value, BoxError>> {
+        match self.bind(params) {
+            Ok(params) => self.inner.call_json(params),
+            Err(e) => Box::pin(async move { Err(e) }),
+        }
+    }
+
+    fn call_json_with_progress(
+        &self,
+        params: Value,
+        progress: ProgressReporter,
+    ) -> BoxFuture<'_, Result<Value, BoxError>> {
+        match self.bind(params) {
+            Ok(params) => self.inner.call_json_with_progress(params, progress),
+            Err(e) => Box::pin(async move { Err(e) }),
+        }
+    }
+
+    fn call_raw(&self, params: &RawValue) -> BoxFuture<'_, Result<Value, BoxError>> {
+        // The defaults have to be merged into a `Value` anyway
+        match serde_json::from_str(params.get()) {
+            Ok(params) => self.call_json(params),
+            Err(e) => Box::pin(async move { Err(Box::new(e) as BoxError) }),
+        }
+    }
+
+    fn call_binary(&self, params: Value) -> BoxFuture<'_, Result<BinaryOutput, BoxError>> {
+        match self.bind(params) {
+            Ok(params) => self.inner.call_binary(params),
+            Err(e) => Box::pin(async move { Err(e) }),
+        }
+    }
+
+    fn is_available(&self) -> BoxFuture<'_, bool> {
+        self.inner.is_available()
+    }
+
+    fn responds_directly(&self) -> bool {
+        self.inner.responds_directly()
+    }
+}
@@ -0,0 +1,140 @@
+use crate::binary::BinaryOutput;
+use crate::cost::CostHint;
+use crate::error::RegistryError;
+use crate::middleware::call_tool;
+use crate::progress::ProgressReporter;
+use crate::traits::{BoxError, BoxFuture, ToolHandler};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// Runs a tool's JSON calls on tokio's blocking thread pool.
+///
+/// The registry builds one per call, as the end of the tool's middleware
+/// chain.
+pub(crate) struct BlockingTool {
+    inner: Arc<dyn ToolHandler>,
+}
+
+impl BlockingTool {
+    pub(crate) fn new(inner: Arc<dyn ToolHandler>) -> Self {
+        Self { inner }
+    }
+
+    fn offload(&self, params: Value, progress: Option<ProgressReporter>) -> BoxFuture<'static, Result<Value, BoxError>> {
+        let inner = Arc::clone(&self.inner);
+        Box::pin(async move {
+            let task = tokio::task::spawn_blocking(move || {
+                futures::executor::block_on(call_tool(inner.as_ref(), params, progress))
+            });
+            match task.await {
+                Ok(result) => result,
+                // Surface the tool's panic as if it had run inline
+                Err(e) if e.is_panic() => std::panic::resume_unwind(e.into_panic()),
+                Err(e) => Err(Box::new(e) as BoxError),
+            }
+        })
+    }
+}
+
+impl ToolHandler for BlockingTool {
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    fn description(&self) -> &str {
+        self.inner.description()
+    }
+
+    fn schema(&self) -> Value {
+        self.inner.schema()
+    }
+
+    fn try_schema(&self) -> Result<Value, serde_json::Error> {
+        self.inner.try_schema()
+    }
+
+    fn output_schema(&self) -> Option<Value> {
+        self.inner.output_schema()
+    }
+
+    fn params_type_name(&self) -> &'static str {
+        self.inner.params_type_name()
+    }
+
+    fn error_type_name(&self) -> &'static str {
+        self.inner.error_type_name()
+    }
+
+    fn positional_fields(&self) -> Option<&'static [&'static str]> {
+        self.inner.positional_fields()
+    }
+
+    fn name_arguments(&self, arguments: Value) -> Result<Value, RegistryError> {
+        self.inner.name_arguments(arguments)
+    }
+
+    fn property_order(&self) -> Option<&'static [&'static str]> {
+        self.inner.property_order()
+    }
+
+    fn accepted_fields(&self) -> Option<&'static [&'static str]> {
+        self.inner.accepted_fields()
+    }
+
+    fn server_only_fields(&self) -> &'static [&'static str] {
+        self.inner.server_only_fields()
+    }
+
+    fn cost_hint(&self) -> CostHint {
+        self.inner.cost_hint()
+    }
+
+    fn is_cpu_bound(&self) -> bool {
+        true
+    }
+
+    fn metadata(&self) -> HashMap<String, String> {
+        self.inner.metadata()
+    }
+
+    fn examples(&self) -> &[Value] {
+        self.inner.examples()
+    }
+
+    fn is_terminal(&self) -> bool {
+        self.inner.is_terminal()
+    }
+
+    fn requires_init(&self) -> bool {
+        self.inner.requires_init()
+    }
+
+    fn init(&self) -> BoxFuture<'_, Result<(), BoxError>> {
+        self.inner.init()
+    }
+
+    fn call_json(&self, params: Value) -> BoxFuture<'_, Result<Value, BoxError>> {
+        self.offload(params, None)
+    }
+
+    fn call_json_with_progress(
+        &self,
+        params: Value,
+        progress: ProgressReporter,
+    ) -> BoxFuture<'_, Result<Value, BoxError>> {
+        self.offload(params, Some(progress))
+    }
+
+    fn call_binary(&self, params: Value) -> BoxFuture<'_, Result<BinaryOutput, BoxError>> {
+        self.inner.call_binary(params)
+    }
+
+    fn is_available(&self) -> BoxFuture<'_, bool> {
+        self.inner.is_available()
+    }
+
+    fn responds_directly(&self) -> bool {
+        self.inner.responds_directly()
+    }
+}
@@ -0,0 +1,56 @@
+use crate::error::RegistryError;
+use std::collections::HashSet;
+
+/// What to do when several tool calls in one batch share a `call_id`.
+///
+/// Models occasionally emit duplicate ids, and the responses then can't be
+/// told apart. Set with
+/// [`ToolRegistry::with_duplicate_call_ids`](crate::ToolRegistry::with_duplicate_call_ids).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DuplicateCallIds {
+    /// Execute every call and return the responses with their ids unchanged.
+    #[default]
+    Allow,
+    /// Fail the batch with [`RegistryError::DuplicateCallId`] before executing anything.
+    Reject,
+    /// Give repeated ids a `#2`, `#3`, ... suffix in the responses.
+    Disambiguate,
+}
+
+impl DuplicateCallIds {
+    /// The `call_id` each response should carry, or `None` to keep every id as-is.
+    pub(crate) fn resolve<'a>(
+        self,
+        call_ids: impl Iterator<Item = &'a str> + Clone,
+    ) -> Result<Option<Vec<String>>, RegistryError> {
+        if self == DuplicateCallIds::Allow {
+            return Ok(None);
+        }
+
+        let mut seen = HashSet::new();
+        let Some(duplicate) = call_ids.clone().find(|id| !seen.insert(*id)) else {
+            return Ok(None);
+        };
+        if self == DuplicateCallIds::Reject {
+            return Err(RegistryError::DuplicateCallId {
+                call_id: duplicate.to_string(),
+            });
+        }
+
+        // Suffixed ids never collide with an id the model actually sent
+        let original: HashSet<&str> = call_ids.clone().collect();
+        let mut assigned = HashSet::new();
+        let resolved = call_ids
+            .map(|id| {
+                if assigned.insert(id.to_string()) {
+                    return id.to_string();
+                }
+                (2..)
+                    .map(|suffix| format!("{}#{}", id, suffix))
+                    .find(|candidate| !original.contains(candidate.as_str()) && assigned.insert(candidate.clone()))
+                    .expect("an unused suffix exists")
+            })
+            .collect();
+        Ok(Some(resolved))
+    }
+}
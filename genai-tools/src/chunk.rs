@@ -0,0 +1,102 @@
+use crate::registry::ToolRegistry;
+use crate::traits::BoxError;
+use crate::adapter::{ToolCallLike, ToolResponseLike};
+use genai::chat::ToolResponse;
+use serde::{Deserialize, Serialize};
+
+/// One part of a result split by [`ToolRegistry::execute_call_chunked`].
+///
+/// Each chunk is sent as the JSON content of its own `ToolResponse`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ResultChunk {
+    /// Shared by every chunk of one result: the id of the call it answers.
+    pub group: String,
+    /// Position of this chunk, starting at 0.
+    pub index: usize,
+    /// Number of chunks in the group.
+    pub total: usize,
+    /// This chunk's slice of the serialized result.
+    pub data: String,
+}
+
+impl ResultChunk {
+    /// Reassemble the serialized result from the chunk responses of one group.
+    ///
+    /// The responses may arrive in any order. Fails if a response is not a
+    /// chunk, if chunks from different groups are mixed, or if a chunk is
+    /// missing.
+    pub fn reassemble(responses: &[ToolResponse]) -> Result<String, BoxError> {
+        let mut chunks = responses
+            .iter()
+            .map(|response| serde_json::from_str::<ResultChunk>(response.content()))
+            .collect::<Result<Vec<_>, _>>()?;
+        chunks.sort_by_key(|chunk| chunk.index);
+
+        let Some(first) = chunks.first() else {
+            return Err("No chunks to reassemble".into());
+        };
+        let complete = chunks.len() == first.total
+            && chunks
+                .iter()
+                .enumerate()
+                .all(|(index, chunk)| chunk.index == index && chunk.group == first.group && chunk.total == first.total);
+        if !complete {
+            return Err(format!("Chunks of result '{}' are missing or mixed with another result", first.group).into());
+        }
+        Ok(chunks.into_iter().map(|chunk| chunk.data).collect())
+    }
+}
+
+impl ToolRegistry {
+    /// Execute a tool call and split its response content into chunks.
+    ///
+    /// For providers and frameworks that accept a tool result in several
+    /// parts, this delivers the full result where
+    /// [`with_max_result_chars`](Self::with_max_result_chars) would cut it,
+    /// so that limit is not applied here. The content is split into pieces of at most `chunk_size` characters,
+    /// each sent as a [`ResultChunk`] in its own `ToolResponse` with the call's
+    /// id. A result that fits produces a single chunk. Use
+    /// [`ResultChunk::reassemble`] to put the result back together.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `chunk_size` is 0.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let responses = registry.execute_call_chunked(&tool_call, 4_000).await?;
+    /// for response in responses {
+    ///     chat_req = chat_req.append_message(response);
+    /// }
+    /// ```
+    pub async fn execute_call_chunked<C>(&self, tool_call: &C, chunk_size: usize) -> Result<Vec<ToolResponse>, BoxError>
+    where
+        C: ToolCallLike + ?Sized,
+    {
+        assert!(chunk_size > 0, "chunk_size must be greater than 0");
+        // The whole result is delivered, however long
+        let response = self.execute_call_within(tool_call, None).await?;
+
+        let chars: Vec<char> = response.content().chars().collect();
+        let pieces: Vec<String> = if chars.is_empty() {
+            vec![String::new()]
+        } else {
+            chars.chunks(chunk_size).map(|piece| piece.iter().collect()).collect()
+        };
+        let total = pieces.len();
+        pieces
+            .into_iter()
+            .enumerate()
+            .map(|(index, data)| {
+                let chunk = ResultChunk {
+                    group: response.call_id().to_string(),
+                    index,
+                    total,
+                    data,
+                };
+                Ok(ToolResponse::from_parts(response.call_id().to_string(), serde_json::to_string(&chunk)?))
+            })
+            .collect()
+    }
+}
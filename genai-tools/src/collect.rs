@@ -0,0 +1,26 @@
+use crate::traits::ToolHandler;
+
+/// A tool constructor collected by `#[tool_function(collect)]`.
+///
+/// Tools marked with `collect` submit one of these to a global inventory at
+/// link time, across modules and crates. [`ToolRegistry::from_inventory`]
+/// builds a registry from every collected tool.
+///
+/// [`ToolRegistry::from_inventory`]: crate::ToolRegistry::from_inventory
+pub struct ToolRegistration {
+    constructor: fn() -> Box<dyn ToolHandler>,
+}
+
+impl ToolRegistration {
+    #[doc(hidden)]
+    pub const fn new(constructor: fn() -> Box<dyn ToolHandler>) -> Self {
+        Self { constructor }
+    }
+
+    /// Construct the collected tool.
+    pub fn build(&self) -> Box<dyn ToolHandler> {
+        (self.constructor)()
+    }
+}
+
+inventory::collect!(ToolRegistration);
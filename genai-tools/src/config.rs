@@ -0,0 +1,171 @@
+use crate::deadline::Timer;
+use crate::error::RegistryError;
+use crate::registry::ToolRegistry;
+use crate::rename::RenamedTool;
+use crate::suggest::closest_names;
+use crate::traits::{BoxError, ToolHandler};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::{Duration, Instant};
+
+/// Deployment-time selection and tuning of tools, for
+/// [`ToolRegistry::from_config`].
+///
+/// Deserializes from any serde format, e.g. this TOML:
+///
+/// ```toml
+/// [tools.get_weather]
+/// description = "Current weather for a city"
+/// timeout_ms = 5000
+///
+/// [tools.search_web]
+/// rate_limit = { max_calls = 10, per_secs = 60 }
+///
+/// [tools.add_numbers]
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RegistryConfig {
+    /// The tools to enable, by name. Tools left out are not registered.
+    #[serde(default)]
+    pub tools: BTreeMap<String, ToolConfig>,
+}
+
+/// Overrides for one tool in a [`RegistryConfig`]. Every field is optional.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ToolConfig {
+    /// The description to advertise instead of the tool's own.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// How long a call may run, in milliseconds, before it fails with
+    /// [`RegistryError::Timeout`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout_ms: Option<u64>,
+    /// How often the tool may be called.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rate_limit: Option<RateLimit>,
+}
+
+/// At most `max_calls` calls in any window of `per_secs` seconds. Calls over
+/// the limit fail with [`RegistryError::RateLimited`] without running.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RateLimit {
+    /// Calls allowed per window.
+    pub max_calls: u32,
+    /// The window length in seconds.
+    pub per_secs: u64,
+}
+
+impl ToolRegistry {
+    /// Build a registry from the tools enabled in `config`, taken from the
+    /// `available` handlers by name.
+    ///
+    /// Keeps which tools are deployed, and how they are tuned, out of the code
+    /// that implements them. Each configured tool is registered under its
+    /// config name with its overrides applied: the description through
+    /// [`set_description`](Self::set_description), and the timeout and rate
+    /// limit as middleware. Available tools the config doesn't mention are
+    /// dropped.
+    ///
+    /// Fails with [`RegistryError::ToolNotFound`], suggesting close matches,
+    /// if the config names a tool that isn't available. Registry options can
+    /// be chained onto the result.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let config: RegistryConfig = toml::from_str(&std::fs::read_to_string("tools.toml")?)?;
+    /// let available: HashMap<String, Box<dyn ToolHandler>> = HashMap::from([
+    ///     ("get_weather".to_string(), Box::new(get_weather_tool()) as Box<dyn ToolHandler>),
+    ///     ("search_web".to_string(), Box::new(search_web_tool())),
+    /// ]);
+    /// let registry = ToolRegistry::from_config(config, available)?.with_input_validation(true);
+    /// ```
+    pub fn from_config(
+        config: RegistryConfig,
+        mut available: HashMap<String, Box<dyn ToolHandler>>,
+    ) -> Result<Self, RegistryError> {
+        if let Some(name) = config.tools.keys().find(|name| !available.contains_key(*name)) {
+            return Err(RegistryError::ToolNotFound {
+                suggestions: closest_names(name, available.keys().map(String::as_str)),
+                tool: name.clone(),
+            });
+        }
+
+        let mut registry = ToolRegistry::new();
+        for (name, tool_config) in config.tools {
+            let handler = available.remove(&name).expect("checked above");
+            let handler = if handler.name() == name {
+                handler
+            } else {
+                Box::new(RenamedTool::new(name.clone(), handler))
+            };
+            registry.register_handler(handler);
+
+            if let Some(description) = tool_config.description {
+                registry.set_description(&name, description)?;
+            }
+            if let Some(limit) = tool_config.rate_limit {
+                let limiter = RateLimiter::new(limit);
+                registry.wrap_tool(&name, move |invocation, next| {
+                    let admitted = limiter.admit();
+                    Box::pin(async move {
+                        if !admitted {
+                            return Err(Box::new(RegistryError::RateLimited {
+                                tool: invocation.tool,
+                                max_calls: limit.max_calls,
+                                per: Duration::from_secs(limit.per_secs),
+                            }) as BoxError);
+                        }
+                        next.run(invocation).await
+                    })
+                })?;
+            }
+            if let Some(timeout_ms) = tool_config.timeout_ms {
+                let budget = Duration::from_millis(timeout_ms);
+                // One timer thread per tool, shared by all its calls
+                let timer = Arc::new(Timer::new());
+                registry.wrap_tool(&name, move |invocation, next| {
+                    let timer = Arc::clone(&timer);
+                    Box::pin(async move {
+                        let tool = invocation.tool.clone();
+                        timer.bound(&tool, budget, next.run(invocation)).await
+                    })
+                })?;
+            }
+        }
+        Ok(registry)
+    }
+}
+
+/// A sliding-window call counter.
+struct RateLimiter {
+    limit: RateLimit,
+    calls: Mutex<VecDeque<Instant>>,
+}
+
+impl RateLimiter {
+    fn new(limit: RateLimit) -> Self {
+        Self {
+            limit,
+            calls: Mutex::new(VecDeque::new()),
+        }
+    }
+
+    /// Record a call, or return `false` if it would exceed the limit.
+    fn admit(&self) -> bool {
+        let now = Instant::now();
+        let window = Duration::from_secs(self.limit.per_secs);
+        let mut calls = self.calls.lock().unwrap_or_else(PoisonError::into_inner);
+        while calls.front().is_some_and(|call| now.duration_since(*call) >= window) {
+            calls.pop_front();
+        }
+        if calls.len() >= self.limit.max_calls as usize {
+            return false;
+        }
+        calls.push_back(now);
+        true
+    }
+}
@@ -0,0 +1,19 @@
+use std::time::Duration;
+
+/// How expensive a tool is to call, as declared by the tool author.
+///
+/// This is scheduling metadata only; the registry executes every tool the
+/// same way. Set it with `#[tool_function(cost = "...")]`, which accepts
+/// `"cheap"`, `"expensive"`, or an estimated duration such as `"250ms"` or `"3s"`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum CostHint {
+    /// No cost has been declared.
+    #[default]
+    Unknown,
+    /// Fast enough to call freely, e.g. pure computation or a cache lookup.
+    Cheap,
+    /// Slow or costly, e.g. a long-running job or a paid external API.
+    Expensive,
+    /// Expected to take roughly this long.
+    Estimated(Duration),
+}
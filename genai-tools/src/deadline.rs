@@ -0,0 +1,170 @@
+use crate::error::RegistryError;
+use crate::traits::BoxError;
+use futures::channel::oneshot;
+use futures::future::{self, BoxFuture, Either, FutureExt, Shared};
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+use std::future::Future;
+use std::sync::{mpsc, Mutex, PoisonError};
+use std::time::{Duration, Instant};
+
+/// A timer that works under any executor.
+///
+/// A helper thread waits out the duration and then wakes every
+/// [`elapsed`](Self::elapsed) future. Dropping the `Deadline` stops the thread
+/// early, so short batches don't leave sleepers behind.
+pub(crate) struct Deadline {
+    fired: Shared<oneshot::Receiver<()>>,
+    _cancel: mpsc::Sender<()>,
+}
+
+impl Deadline {
+    pub(crate) fn after(duration: Duration) -> Self {
+        let (fire, fired) = oneshot::channel();
+        let (cancel, cancelled) = mpsc::channel::<()>();
+        std::thread::spawn(move || {
+            if let Err(mpsc::RecvTimeoutError::Timeout) = cancelled.recv_timeout(duration) {
+                let _ = fire.send(());
+            }
+        });
+        Self {
+            fired: fired.shared(),
+            _cancel: cancel,
+        }
+    }
+
+    /// Run `call`, failing with [`RegistryError::Timeout`] if the deadline
+    /// passes first. `budget` is only reported in the error.
+    pub(crate) async fn bound<T>(
+        &self,
+        tool: &str,
+        budget: Duration,
+        call: impl Future<Output = Result<T, BoxError>>,
+    ) -> Result<T, BoxError> {
+        match future::select(Box::pin(call), self.elapsed()).await {
+            Either::Left((result, _)) => result,
+            Either::Right(_) => Err(Box::new(RegistryError::Timeout {
+                tool: tool.to_string(),
+                budget,
+            })),
+        }
+    }
+
+    /// Resolves once the deadline passes; never resolves if it is cancelled first.
+    pub(crate) fn elapsed(&self) -> BoxFuture<'static, ()> {
+        let fired = self.fired.clone();
+        async move {
+            if fired.await.is_err() {
+                futures::future::pending::<()>().await
+            }
+        }
+        .boxed()
+    }
+}
+
+/// A timer for many calls, served by a single helper thread.
+///
+/// Unlike [`Deadline`], which spawns a thread per deadline, this suits a
+/// per-call timeout on a hot path. The thread is started on first use and
+/// exits when the `Timer` is dropped.
+pub(crate) struct Timer {
+    requests: Mutex<Option<mpsc::Sender<Alarm>>>,
+}
+
+/// A pending wake-up, ordered so the earliest is on top of the heap.
+struct Alarm {
+    at: Instant,
+    fire: oneshot::Sender<()>,
+}
+
+impl PartialEq for Alarm {
+    fn eq(&self, other: &Self) -> bool {
+        self.at == other.at
+    }
+}
+
+impl Eq for Alarm {}
+
+impl PartialOrd for Alarm {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Alarm {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.at.cmp(&self.at)
+    }
+}
+
+impl Timer {
+    pub(crate) fn new() -> Self {
+        Self {
+            requests: Mutex::new(None),
+        }
+    }
+
+    /// Run `call`, failing with [`RegistryError::Timeout`] if `budget` passes
+    /// first.
+    pub(crate) async fn bound<T>(
+        &self,
+        tool: &str,
+        budget: Duration,
+        call: impl Future<Output = Result<T, BoxError>>,
+    ) -> Result<T, BoxError> {
+        let fired = self.alarm(Instant::now() + budget);
+        let elapsed = async move {
+            if fired.await.is_err() {
+                futures::future::pending::<()>().await
+            }
+        };
+        match future::select(Box::pin(call), Box::pin(elapsed)).await {
+            Either::Left((result, _)) => result,
+            Either::Right(_) => Err(Box::new(RegistryError::Timeout {
+                tool: tool.to_string(),
+                budget,
+            })),
+        }
+    }
+
+    /// Resolves at `at`. Dropping the receiver lets the thread forget the alarm.
+    fn alarm(&self, at: Instant) -> oneshot::Receiver<()> {
+        let (fire, fired) = oneshot::channel();
+        let mut requests = self.requests.lock().unwrap_or_else(PoisonError::into_inner);
+        let requests = requests.get_or_insert_with(|| {
+            let (requests, received) = mpsc::channel();
+            std::thread::spawn(move || run_timer(received));
+            requests
+        });
+        let _ = requests.send(Alarm { at, fire });
+        fired
+    }
+}
+
+fn run_timer(requests: mpsc::Receiver<Alarm>) {
+    let mut alarms = BinaryHeap::new();
+    let mut pruned_len = 0;
+    loop {
+        let now = Instant::now();
+        while alarms.peek().is_some_and(|alarm: &Alarm| alarm.at <= now) {
+            if let Some(alarm) = alarms.pop() {
+                let _ = alarm.fire.send(());
+            }
+        }
+        // Calls that finished in time leave their alarms behind; drop them
+        // whenever the heap has doubled since the last sweep
+        if alarms.len() > 64 && alarms.len() >= 2 * pruned_len {
+            alarms.retain(|alarm| !alarm.fire.is_canceled());
+            pruned_len = alarms.len();
+        }
+        let request = match alarms.peek() {
+            Some(next) => requests.recv_timeout(next.at.saturating_duration_since(now)),
+            None => requests.recv().map_err(|_| mpsc::RecvTimeoutError::Disconnected),
+        };
+        match request {
+            Ok(alarm) => alarms.push(alarm),
+            Err(mpsc::RecvTimeoutError::Timeout) => {}
+            Err(mpsc::RecvTimeoutError::Disconnected) => return,
+        }
+    }
+}
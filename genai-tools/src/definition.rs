@@ -0,0 +1,116 @@
+use crate::error::RegistryError;
+use crate::registry::ToolRegistry;
+use crate::schema;
+use genai::chat::Tool;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+
+/// The advertised metadata of a tool, without its handler.
+///
+/// Definitions can be persisted (e.g. as a JSON manifest) and served later,
+/// for example by a tool-discovery service. Deserializing a definition only
+/// reconstructs this metadata: it can be advertised to a model with
+/// [`to_tool`](Self::to_tool), but not executed, since the handler is not part
+/// of it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ToolDefinition {
+    /// The tool name.
+    pub name: String,
+    /// The tool description.
+    pub description: String,
+    /// The parameter schema, as advertised by the registry.
+    pub schema: Value,
+    /// The output schema, if the tool advertises one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub output_schema: Option<Value>,
+    /// The tool's [metadata](crate::ToolHandler::metadata), ordered by key.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub metadata: BTreeMap<String, String>,
+    /// The tool's [example arguments](crate::ToolHandler::examples).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub examples: Vec<Value>,
+}
+
+impl ToolDefinition {
+    /// Convert the definition into a `genai` tool to send to a model.
+    pub fn to_tool(&self) -> Tool {
+        Tool::new(self.name.clone())
+            .with_description(self.description.clone())
+            .with_schema(self.schema.clone())
+    }
+}
+
+impl ToolRegistry {
+    /// Export every tool's definition, ordered by name.
+    ///
+    /// Schemas are exported as advertised, with any schema transform applied.
+    /// Fails with [`RegistryError::Schema`] if a tool's schema cannot be
+    /// generated.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let manifest = serde_json::to_string_pretty(&registry.export_definitions()?)?;
+    /// std::fs::write("tools.json", manifest)?;
+    ///
+    /// // Later, possibly in another process: advertise without the handlers
+    /// let definitions: Vec<ToolDefinition> = serde_json::from_str(&std::fs::read_to_string("tools.json")?)?;
+    /// let tools: Vec<Tool> = definitions.iter().map(ToolDefinition::to_tool).collect();
+    /// ```
+    pub fn export_definitions(&self) -> Result<Vec<ToolDefinition>, RegistryError> {
+        let mut definitions = self
+            .iter()
+            .map(|(name, handler)| {
+                Ok(ToolDefinition {
+                    name: self.advertised_name(name).to_string(),
+                    description: self.advertised_description(handler).to_string(),
+                    schema: self.advertised_schema(handler)?,
+                    output_schema: handler.output_schema(),
+                    metadata: handler.metadata().into_iter().collect(),
+                    examples: handler.examples().to_vec(),
+                })
+            })
+            .collect::<Result<Vec<_>, RegistryError>>()?;
+        definitions.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(definitions)
+    }
+
+    /// A short hash of the advertised tool catalog, as 16 hex digits.
+    ///
+    /// Covers every tool's name, description and canonicalized parameter
+    /// schema, so it changes whenever a tool is added, removed or changes its
+    /// contract, and stays the same across registration orders, processes and
+    /// crate builds. Clients that cache the tool list can compare fingerprints
+    /// to know when to refetch. A tool whose schema can't be generated is
+    /// hashed with a `null` schema.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// if registry.fingerprint() != cached.fingerprint {
+    ///     cached = Catalog { fingerprint: registry.fingerprint(), tools: registry.get_tools() };
+    /// }
+    /// ```
+    pub fn fingerprint(&self) -> String {
+        let mut tools: Vec<Value> = self
+            .iter()
+            .map(|(name, handler)| {
+                json!({
+                    "name": self.advertised_name(name),
+                    "description": self.advertised_description(handler),
+                    "parameters": self.advertised_schema(handler).unwrap_or(Value::Null),
+                })
+            })
+            .collect();
+        tools.sort_by(|a, b| a["name"].as_str().cmp(&b["name"].as_str()));
+        format!("{:016x}", fnv1a(schema::canonical_json(&Value::Array(tools)).as_bytes()))
+    }
+}
+
+/// 64-bit FNV-1a, which unlike `std`'s hashers is stable across releases.
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
@@ -0,0 +1,334 @@
+use crate::traits::BoxError;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::error::Error;
+use std::fmt;
+use std::time::Duration;
+
+/// A single JSON Schema violation found while validating a value.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SchemaViolation {
+    /// JSON pointer to the offending value (empty for the root).
+    pub pointer: String,
+    /// Human-readable description of the violation.
+    pub message: String,
+}
+
+impl fmt::Display for SchemaViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.pointer.is_empty() {
+            write!(f, "{}", self.message)
+        } else {
+            write!(f, "{}: {}", self.pointer, self.message)
+        }
+    }
+}
+
+/// Errors raised by the [`ToolRegistry`](crate::ToolRegistry) itself, as opposed
+/// to errors returned by the tool functions it executes.
+///
+/// Registry methods return these boxed as `Box<dyn Error + Send + Sync>`; use
+/// `downcast_ref::<RegistryError>()` to inspect them.
+#[derive(Debug)]
+pub enum RegistryError {
+    /// No tool with the requested name is registered (or visible).
+    ToolNotFound {
+        tool: String,
+        /// The registered names closest to `tool`, for a "did you mean" hint.
+        suggestions: Vec<String>,
+    },
+    /// Several tool calls in one batch share a `call_id`.
+    DuplicateCallId { call_id: String },
+    /// Two tools with the same name were registered where names must be unique.
+    DuplicateTool { tool: String },
+    /// A tool still has the placeholder description while
+    /// [`with_require_descriptions`](crate::ToolRegistry::with_require_descriptions) is on.
+    MissingDescription { tool: String },
+    /// A tool's parameter schema could not be generated.
+    Schema {
+        tool: String,
+        source: serde_json::Error,
+    },
+    /// The arguments supplied for a tool could not be deserialized into its params type.
+    InvalidArguments {
+        tool: String,
+        /// The Rust type name of the tool's parameters.
+        params_type: &'static str,
+        source: serde_json::Error,
+    },
+    /// The tool requires initialization and `init_all` has not completed for it.
+    NotInitialized { tool: String },
+    /// A tool's async initialization failed.
+    InitFailed { tool: String, source: BoxError },
+    /// The tool is registered but its availability guard currently rejects calls.
+    Unavailable { tool: String },
+    /// The arguments supplied for a tool do not match its parameter schema.
+    InputValidation {
+        tool: String,
+        violations: Vec<SchemaViolation>,
+    },
+    /// A tool produced a result that does not match its advertised output schema.
+    OutputValidation {
+        tool: String,
+        violations: Vec<SchemaViolation>,
+    },
+    /// A stage of a pipeline tool failed, short-circuiting the pipeline.
+    PipelineStage {
+        pipeline: String,
+        /// Zero-based position of the failing stage.
+        index: usize,
+        stage: String,
+        source: BoxError,
+    },
+    /// An agent loop was still receiving tool calls after its round limit.
+    RoundLimit { max_rounds: usize },
+    /// A call to a tool wrapping a sub-registry named no operation, or an unknown one.
+    UnknownOperation {
+        tool: String,
+        /// The requested operation, or `None` if the `op` field was missing.
+        operation: Option<String>,
+        /// The operations the tool supports, sorted.
+        valid: Vec<String>,
+    },
+    /// A tool call was still running when its time budget ran out.
+    Timeout { tool: String, budget: Duration },
+    /// A tool was called more often than its configured rate limit allows.
+    RateLimited {
+        tool: String,
+        max_calls: u32,
+        per: Duration,
+    },
+    /// Raw bytes were requested from a tool whose output isn't a
+    /// [`BinaryOutput`](crate::BinaryOutput).
+    NotBinary { tool: String },
+}
+
+impl fmt::Display for RegistryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RegistryError::ToolNotFound { tool, suggestions } => match suggestions.as_slice() {
+                [] => write!(f, "Tool '{}' not found in registry", tool),
+                [suggestion] => write!(f, "Tool '{}' not found; did you mean '{}'?", tool, suggestion),
+                [rest @ .., last] => {
+                    let rest: Vec<_> = rest.iter().map(|name| format!("'{}'", name)).collect();
+                    write!(f, "Tool '{}' not found; did you mean {} or '{}'?", tool, rest.join(", "), last)
+                }
+            },
+            RegistryError::DuplicateCallId { call_id } => {
+                write!(f, "Several tool calls share the call id '{}'", call_id)
+            }
+            RegistryError::DuplicateTool { tool } => {
+                write!(f, "Tool '{}' is registered more than once", tool)
+            }
+            RegistryError::MissingDescription { tool } => write!(
+                f,
+                "Tool '{}' has no description; add one with #[tool_function(description = \"...\")]",
+                tool
+            ),
+            RegistryError::Schema { tool, source } => {
+                write!(f, "Failed to generate schema for tool '{}': {}", tool, source)
+            }
+            RegistryError::InvalidArguments { tool, params_type, source } => write!(
+                f,
+                "Invalid arguments for tool '{}' (expected {}): {}",
+                tool, params_type, source
+            ),
+            RegistryError::NotInitialized { tool } => write!(
+                f,
+                "Tool '{}' has not been initialized; call ToolRegistry::init_all first",
+                tool
+            ),
+            RegistryError::InitFailed { tool, source } => {
+                write!(f, "Failed to initialize tool '{}': {}", tool, source)
+            }
+            RegistryError::Unavailable { tool } => {
+                write!(f, "Tool '{}' is currently unavailable", tool)
+            }
+            RegistryError::InputValidation { tool, violations } => {
+                write!(f, "Invalid arguments for tool '{}'", tool)?;
+                write_violations(f, violations)
+            }
+            RegistryError::OutputValidation { tool, violations } => {
+                write!(f, "Tool '{}' returned a result that does not match its output schema", tool)?;
+                write_violations(f, violations)
+            }
+            RegistryError::PipelineStage { pipeline, index, stage, source } => write!(
+                f,
+                "Pipeline '{}' failed at stage {} ('{}'): {}",
+                pipeline, index, stage, source
+            ),
+            RegistryError::RoundLimit { max_rounds } => write!(
+                f,
+                "Model was still calling tools after {} rounds",
+                max_rounds
+            ),
+            RegistryError::UnknownOperation { tool, operation: Some(operation), valid } => write!(
+                f,
+                "Tool '{}' has no operation '{}'; valid operations: {}",
+                tool,
+                operation,
+                valid.join(", ")
+            ),
+            RegistryError::UnknownOperation { tool, operation: None, valid } => write!(
+                f,
+                "Tool '{}' needs an 'op' field naming one of: {}",
+                tool,
+                valid.join(", ")
+            ),
+            RegistryError::Timeout { tool, budget } => {
+                write!(f, "Tool '{}' did not finish within {:?}", tool, budget)
+            }
+            RegistryError::RateLimited { tool, max_calls, per } => write!(
+                f,
+                "Tool '{}' was called more than {} times within {:?}; try again later",
+                tool, max_calls, per
+            ),
+            RegistryError::NotBinary { tool } => {
+                write!(f, "Tool '{}' does not return binary output", tool)
+            }
+        }
+    }
+}
+
+impl Error for RegistryError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            RegistryError::Schema { source, .. } => Some(source),
+            RegistryError::InvalidArguments { source, .. } => Some(source),
+            RegistryError::InitFailed { source, .. } => Some(source.as_ref()),
+            RegistryError::PipelineStage { source, .. } => Some(source.as_ref()),
+            _ => None,
+        }
+    }
+}
+
+fn write_violations(f: &mut fmt::Formatter<'_>, violations: &[SchemaViolation]) -> fmt::Result {
+    for violation in violations {
+        write!(f, "\n  - {}", violation)?;
+    }
+    Ok(())
+}
+
+/// A machine-readable description of a failed tool call, for the model.
+///
+/// Used by [`ToolRegistry::execute_call_as_genai`](crate::ToolRegistry::execute_call_as_genai),
+/// which reports failures as response content shaped like:
+///
+/// ```json
+/// {"error": {"type": "invalid_arguments", "message": "...", "recoverable": true}}
+/// ```
+///
+/// - `type` is a stable snake_case category: `tool_not_found`,
+///   `invalid_arguments`, `unavailable`, `timeout`, `not_initialized`,
+///   `invalid_output`, `registry_error`, or `tool_error` for errors returned by
+///   the tool itself.
+/// - `message` is the error's `Display` output.
+/// - `recoverable` says whether calling again can succeed, e.g. with corrected
+///   arguments or later on. Tool errors are assumed to be recoverable.
+///
+/// Prompt authors can describe this shape to the model so it can tell failed
+/// calls apart from results and decide whether to retry.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ToolErrorPayload {
+    /// Stable error category.
+    #[serde(rename = "type")]
+    pub kind: String,
+    /// Human-readable description of the failure.
+    pub message: String,
+    /// Whether calling the tool again can succeed.
+    pub recoverable: bool,
+}
+
+impl ToolErrorPayload {
+    /// Map an error returned by the registry or by a tool.
+    ///
+    /// Pipeline stage failures take the category of the failing stage.
+    pub fn from_error(error: &(dyn Error + 'static)) -> Self {
+        let (kind, recoverable) = match error.downcast_ref::<RegistryError>() {
+            Some(RegistryError::PipelineStage { source, .. }) => {
+                let stage = Self::from_error(source.as_ref());
+                (stage.kind, stage.recoverable)
+            }
+            Some(registry_error) => {
+                let (kind, recoverable) = registry_error.category();
+                (kind.to_string(), recoverable)
+            }
+            None => ("tool_error".to_string(), true),
+        };
+        Self {
+            kind,
+            message: error.to_string(),
+            recoverable,
+        }
+    }
+
+    /// Wrap the payload in the `{"error": ...}` envelope.
+    pub fn to_envelope(&self) -> Value {
+        json!({ "error": self })
+    }
+
+    /// Recognize an envelope produced by [`to_envelope`](Self::to_envelope).
+    ///
+    /// Returns `None` unless `value` is an object whose only key is `error`
+    /// holding a complete payload.
+    pub fn from_envelope(value: &Value) -> Option<Self> {
+        let object = value.as_object().filter(|object| object.len() == 1)?;
+        Self::deserialize(object.get("error")?).ok()
+    }
+}
+
+impl RegistryError {
+    /// The error's envelope category and whether calling again can succeed.
+    fn category(&self) -> (&'static str, bool) {
+        match self {
+            RegistryError::ToolNotFound { .. } => ("tool_not_found", true),
+            RegistryError::InvalidArguments { .. }
+            | RegistryError::InputValidation { .. }
+            | RegistryError::UnknownOperation { .. } => {
+                ("invalid_arguments", true)
+            }
+            RegistryError::Unavailable { .. } | RegistryError::RateLimited { .. } => ("unavailable", true),
+            RegistryError::Timeout { .. } => ("timeout", true),
+            RegistryError::NotInitialized { .. } | RegistryError::InitFailed { .. } => {
+                ("not_initialized", false)
+            }
+            RegistryError::OutputValidation { .. } => ("invalid_output", false),
+            RegistryError::DuplicateCallId { .. }
+            | RegistryError::DuplicateTool { .. }
+            | RegistryError::MissingDescription { .. }
+            | RegistryError::NotBinary { .. }
+            | RegistryError::Schema { .. }
+            | RegistryError::PipelineStage { .. }
+            | RegistryError::RoundLimit { .. } => ("registry_error", false),
+        }
+    }
+}
+
+/// The failure of [`ToolRegistry::execute_call_result`](crate::ToolRegistry::execute_call_result).
+#[derive(Debug)]
+pub enum ToolCallError {
+    /// The call failed and was reported as an error envelope.
+    Tool(ToolErrorPayload),
+    /// The call succeeded, but its result is not a valid `T`.
+    Decode { tool: String, source: serde_json::Error },
+}
+
+impl fmt::Display for ToolCallError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ToolCallError::Tool(payload) => write!(f, "{} ({})", payload.message, payload.kind),
+            ToolCallError::Decode { tool, source } => {
+                write!(f, "Failed to decode the result of tool '{}': {}", tool, source)
+            }
+        }
+    }
+}
+
+impl Error for ToolCallError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ToolCallError::Decode { source, .. } => Some(source),
+            ToolCallError::Tool(_) => None,
+        }
+    }
+}
@@ -0,0 +1,86 @@
+use crate::adapter::ToolResponseLike;
+use genai::chat::ToolResponse;
+use serde::ser::SerializeMap;
+use serde::{Serialize, Serializer};
+use serde_json::Value;
+
+const STATUS_KEY: &str = "status";
+const REQUEST_KEY: &str = "request";
+const NEED_MORE_INPUT: &str = "need_more_input";
+
+/// A control-flow signal a tool can return instead of a plain result.
+///
+/// Use `ToolFlow<T>` as a tool's output type when the tool sometimes needs to
+/// be called again with more data. `Done(result)` serializes exactly like
+/// `result`, so the common path looks the same as an ordinary tool.
+/// `NeedMoreInput(request)` serializes to an envelope the model can read:
+///
+/// ```json
+/// {"status": "need_more_input", "request": <request>}
+/// ```
+///
+/// Callers can tell the two apart with [`ToolFlow::from_response`].
+///
+/// # Example
+///
+/// ```ignore
+/// #[tool_function(description = "Book a table")]
+/// pub async fn book_table(params: BookingParams) -> Result<ToolFlow<Booking>, BookingError> {
+///     match params.time {
+///         None => Ok(ToolFlow::NeedMoreInput(json!({"missing": ["time"]}))),
+///         Some(time) => Ok(ToolFlow::Done(book(time).await?)),
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub enum ToolFlow<T = Value> {
+    /// The tool finished with a final result.
+    Done(T),
+    /// The tool wants to be called again; the value describes what it needs.
+    NeedMoreInput(Value),
+}
+
+impl<T> ToolFlow<T> {
+    /// Returns `true` if the tool asked to be called again.
+    pub fn needs_more_input(&self) -> bool {
+        matches!(self, ToolFlow::NeedMoreInput(_))
+    }
+}
+
+impl ToolFlow<Value> {
+    /// Interpret a tool result value, recognizing the `need_more_input` envelope.
+    pub fn from_value(value: Value) -> Self {
+        if let Value::Object(map) = &value {
+            if map.len() == 2 && map.get(STATUS_KEY).and_then(Value::as_str) == Some(NEED_MORE_INPUT) {
+                if let Some(request) = map.get(REQUEST_KEY) {
+                    return ToolFlow::NeedMoreInput(request.clone());
+                }
+            }
+        }
+        ToolFlow::Done(value)
+    }
+
+    /// Interpret the content of a `ToolResponse` produced by the registry.
+    ///
+    /// Content that is not valid JSON is returned as `Done` with a string value.
+    pub fn from_response(response: &ToolResponse) -> Self {
+        match serde_json::from_str(response.content()) {
+            Ok(value) => Self::from_value(value),
+            Err(_) => ToolFlow::Done(Value::String(response.content().to_string())),
+        }
+    }
+}
+
+impl<T: Serialize> Serialize for ToolFlow<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            ToolFlow::Done(result) => result.serialize(serializer),
+            ToolFlow::NeedMoreInput(request) => {
+                let mut map = serializer.serialize_map(Some(2))?;
+                map.serialize_entry(STATUS_KEY, NEED_MORE_INPUT)?;
+                map.serialize_entry(REQUEST_KEY, request)?;
+                map.end()
+            }
+        }
+    }
+}
@@ -0,0 +1,198 @@
+use crate::error::RegistryError;
+use crate::registry::ToolRegistry;
+use crate::schema;
+use serde_json::{json, Map, Value};
+
+/// Keywords of Gemini's OpenAPI-based schema subset that are passed through.
+const SUPPORTED_KEYWORDS: &[&str] = &[
+    "type",
+    "format",
+    "title",
+    "description",
+    "nullable",
+    "enum",
+    "items",
+    "minItems",
+    "maxItems",
+    "properties",
+    "required",
+    "propertyOrdering",
+    "minProperties",
+    "maxProperties",
+    "minimum",
+    "maximum",
+    "minLength",
+    "maxLength",
+    "pattern",
+    "anyOf",
+];
+
+impl ToolRegistry {
+    /// Describe every tool as a Gemini function declaration, ordered by name.
+    ///
+    /// Each declaration is `{"name", "description", "parameters"}`, ready for
+    /// the `functionDeclarations` of a Gemini request. Gemini accepts only a
+    /// subset of JSON Schema, so the advertised schemas are rewritten:
+    ///
+    /// - `$ref`s are inlined and `$schema`/`$defs` dropped. Gemini can't
+    ///   express recursion, so a reference that closes a cycle becomes a plain
+    ///   `object`.
+    /// - `null` alternatives (`["string", "null"]`, `anyOf: [T, null]`) become
+    ///   `nullable: true`, and `oneOf` becomes `anyOf`.
+    /// - `const` becomes a one-value `enum`; enums of anything but strings are
+    ///   dropped, as are formats Gemini doesn't know and other unsupported
+    ///   keywords.
+    /// - Tools without parameters get no `parameters`, since Gemini rejects
+    ///   objects without properties.
+    ///
+    /// Fails with [`RegistryError::Schema`] if a tool's schema cannot be generated.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let body = json!({
+    ///     "contents": contents,
+    ///     "tools": [{ "functionDeclarations": registry.to_gemini_tools()? }],
+    /// });
+    /// ```
+    pub fn to_gemini_tools(&self) -> Result<Vec<Value>, RegistryError> {
+        let mut declarations = self
+            .iter()
+            .map(|(name, handler)| {
+                let mut parameters = self.advertised_schema(handler)?;
+                schema::inline_refs(&mut parameters);
+                let parameters = gemini_schema(&parameters);
+
+                let mut declaration = json!({
+                    "name": self.advertised_name(name),
+                    "description": self.advertised_description(handler),
+                });
+                let has_properties = parameters
+                    .get("properties")
+                    .and_then(Value::as_object)
+                    .is_some_and(|properties| !properties.is_empty());
+                if has_properties {
+                    declaration["parameters"] = parameters;
+                }
+                Ok(declaration)
+            })
+            .collect::<Result<Vec<_>, RegistryError>>()?;
+        declarations.sort_by(|a, b| a["name"].as_str().cmp(&b["name"].as_str()));
+        Ok(declarations)
+    }
+}
+
+/// Rewrite a JSON Schema into Gemini's subset.
+fn gemini_schema(schema: &Value) -> Value {
+    let Value::Object(object) = schema else {
+        return json!({});
+    };
+    // References left after inlining close a cycle
+    if object.contains_key("$ref") {
+        let mut replacement = json!({ "type": "object" });
+        if let Some(description) = object.get("description") {
+            replacement["description"] = description.clone();
+        }
+        return replacement;
+    }
+
+    let mut object = object.clone();
+    if let Some(Value::Array(parts)) = object.remove("allOf") {
+        for part in parts.iter().filter_map(Value::as_object) {
+            for (key, value) in part {
+                object.entry(key.clone()).or_insert_with(|| value.clone());
+            }
+        }
+    }
+    if let Some(value) = object.remove("const") {
+        object.insert("enum".to_string(), json!([value]));
+    }
+
+    let mut out = Map::new();
+    let mut nullable = false;
+
+    match object.remove("type") {
+        Some(Value::Array(kinds)) => {
+            let count = kinds.len();
+            let kinds: Vec<Value> = kinds.into_iter().filter(|kind| kind != "null").collect();
+            nullable = kinds.len() < count;
+            match kinds.as_slice() {
+                [kind] => {
+                    out.insert("type".to_string(), kind.clone());
+                }
+                kinds => {
+                    let variants = kinds.iter().map(|kind| json!({ "type": kind })).collect();
+                    out.insert("anyOf".to_string(), Value::Array(variants));
+                }
+            }
+        }
+        Some(kind) => {
+            out.insert("type".to_string(), kind);
+        }
+        None if object.contains_key("properties") => {
+            out.insert("type".to_string(), json!("object"));
+        }
+        None => {}
+    }
+
+    let variants = object.remove("anyOf").or_else(|| object.remove("oneOf"));
+    if let Some(Value::Array(variants)) = variants {
+        let is_null = |variant: &Value| variant.get("type").is_some_and(|kind| kind == "null");
+        nullable |= variants.iter().any(is_null);
+        let mut variants: Vec<Value> = variants.iter().filter(|variant| !is_null(variant)).map(gemini_schema).collect();
+        if variants.len() == 1 {
+            // A single alternative is the schema itself, e.g. `Option<Struct>`
+            if let Value::Object(variant) = variants.remove(0) {
+                for (key, value) in variant {
+                    out.entry(key).or_insert(value);
+                }
+            }
+        } else if variants.iter().all(|variant| variant.get("enum").is_some()) {
+            // Documented unit enums come out of schemars as a `oneOf` of consts
+            let values = variants.iter().filter_map(|variant| variant["enum"].as_array()).flatten().cloned();
+            out.insert("enum".to_string(), Value::Array(values.collect()));
+        } else if !variants.is_empty() {
+            out.insert("anyOf".to_string(), Value::Array(variants));
+        }
+    }
+
+    for (key, value) in object {
+        if !SUPPORTED_KEYWORDS.contains(&key.as_str()) || out.contains_key(&key) {
+            continue;
+        }
+        let value = match key.as_str() {
+            "properties" => match value {
+                Value::Object(properties) => Value::Object(
+                    properties
+                        .iter()
+                        .map(|(name, property)| (name.clone(), gemini_schema(property)))
+                        .collect(),
+                ),
+                _ => continue,
+            },
+            "items" => gemini_schema(&value),
+            "enum" if !value.as_array().is_some_and(|values| values.iter().all(Value::is_string)) => continue,
+            _ => value,
+        };
+        out.insert(key, value);
+    }
+
+    if let Some(Value::String(format)) = out.get("format") {
+        let supported = match out.get("type").and_then(Value::as_str) {
+            Some("string") => ["enum", "date-time"].contains(&format.as_str()),
+            Some("integer") => ["int32", "int64"].contains(&format.as_str()),
+            Some("number") => ["float", "double"].contains(&format.as_str()),
+            _ => false,
+        };
+        if !supported {
+            out.remove("format");
+        }
+    }
+    if out.contains_key("enum") {
+        out.insert("type".to_string(), json!("string"));
+    }
+    if nullable {
+        out.insert("nullable".to_string(), Value::Bool(true));
+    }
+    Value::Object(out)
+}
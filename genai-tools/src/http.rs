@@ -0,0 +1,153 @@
+use crate::traits::{BoxError, BoxFuture, ToolHandler};
+use reqwest::header::CONTENT_TYPE;
+use reqwest::{Client, Method, RequestBuilder};
+use serde_json::value::RawValue;
+use serde_json::Value;
+use std::error::Error;
+use std::fmt;
+
+/// A tool that proxies calls to a remote HTTP endpoint.
+///
+/// The tool's name, description and parameter schema are supplied at runtime
+/// rather than derived from Rust types. Each call sends the arguments as the
+/// JSON request body and returns the JSON response body as the result. A
+/// non-JSON response body is returned as a JSON string.
+///
+/// Requires the `http` feature.
+///
+/// # Example
+///
+/// ```ignore
+/// let mut registry = ToolRegistry::new();
+/// registry.register_http_tool(
+///     "lookup_order",
+///     "Look up an order by id",
+///     json!({"type": "object", "properties": {"id": {"type": "string"}}, "required": ["id"]}),
+///     "https://orders.internal/lookup",
+///     HttpMethod::POST,
+/// );
+/// ```
+pub struct HttpTool {
+    name: String,
+    description: String,
+    schema: Value,
+    url: String,
+    method: Method,
+    client: Client,
+}
+
+impl HttpTool {
+    /// Create a new HTTP tool using a default `reqwest::Client`.
+    pub fn new(
+        name: impl Into<String>,
+        description: impl Into<String>,
+        schema: Value,
+        url: impl Into<String>,
+        method: Method,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            description: description.into(),
+            schema,
+            url: url.into(),
+            method,
+            client: Client::new(),
+        }
+    }
+
+    /// Use the given client, e.g. to share a connection pool or set default headers.
+    pub fn with_client(mut self, client: Client) -> Self {
+        self.client = client;
+        self
+    }
+
+    async fn send(&self, request: RequestBuilder) -> Result<Value, BoxError> {
+        let response = request.send().await?;
+
+        let status = response.status();
+        let text = response.text().await?;
+        let body = if text.is_empty() {
+            Value::Null
+        } else {
+            serde_json::from_str(&text).unwrap_or(Value::String(text))
+        };
+
+        if !status.is_success() {
+            return Err(Box::new(HttpToolError {
+                tool: self.name.clone(),
+                status: status.as_u16(),
+                body,
+            }));
+        }
+
+        Ok(body)
+    }
+}
+
+impl ToolHandler for HttpTool {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn description(&self) -> &str {
+        &self.description
+    }
+
+    fn schema(&self) -> Value {
+        self.schema.clone()
+    }
+
+    fn try_schema(&self) -> Result<Value, serde_json::Error> {
+        Ok(self.schema.clone())
+    }
+
+    fn output_schema(&self) -> Option<Value> {
+        None
+    }
+
+    fn requires_init(&self) -> bool {
+        false
+    }
+
+    fn init(&self) -> BoxFuture<'_, Result<(), BoxError>> {
+        Box::pin(async { Ok(()) })
+    }
+
+    fn call_json(&self, params: Value) -> BoxFuture<'_, Result<Value, BoxError>> {
+        let request = self.client.request(self.method.clone(), &self.url).json(&params);
+        Box::pin(self.send(request))
+    }
+
+    fn call_raw(&self, params: &RawValue) -> BoxFuture<'_, Result<Value, BoxError>> {
+        let request = self
+            .client
+            .request(self.method.clone(), &self.url)
+            .header(CONTENT_TYPE, "application/json")
+            .body(params.get().to_owned());
+        Box::pin(self.send(request))
+    }
+}
+
+/// Error returned when a remote tool endpoint responds with a non-2xx status.
+#[derive(Debug)]
+pub struct HttpToolError {
+    /// The name of the tool that was called.
+    pub tool: String,
+    /// The HTTP status code of the response.
+    pub status: u16,
+    /// The response body, parsed as JSON when possible and otherwise as a string.
+    pub body: Value,
+}
+
+impl fmt::Display for HttpToolError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Remote tool '{}' responded with HTTP {}", self.tool, self.status)?;
+        match &self.body {
+            Value::Null => Ok(()),
+            Value::String(text) => write!(f, ": {}", text),
+            body => write!(f, ": {}", body),
+        }
+    }
+}
+
+impl Error for HttpToolError {}
@@ -0,0 +1,133 @@
+use crate::adapter::{ToolCallLike, ToolResponseLike};
+use crate::error::{RegistryError, ToolErrorPayload};
+use crate::registry::ToolRegistry;
+use genai::chat::ToolCall;
+use serde_json::{json, Map, Value};
+
+/// The request is not a valid JSON-RPC 2.0 request object.
+const INVALID_REQUEST: i64 = -32600;
+/// No tool with the requested name is registered.
+const METHOD_NOT_FOUND: i64 = -32601;
+/// The arguments do not match the tool's parameters.
+const INVALID_PARAMS: i64 = -32602;
+/// The registry failed to run the tool.
+const INTERNAL_ERROR: i64 = -32603;
+/// The tool itself returned an error.
+const TOOL_ERROR: i64 = -32000;
+
+impl ToolRegistry {
+    /// Describe every tool as a JSON-RPC method, ordered by name.
+    ///
+    /// Each descriptor has the tool's `name`, `description`, and advertised
+    /// parameter schema under `params`. Fails with [`RegistryError::Schema`] if
+    /// any tool's schema cannot be generated.
+    pub fn to_jsonrpc_methods(&self) -> Result<Vec<Value>, RegistryError> {
+        let mut tools = self.try_get_tools()?;
+        tools.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(tools
+            .into_iter()
+            .map(|tool| {
+                json!({
+                    "name": tool.name,
+                    "description": tool.description,
+                    "params": tool.schema,
+                })
+            })
+            .collect())
+    }
+
+    /// Handle a JSON-RPC 2.0 request by calling the tool named by its `method`.
+    ///
+    /// `params` may be an object or, for tools that accept positional
+    /// arguments, an array; it defaults to `{}` when omitted. The call goes
+    /// through [`execute_call`](Self::execute_call), so validation and
+    /// middleware apply, and the tool's result is returned as `result`.
+    ///
+    /// Failures use the standard error codes: `-32600` for a malformed request,
+    /// `-32601` for an unknown tool, `-32602` for invalid arguments, `-32603`
+    /// for other registry errors, and `-32000` for errors returned by the tool.
+    /// The error's `data` holds the [`ToolErrorPayload`].
+    ///
+    /// Batch requests (arrays) are handled call by call. Returns `None` for
+    /// notifications (requests without an `id`) and for batches made only of
+    /// notifications, which get no response.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let request: Value = serde_json::from_slice(&body)?;
+    /// if let Some(response) = registry.handle_jsonrpc(request).await {
+    ///     return Ok(Json(response));
+    /// }
+    /// ```
+    pub async fn handle_jsonrpc(&self, request: Value) -> Option<Value> {
+        match request {
+            Value::Array(requests) if !requests.is_empty() => {
+                let responses = futures::future::join_all(requests.into_iter().map(|request| self.handle_single(request)));
+                let responses: Vec<Value> = responses.await.into_iter().flatten().collect();
+                (!responses.is_empty()).then_some(Value::Array(responses))
+            }
+            request => self.handle_single(request).await,
+        }
+    }
+
+    async fn handle_single(&self, request: Value) -> Option<Value> {
+        let Value::Object(mut request) = request else {
+            return Some(invalid_request(Value::Null, "Request must be an object"));
+        };
+        let id = request.remove("id");
+        let notification = id.is_none();
+        let id = id.unwrap_or(Value::Null);
+
+        if request.get("jsonrpc") != Some(&json!("2.0")) {
+            return Some(invalid_request(id, "Missing or unsupported \"jsonrpc\" version"));
+        }
+        let Some(Value::String(method)) = request.remove("method") else {
+            return Some(invalid_request(id, "Missing \"method\""));
+        };
+        let params = match request.remove("params") {
+            None => Value::Object(Map::new()),
+            Some(params @ (Value::Object(_) | Value::Array(_))) => params,
+            Some(_) => return Some(invalid_request(id, "\"params\" must be an object or an array")),
+        };
+
+        let call_id = match &id {
+            Value::String(id) => id.clone(),
+            other => other.to_string(),
+        };
+        let call = ToolCall::from_parts(call_id, method, params);
+        let response = match self.execute_call(&call).await {
+            Ok(response) => {
+                // Non-JSON content (e.g. cut by truncation) is returned as a string
+                let content = response.content();
+                let result = serde_json::from_str(content).unwrap_or_else(|_| Value::String(content.to_string()));
+                json!({ "jsonrpc": "2.0", "result": result, "id": id })
+            }
+            Err(error) => {
+                let code = match error.downcast_ref::<RegistryError>() {
+                    Some(RegistryError::ToolNotFound { .. }) => METHOD_NOT_FOUND,
+                    Some(RegistryError::InvalidArguments { .. } | RegistryError::InputValidation { .. }) => {
+                        INVALID_PARAMS
+                    }
+                    Some(_) => INTERNAL_ERROR,
+                    None => TOOL_ERROR,
+                };
+                let payload = ToolErrorPayload::from_error(error.as_ref());
+                error_response(id, code, payload.message.clone(), json!(payload))
+            }
+        };
+        (!notification).then_some(response)
+    }
+}
+
+fn invalid_request(id: Value, message: &str) -> Value {
+    error_response(id, INVALID_REQUEST, format!("Invalid Request: {}", message), Value::Null)
+}
+
+fn error_response(id: Value, code: i64, message: String, data: Value) -> Value {
+    let mut error = json!({ "code": code, "message": message });
+    if !data.is_null() {
+        error["data"] = data;
+    }
+    json!({ "jsonrpc": "2.0", "error": error, "id": id })
+}
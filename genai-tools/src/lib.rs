@@ -41,15 +41,120 @@
 //! let mut registry = ToolRegistry::new();
 //! registry.register_function(get_weather);
 //! ```
+//!
+//! ## Async runtime
+//!
+//! Nothing here depends on a particular executor. Tool execution returns boxed
+//! `Send` futures, and batch helpers like
+//! [`ToolRegistry::execute_calls`] poll calls concurrently within the calling
+//! task using `futures` instead of spawning, so any executor can drive them.
+//! Only two optional features bring in tokio: `http`, through reqwest, and
+//! `blocking`, which runs CPU-bound tools on tokio's blocking thread pool and
+//! so must be used from within a tokio runtime.
 
+mod adapter;
+mod agent;
+mod binary;
+mod bind;
+#[cfg(feature = "blocking")]
+mod blocking;
+mod call_ids;
+mod chunk;
+mod collect;
+mod config;
+mod cost;
+mod deadline;
+mod definition;
+mod error;
+mod flow;
+mod gemini;
+mod guard;
+#[cfg(feature = "http")]
+mod http;
+mod jsonrpc;
+mod maybe;
+mod middleware;
+mod ndjson;
+mod openapi;
+mod pipeline;
+mod positional;
+mod precision;
+mod progress;
+mod redact;
 mod registry;
+mod rename;
+mod response;
+pub mod schema;
+mod schema_override;
+mod shared;
+mod shared_registry;
+mod sub_registry;
+mod suggest;
+#[cfg(feature = "testing")]
+pub mod testing;
 mod traits;
+mod truncate;
+mod validation;
+mod view;
+mod warnings;
 
-pub use registry::ToolRegistry;
+pub use adapter::{ToolCallLike, ToolResponseLike};
+pub use agent::AgentRun;
+pub use binary::BinaryOutput;
+pub use bytes::Bytes;
+pub use call_ids::DuplicateCallIds;
+pub use chunk::ResultChunk;
+pub use collect::ToolRegistration;
+pub use config::{RateLimit, RegistryConfig, ToolConfig};
+pub use cost::CostHint;
+pub use definition::ToolDefinition;
+pub use error::{RegistryError, SchemaViolation, ToolCallError, ToolErrorPayload};
+pub use flow::ToolFlow;
+#[cfg(feature = "http")]
+pub use http::{HttpTool, HttpToolError};
+#[cfg(feature = "http")]
+pub use reqwest::Method as HttpMethod;
+pub use maybe::Maybe;
+pub use middleware::{Next, ToolInvocation};
+pub use progress::{Progress, ProgressReporter};
+pub use redact::RedactionPolicy;
+pub use registry::{ToolIter, ToolRegistry};
+pub use shared_registry::SharedToolRegistry;
 pub use traits::*;
+pub use view::RegistryView;
+pub use warnings::WithWarnings;
 
 // Re-export the proc macro
-pub use genai_tools_macros::tool_function;
+pub use genai_tools_macros::{tool_function, tool_set};
+
+// Dependencies referenced by macro-generated code, so users don't need them in scope.
+#[doc(hidden)]
+pub mod __private {
+    pub use inventory;
+    pub use schemars;
+    pub use serde;
+    pub use serde_json;
+}
+
+// Expands to the value of `is_cpu_bound` for `#[tool_function(cpu_bound)]`,
+// which is only honored with the `blocking` feature.
+#[cfg(feature = "blocking")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __cpu_bound {
+    () => {
+        true
+    };
+}
+
+#[cfg(not(feature = "blocking"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __cpu_bound {
+    () => {
+        compile_error!("`#[tool_function(cpu_bound)]` requires the `blocking` feature of genai-tools")
+    };
+}
 
 #[cfg(test)]
 mod tests {
@@ -185,7 +290,7 @@ mod tests {
         
         let result = simple_tool_impl(params).await.unwrap();
         
-        assert_eq!(result.success, true);
+        assert!(result.success);
         assert_eq!(result.message, "Processed: Hello, World!");
     }
 
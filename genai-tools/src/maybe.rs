@@ -0,0 +1,129 @@
+use schemars::{JsonSchema, Schema, SchemaGenerator};
+use serde::de::{Deserialize, Deserializer, Error as _};
+use serde::ser::{Serialize, Serializer};
+use serde_json::Value;
+use std::borrow::Cow;
+
+/// An argument that the model may omit, set to `null`, or set to a value,
+/// keeping the three apart.
+///
+/// serde reads both an absent `Option` field and an explicit `null` as
+/// `None`. Patch-style tools need the difference: an omitted field keeps its
+/// current value, while `null` clears it. Fields of this type must be marked
+/// `#[serde(default)]`, which is what makes an omitted field
+/// [`Absent`](Self::Absent); without it, omitting the field is a
+/// missing-field error. The schema is the same as for `Option<T>`: the value
+/// or `null`, and optional given the `#[serde(default)]`.
+///
+/// `Absent` and `Null` both serialize as `null`; skip absent fields with
+/// `#[serde(skip_serializing_if = "Maybe::is_absent")]`.
+///
+/// # Example
+///
+/// ```ignore
+/// #[derive(Deserialize, JsonSchema)]
+/// pub struct UpdateContactParams {
+///     pub id: u64,
+///     #[serde(default)]
+///     pub phone: Maybe<String>,
+/// }
+///
+/// #[tool_function(description = "Update a contact")]
+/// pub async fn update_contact(params: UpdateContactParams) -> Result<Contact, ContactError> {
+///     let mut contact = load_contact(params.id).await?;
+///     params.phone.apply(&mut contact.phone);
+///     save_contact(&contact).await
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Maybe<T> {
+    /// The field was omitted.
+    #[default]
+    Absent,
+    /// The field was explicitly `null`.
+    Null,
+    /// The field was set to a value.
+    Present(T),
+}
+
+impl<T> Maybe<T> {
+    /// Whether the field was omitted.
+    pub fn is_absent(&self) -> bool {
+        matches!(self, Maybe::Absent)
+    }
+
+    /// Whether the field was explicitly `null`.
+    pub fn is_null(&self) -> bool {
+        matches!(self, Maybe::Null)
+    }
+
+    /// Whether the field was set to a value.
+    pub fn is_present(&self) -> bool {
+        matches!(self, Maybe::Present(_))
+    }
+
+    /// Borrow the value, if any.
+    pub fn as_ref(&self) -> Maybe<&T> {
+        match self {
+            Maybe::Absent => Maybe::Absent,
+            Maybe::Null => Maybe::Null,
+            Maybe::Present(value) => Maybe::Present(value),
+        }
+    }
+
+    /// The value, if any, forgetting whether the field was omitted or `null`.
+    pub fn into_option(self) -> Option<T> {
+        match self {
+            Maybe::Present(value) => Some(value),
+            Maybe::Absent | Maybe::Null => None,
+        }
+    }
+
+    /// Apply the field as a patch: leave `target` alone if it was omitted,
+    /// clear it if it was `null`, and overwrite it otherwise.
+    pub fn apply(self, target: &mut Option<T>) {
+        match self {
+            Maybe::Absent => {}
+            Maybe::Null => *target = None,
+            Maybe::Present(value) => *target = Some(value),
+        }
+    }
+}
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for Maybe<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        // Going through `Value` rather than `deserialize_option` makes an
+        // omitted field without `#[serde(default)]` an error instead of `Null`
+        match Value::deserialize(deserializer)? {
+            Value::Null => Ok(Maybe::Null),
+            value => T::deserialize(value).map(Maybe::Present).map_err(D::Error::custom),
+        }
+    }
+}
+
+impl<T: Serialize> Serialize for Maybe<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Maybe::Present(value) => value.serialize(serializer),
+            Maybe::Absent | Maybe::Null => serializer.serialize_none(),
+        }
+    }
+}
+
+impl<T: JsonSchema> JsonSchema for Maybe<T> {
+    fn inline_schema() -> bool {
+        true
+    }
+
+    fn schema_name() -> Cow<'static, str> {
+        Option::<T>::schema_name()
+    }
+
+    fn schema_id() -> Cow<'static, str> {
+        format!("Maybe<{}>", T::schema_id()).into()
+    }
+
+    fn json_schema(generator: &mut SchemaGenerator) -> Schema {
+        Option::<T>::json_schema(generator)
+    }
+}
@@ -0,0 +1,60 @@
+use crate::progress::ProgressReporter;
+use crate::traits::{BoxError, BoxFuture, ToolHandler};
+use serde_json::Value;
+use std::sync::Arc;
+
+/// A middleware attached to a single tool with
+/// [`ToolRegistry::wrap_tool`](crate::ToolRegistry::wrap_tool).
+pub(crate) type Middleware =
+    Arc<dyn for<'a> Fn(ToolInvocation, Next<'a>) -> BoxFuture<'a, Result<Value, BoxError>> + Send + Sync>;
+
+/// The call a tool middleware is asked to handle.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ToolInvocation {
+    /// The id the model assigned to the call.
+    pub call_id: String,
+    /// The name the tool is registered under.
+    pub tool: String,
+    /// The call's arguments, which a middleware may rewrite before passing them on.
+    pub arguments: Value,
+}
+
+/// The rest of a tool's middleware chain, ending in the tool itself.
+///
+/// A middleware calls [`run`](Self::run) to continue the chain, or returns
+/// without calling it to short-circuit the tool.
+pub struct Next<'a> {
+    handler: &'a dyn ToolHandler,
+    chain: &'a [Middleware],
+    progress: Option<ProgressReporter>,
+}
+
+impl<'a> Next<'a> {
+    pub(crate) fn new(
+        handler: &'a dyn ToolHandler,
+        chain: &'a [Middleware],
+        progress: Option<ProgressReporter>,
+    ) -> Self {
+        Self { handler, chain, progress }
+    }
+
+    /// Run the remaining middleware and the tool.
+    pub fn run(self, invocation: ToolInvocation) -> BoxFuture<'a, Result<Value, BoxError>> {
+        match self.chain.split_first() {
+            Some((middleware, rest)) => middleware(invocation, Next::new(self.handler, rest, self.progress)),
+            None => call_tool(self.handler, invocation.arguments, self.progress),
+        }
+    }
+}
+
+/// Call the tool itself, bypassing any middleware.
+pub(crate) fn call_tool(
+    handler: &dyn ToolHandler,
+    arguments: Value,
+    progress: Option<ProgressReporter>,
+) -> BoxFuture<'_, Result<Value, BoxError>> {
+    match progress {
+        Some(progress) => handler.call_json_with_progress(arguments, progress),
+        None => handler.call_json(arguments),
+    }
+}
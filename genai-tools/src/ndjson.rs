@@ -0,0 +1,55 @@
+use crate::adapter::{ToolCallLike, ToolResponseLike};
+use crate::error::{RegistryError, ToolErrorPayload};
+use crate::registry::ToolRegistry;
+use futures::stream::{FuturesUnordered, Stream};
+use serde_json::{json, Value};
+
+impl ToolRegistry {
+    /// Execute multiple tool calls concurrently, streaming each outcome as a
+    /// line of newline-delimited JSON as soon as the call completes.
+    ///
+    /// Lines arrive in completion order, not call order, and each ends with
+    /// `\n`, ready to be written to a socket. A successful call produces
+    /// `{"call_id": ..., "result": ...}`, with content that isn't valid JSON
+    /// (e.g. after truncation) given as a string; a failed call produces
+    /// `{"call_id": ..., "error": {...}}` with a [`ToolErrorPayload`]. Fails
+    /// up front only for duplicate call ids rejected by
+    /// [`with_duplicate_call_ids`](Self::with_duplicate_call_ids).
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let mut lines = registry.execute_calls_ndjson(&tool_calls)?;
+    /// while let Some(line) = lines.next().await {
+    ///     socket.write_all(line.as_bytes()).await?;
+    /// }
+    /// ```
+    pub fn execute_calls_ndjson<'a, C>(
+        &'a self,
+        tool_calls: &'a [C],
+    ) -> Result<impl Stream<Item = String> + Send + 'a, RegistryError>
+    where
+        C: ToolCallLike + Sync,
+    {
+        let call_ids = self
+            .duplicate_call_ids()
+            .resolve(tool_calls.iter().map(|call| call.call_id()))?
+            .unwrap_or_else(|| tool_calls.iter().map(|call| call.call_id().to_string()).collect());
+
+        Ok(tool_calls
+            .iter()
+            .zip(call_ids)
+            .map(|(call, call_id)| async move {
+                let line = match self.execute_call(call).await {
+                    Ok(response) => {
+                        let content = response.content();
+                        let result = serde_json::from_str(content).unwrap_or_else(|_| Value::String(content.to_string()));
+                        json!({ "call_id": call_id, "result": result })
+                    }
+                    Err(error) => json!({ "call_id": call_id, "error": ToolErrorPayload::from_error(error.as_ref()) }),
+                };
+                format!("{}\n", line)
+            })
+            .collect::<FuturesUnordered<_>>())
+    }
+}
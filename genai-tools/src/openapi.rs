@@ -0,0 +1,120 @@
+use crate::error::RegistryError;
+use crate::registry::ToolRegistry;
+use crate::schema;
+use serde_json::{json, Map, Value};
+
+impl ToolRegistry {
+    /// Describe every tool as an OpenAPI 3.1 path, for the `paths` object of
+    /// an OpenAPI document.
+    ///
+    /// Each tool becomes `POST /{name}` with the tool's description as the
+    /// summary and its advertised parameter schema as the JSON request body.
+    /// The `200` response carries the output schema when the tool advertises
+    /// one, and the `default` response the [`ToolErrorPayload`](crate::ToolErrorPayload)
+    /// envelope. References are inlined; those that close a cycle are pointed
+    /// at the `$defs` kept next to the schema, so the paths must be placed
+    /// under the document's top-level `paths`.
+    ///
+    /// Fails with [`RegistryError::Schema`] if a tool's schema cannot be generated.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let document = json!({
+    ///     "openapi": "3.1.0",
+    ///     "info": { "title": "Tools", "version": "1.0.0" },
+    ///     "paths": registry.to_openapi_paths()?,
+    /// });
+    /// ```
+    pub fn to_openapi_paths(&self) -> Result<Value, RegistryError> {
+        let mut paths = Map::new();
+        for (name, handler) in self.iter() {
+            let name = self.advertised_name(name);
+            let path = format!("/{}", name);
+            let operation_pointer = format!("#/paths/{}/post", escape_pointer(&path));
+
+            let parameters = openapi_schema(
+                self.advertised_schema(handler)?,
+                &format!("{}/requestBody/content/application~1json/schema", operation_pointer),
+            );
+            let mut success = json!({ "description": "The tool's result" });
+            if let Some(output) = handler.output_schema() {
+                let pointer = format!("{}/responses/200/content/application~1json/schema", operation_pointer);
+                success["content"] = json!({ "application/json": { "schema": openapi_schema(output, &pointer) } });
+            }
+
+            let operation = json!({
+                "operationId": name,
+                "summary": self.advertised_description(handler),
+                "requestBody": {
+                    "required": true,
+                    "content": { "application/json": { "schema": parameters } },
+                },
+                "responses": {
+                    "200": success,
+                    "default": {
+                        "description": "The call failed",
+                        "content": { "application/json": { "schema": error_envelope_schema() } },
+                    },
+                },
+            });
+            paths.insert(path, json!({ "post": operation }));
+        }
+        Ok(Value::Object(paths))
+    }
+}
+
+/// Prepare a schema for embedding at `pointer` in an OpenAPI document.
+fn openapi_schema(mut schema: Value, pointer: &str) -> Value {
+    schema::inline_refs(&mut schema);
+    if let Some(object) = schema.as_object_mut() {
+        object.remove("$schema");
+    }
+    // Local references would resolve against the document root
+    let defs_prefix = format!("{}/$defs/", pointer);
+    retarget_refs(&mut schema, &defs_prefix);
+    schema
+}
+
+fn retarget_refs(value: &mut Value, defs_prefix: &str) {
+    match value {
+        Value::Object(map) => {
+            for (key, item) in map.iter_mut() {
+                match item {
+                    Value::String(reference) if key == "$ref" => {
+                        if let Some(name) = reference.strip_prefix("#/$defs/") {
+                            *reference = format!("{}{}", defs_prefix, name);
+                        }
+                    }
+                    item => retarget_refs(item, defs_prefix),
+                }
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(|item| retarget_refs(item, defs_prefix)),
+        _ => {}
+    }
+}
+
+/// Escape a JSON pointer token.
+fn escape_pointer(token: &str) -> String {
+    token.replace('~', "~0").replace('/', "~1")
+}
+
+/// The schema of [`ToolErrorPayload::to_envelope`](crate::ToolErrorPayload::to_envelope).
+fn error_envelope_schema() -> Value {
+    json!({
+        "type": "object",
+        "properties": {
+            "error": {
+                "type": "object",
+                "properties": {
+                    "type": { "type": "string" },
+                    "message": { "type": "string" },
+                    "recoverable": { "type": "boolean" },
+                },
+                "required": ["type", "message", "recoverable"],
+            },
+        },
+        "required": ["error"],
+    })
+}
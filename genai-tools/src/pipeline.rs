@@ -0,0 +1,196 @@
+use crate::binary::BinaryOutput;
+use crate::cost::CostHint;
+use crate::error::RegistryError;
+use crate::progress::ProgressReporter;
+use crate::traits::{BoxError, BoxFuture, ToolHandler};
+use serde_json::value::RawValue;
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// A composite tool that feeds each stage's JSON output into the next stage.
+///
+/// The pipeline advertises the first stage's parameter schema and the last
+/// stage's output schema. A failing stage short-circuits the pipeline with
+/// [`RegistryError::PipelineStage`].
+pub(crate) struct ToolPipeline {
+    name: String,
+    description: String,
+    stages: Vec<Box<dyn ToolHandler>>,
+}
+
+impl ToolPipeline {
+    pub(crate) fn new(name: String, description: String, stages: Vec<Box<dyn ToolHandler>>) -> Self {
+        assert!(!stages.is_empty(), "Pipeline '{}' must have at least one stage", name);
+        Self {
+            name,
+            description,
+            stages,
+        }
+    }
+
+    /// Await the first stage's result, then feed it through the remaining stages.
+    async fn run(
+        &self,
+        first: BoxFuture<'_, Result<Value, BoxError>>,
+        progress: ProgressReporter,
+    ) -> Result<Value, BoxError> {
+        let mut value = first.await.map_err(|source| self.stage_error(0, source))?;
+        for (index, stage) in self.stages.iter().enumerate().skip(1) {
+            value = stage
+                .call_json_with_progress(value, progress.clone())
+                .await
+                .map_err(|source| self.stage_error(index, source))?;
+        }
+        Ok(value)
+    }
+
+    fn stage_error(&self, index: usize, source: BoxError) -> RegistryError {
+        RegistryError::PipelineStage {
+            pipeline: self.name.clone(),
+            index,
+            stage: self.stages[index].name().to_string(),
+            source,
+        }
+    }
+}
+
+impl ToolHandler for ToolPipeline {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn description(&self) -> &str {
+        &self.description
+    }
+
+    fn schema(&self) -> Value {
+        self.stages[0].schema()
+    }
+
+    fn try_schema(&self) -> Result<Value, serde_json::Error> {
+        self.stages[0].try_schema()
+    }
+
+    fn output_schema(&self) -> Option<Value> {
+        self.stages.last().and_then(|stage| stage.output_schema())
+    }
+
+    fn params_type_name(&self) -> &'static str {
+        self.stages[0].params_type_name()
+    }
+
+    fn error_type_name(&self) -> &'static str {
+        // Stage failures are wrapped in `RegistryError::PipelineStage`
+        std::any::type_name::<RegistryError>()
+    }
+
+    fn positional_fields(&self) -> Option<&'static [&'static str]> {
+        self.stages[0].positional_fields()
+    }
+
+    fn name_arguments(&self, arguments: Value) -> Result<Value, RegistryError> {
+        self.stages[0].name_arguments(arguments)
+    }
+
+    fn property_order(&self) -> Option<&'static [&'static str]> {
+        self.stages[0].property_order()
+    }
+
+    fn accepted_fields(&self) -> Option<&'static [&'static str]> {
+        self.stages[0].accepted_fields()
+    }
+
+    fn server_only_fields(&self) -> &'static [&'static str] {
+        self.stages[0].server_only_fields()
+    }
+
+    fn cost_hint(&self) -> CostHint {
+        // Expensive if any stage is; otherwise estimates add up and cheap stages are free
+        self.stages.iter().map(|stage| stage.cost_hint()).fold(CostHint::Cheap, |total, cost| match (total, cost) {
+            (CostHint::Expensive, _) | (_, CostHint::Expensive) => CostHint::Expensive,
+            (CostHint::Unknown, _) | (_, CostHint::Unknown) => CostHint::Unknown,
+            (CostHint::Cheap, cost) | (cost, CostHint::Cheap) => cost,
+            (CostHint::Estimated(a), CostHint::Estimated(b)) => CostHint::Estimated(a + b),
+        })
+    }
+
+    fn is_cpu_bound(&self) -> bool {
+        // The pipeline blocks if any stage does
+        self.stages.iter().any(|stage| stage.is_cpu_bound())
+    }
+
+    fn metadata(&self) -> HashMap<String, String> {
+        self.stages[0].metadata()
+    }
+
+    fn examples(&self) -> &[Value] {
+        self.stages[0].examples()
+    }
+
+    fn requires_init(&self) -> bool {
+        self.stages.iter().any(|stage| stage.requires_init())
+    }
+
+    fn init(&self) -> BoxFuture<'_, Result<(), BoxError>> {
+        Box::pin(async move {
+            for stage in &self.stages {
+                stage.init().await?;
+            }
+            Ok(())
+        })
+    }
+
+    fn call_json(&self, params: Value) -> BoxFuture<'_, Result<Value, BoxError>> {
+        let first = self.stages[0].call_json(params);
+        Box::pin(self.run(first, ProgressReporter::disabled()))
+    }
+
+    fn call_json_with_progress(
+        &self,
+        params: Value,
+        progress: ProgressReporter,
+    ) -> BoxFuture<'_, Result<Value, BoxError>> {
+        let first = self.stages[0].call_json_with_progress(params, progress.clone());
+        Box::pin(self.run(first, progress))
+    }
+
+    fn call_raw(&self, params: &RawValue) -> BoxFuture<'_, Result<Value, BoxError>> {
+        let first = self.stages[0].call_raw(params);
+        Box::pin(self.run(first, ProgressReporter::disabled()))
+    }
+
+    fn call_binary(&self, params: Value) -> BoxFuture<'_, Result<BinaryOutput, BoxError>> {
+        Box::pin(async move {
+            // Stages pass JSON along; only the last one's output is binary
+            let last = self.stages.len() - 1;
+            let mut value = params;
+            for (index, stage) in self.stages[..last].iter().enumerate() {
+                value = stage.call_json(value).await.map_err(|source| self.stage_error(index, source))?;
+            }
+            self.stages[last]
+                .call_binary(value)
+                .await
+                .map_err(|source| self.stage_error(last, source).into())
+        })
+    }
+
+    fn is_available(&self) -> BoxFuture<'_, bool> {
+        // The pipeline can only run if every stage can
+        Box::pin(async move {
+            for stage in &self.stages {
+                if !stage.is_available().await {
+                    return false;
+                }
+            }
+            true
+        })
+    }
+
+    fn is_terminal(&self) -> bool {
+        self.stages.last().is_some_and(|stage| stage.is_terminal())
+    }
+
+    fn responds_directly(&self) -> bool {
+        self.stages.last().is_some_and(|stage| stage.responds_directly())
+    }
+}
@@ -0,0 +1,36 @@
+use crate::error::RegistryError;
+use serde::de::Error as _;
+use serde_json::{Map, Value};
+
+/// Convert positional arguments (`[1, "a"]`) into named ones (`{"x": 1, "y": "a"}`).
+///
+/// Positions map to `fields` in declaration order. Anything other than an
+/// array is returned unchanged, so object-keyed arguments keep working.
+pub(crate) fn named_arguments(
+    tool: &str,
+    params_type: &'static str,
+    fields: &[&str],
+    params: Value,
+) -> Result<Value, RegistryError> {
+    let Value::Array(values) = params else {
+        return Ok(params);
+    };
+    if values.len() != fields.len() {
+        return Err(RegistryError::InvalidArguments {
+            tool: tool.to_string(),
+            params_type,
+            source: serde_json::Error::custom(format!(
+                "expected {} positional arguments ({}), got {}",
+                fields.len(),
+                fields.join(", "),
+                values.len()
+            )),
+        });
+    }
+    let named: Map<String, Value> = fields
+        .iter()
+        .map(|field| field.to_string())
+        .zip(values)
+        .collect();
+    Ok(Value::Object(named))
+}
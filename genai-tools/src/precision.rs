@@ -0,0 +1,28 @@
+use serde_json::{Number, Value};
+
+/// Round every float in `value` to `decimals` decimal places, recursively.
+///
+/// Integers are left untouched, as are floats too large for rounding to
+/// change them.
+pub(crate) fn round_floats(value: &mut Value, decimals: u32) {
+    match value {
+        Value::Number(number) if number.is_f64() => {
+            if let Some(rounded) = number.as_f64().and_then(|float| round(float, decimals)) {
+                *number = rounded;
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(|item| round_floats(item, decimals)),
+        Value::Object(map) => map.values_mut().for_each(|item| round_floats(item, decimals)),
+        _ => {}
+    }
+}
+
+fn round(float: f64, decimals: u32) -> Option<Number> {
+    let factor = 10f64.powi(i32::try_from(decimals).ok()?);
+    let scaled = float * factor;
+    // Beyond 2^53 every f64 is already an integer
+    if !scaled.is_finite() || scaled.abs() >= 9_007_199_254_740_992.0 {
+        return None;
+    }
+    Number::from_f64(scaled.round() / factor)
+}
@@ -0,0 +1,58 @@
+use futures::channel::mpsc::Sender;
+
+/// A progress update emitted by a tool while it runs.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Progress {
+    /// How far along the tool is, from `0.0` to `100.0`.
+    pub percent: f32,
+    /// A short human-readable status, e.g. "Fetched 3 of 10 pages".
+    pub message: String,
+}
+
+/// Handle a tool uses to report progress.
+///
+/// Tools opt in with `#[tool_function(progress)]`, which passes a reporter as
+/// the function's last argument. When the tool is run through
+/// [`ToolRegistry::execute_call_with_progress`](crate::ToolRegistry::execute_call_with_progress)
+/// the updates are sent to the caller's channel; otherwise reporting does nothing.
+///
+/// Reporting never waits: if the channel is full or closed, the update is dropped.
+///
+/// # Example
+///
+/// ```ignore
+/// #[tool_function(description = "Crawl a site", progress)]
+/// pub async fn crawl(params: CrawlParams, progress: ProgressReporter) -> Result<Site, CrawlError> {
+///     for (i, page) in params.pages.iter().enumerate() {
+///         progress.report(100.0 * i as f32 / params.pages.len() as f32, format!("Fetching {}", page));
+///         // ...
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ProgressReporter {
+    sender: Option<Sender<Progress>>,
+}
+
+impl ProgressReporter {
+    /// A reporter that discards every update.
+    pub fn disabled() -> Self {
+        Self::default()
+    }
+
+    pub(crate) fn new(sender: Sender<Progress>) -> Self {
+        Self { sender: Some(sender) }
+    }
+
+    /// Send a progress update, if anyone is listening.
+    pub fn report(&self, percent: f32, message: impl Into<String>) {
+        if let Some(sender) = &self.sender {
+            // Each clone of a sender has a guaranteed slot, so this only fails once
+            // the receiver is gone
+            let _ = sender.clone().try_send(Progress {
+                percent,
+                message: message.into(),
+            });
+        }
+    }
+}
@@ -0,0 +1,71 @@
+use serde_json::Value;
+
+/// Strips secrets from tool results by field name, wherever they are nested.
+///
+/// Field names are matched case-insensitively. Install a policy with
+/// [`ToolRegistry::with_result_redactor`](crate::ToolRegistry::with_result_redactor):
+///
+/// ```ignore
+/// let policy = RedactionPolicy::mask(["password", "token", "api_key"]);
+/// let registry = ToolRegistry::new().with_result_redactor(move |result| policy.redact(result));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct RedactionPolicy {
+    keys: Vec<String>,
+    /// Replacement value, or `None` to remove the field.
+    mask: Option<Value>,
+}
+
+impl RedactionPolicy {
+    /// Replace the values of the named fields with `"[REDACTED]"`, so the
+    /// model can still see that the field exists.
+    pub fn mask<I, K>(keys: I) -> Self
+    where
+        I: IntoIterator<Item = K>,
+        K: Into<String>,
+    {
+        Self {
+            keys: keys.into_iter().map(Into::into).collect(),
+            mask: Some(Value::String("[REDACTED]".to_string())),
+        }
+    }
+
+    /// Remove the named fields entirely.
+    pub fn remove<I, K>(keys: I) -> Self
+    where
+        I: IntoIterator<Item = K>,
+        K: Into<String>,
+    {
+        Self {
+            mask: None,
+            ..Self::mask(keys)
+        }
+    }
+
+    /// Use `mask` instead of `"[REDACTED]"` as the replacement value.
+    pub fn with_mask(mut self, mask: impl Into<Value>) -> Self {
+        self.mask = Some(mask.into());
+        self
+    }
+
+    /// Apply the policy to every object in `value`, recursively.
+    pub fn redact(&self, value: Value) -> Value {
+        match value {
+            Value::Array(items) => Value::Array(items.into_iter().map(|item| self.redact(item)).collect()),
+            Value::Object(map) => Value::Object(
+                map.into_iter()
+                    .filter_map(|(key, item)| match (self.matches(&key), &self.mask) {
+                        (false, _) => Some((key, self.redact(item))),
+                        (true, Some(mask)) => Some((key, mask.clone())),
+                        (true, None) => None,
+                    })
+                    .collect(),
+            ),
+            scalar => scalar,
+        }
+    }
+
+    fn matches(&self, key: &str) -> bool {
+        self.keys.iter().any(|redacted| redacted.eq_ignore_ascii_case(key))
+    }
+}
@@ -1,8 +1,42 @@
-use crate::traits::{ToolFunction, ToolHandler};
-use genai::chat::{Tool, ToolCall, ToolResponse};
+use crate::adapter::{ToolCallLike, ToolResponseLike};
+use crate::bind::BoundTool;
+use crate::call_ids::DuplicateCallIds;
+use crate::collect::ToolRegistration;
+use crate::deadline::Deadline;
+use crate::error::{RegistryError, ToolCallError, ToolErrorPayload};
+use crate::guard::GuardedTool;
+use crate::middleware::{call_tool, Middleware, Next, ToolInvocation};
+use crate::pipeline::ToolPipeline;
+use crate::precision::round_floats;
+use crate::progress::{Progress, ProgressReporter};
+use crate::rename::RenamedTool;
+use crate::response::DirectResponseTool;
+use crate::schema;
+use crate::schema_override::SchemaOverrideTool;
+use crate::shared::SharedTool;
+use crate::suggest::closest_names;
+use crate::traits::{self, BoxError, BoxFuture, ToolFunction, ToolHandler};
+use crate::truncate::truncate_result;
+use crate::validation::{self, CompiledSchema};
+use crate::view::RegistryView;
+use bytes::Bytes;
+use futures::channel::mpsc::Sender;
+use futures::future;
+use genai::chat::{ChatRequest, ChatResponse, Tool, ToolResponse};
+use serde::de::DeserializeOwned;
+use serde_json::value::RawValue;
+use serde_json::{json, Value};
 
-use std::collections::HashMap;
+use std::collections::{hash_map, BTreeMap, HashMap, HashSet};
 use std::error::Error;
+use std::future::Future;
+use std::sync::{Arc, PoisonError, RwLock};
+use std::time::{Duration, Instant};
+
+type SchemaTransform = Arc<dyn Fn(Value) -> Value + Send + Sync>;
+type ResultEnvelope = Arc<dyn Fn(&str, Value) -> Value + Send + Sync>;
+type ArgPreprocessor = Arc<dyn Fn(&str, Value) -> Value + Send + Sync>;
+type ResultRedactor = Arc<dyn Fn(Value) -> Value + Send + Sync>;
 
 /// A registry for managing and executing tool functions.
 ///
@@ -26,112 +60,1709 @@ use std::error::Error;
 /// let response = registry.execute_call(&tool_call).await?;
 /// ```
 pub struct ToolRegistry {
-    tools: HashMap<String, Box<dyn ToolHandler>>,
+    tools: HashMap<String, Arc<dyn ToolHandler>>,
+    input_validation: bool,
+    output_validation: bool,
+    property_ordering: bool,
+    tool_examples: bool,
+    require_descriptions: bool,
+    schema_transform: Option<SchemaTransform>,
+    arg_preprocessor: Option<ArgPreprocessor>,
+    result_envelope: Option<ResultEnvelope>,
+    result_redactor: Option<ResultRedactor>,
+    max_result_chars: Option<usize>,
+    float_precision: Option<u32>,
+    duplicate_call_ids: DuplicateCallIds,
+    /// Middleware chains attached with `wrap_tool`, by tool name.
+    middleware: HashMap<String, Vec<Middleware>>,
+    /// Runtime description overrides, by tool name.
+    descriptions: HashMap<String, DescriptionOverride>,
+    /// Overrides of `ToolHandler::is_cpu_bound` set with `set_blocking`, by tool name.
+    blocking: HashMap<String, bool>,
+    /// Names shown to the model instead of the tool name, from `register_function_as`.
+    advertised_names: HashMap<String, String>,
+    /// Advertised (transformed) schemas, computed lazily per tool name.
+    schema_cache: RwLock<HashMap<String, Value>>,
+    /// Compiled advertised schemas for input validation, by tool name.
+    input_validators: RwLock<HashMap<String, Arc<CompiledSchema>>>,
+    /// Compiled output schemas for output validation, by tool name.
+    output_validators: RwLock<HashMap<String, Option<Arc<CompiledSchema>>>>,
+    /// Names of tools whose `init` has completed.
+    initialized: RwLock<HashSet<String>>,
 }
 
-impl ToolRegistry {
-    /// Create a new empty tool registry.
-    pub fn new() -> Self {
-        Self {
-            tools: HashMap::new(),
-        }
+impl ToolRegistry {
+    /// Create a new empty tool registry.
+    pub fn new() -> Self {
+        Self::with_capacity(0)
+    }
+
+    /// Create an empty registry with room for at least `capacity` tools.
+    ///
+    /// Behaves exactly like [`new`](Self::new), but avoids rehashing while
+    /// registering a large, known number of tools.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            tools: HashMap::with_capacity(capacity),
+            input_validation: false,
+            output_validation: false,
+            property_ordering: false,
+            tool_examples: false,
+            require_descriptions: false,
+            schema_transform: None,
+            arg_preprocessor: None,
+            result_envelope: None,
+            result_redactor: None,
+            max_result_chars: None,
+            float_precision: None,
+            duplicate_call_ids: DuplicateCallIds::Allow,
+            middleware: HashMap::new(),
+            descriptions: HashMap::new(),
+            blocking: HashMap::new(),
+            advertised_names: HashMap::new(),
+            schema_cache: RwLock::new(HashMap::with_capacity(capacity)),
+            input_validators: RwLock::new(HashMap::new()),
+            output_validators: RwLock::new(HashMap::new()),
+            initialized: RwLock::new(HashSet::new()),
+        }
+    }
+
+    /// A copy sharing this registry's handlers, options and caches, for
+    /// copy-on-write updates by [`SharedToolRegistry`](crate::SharedToolRegistry).
+    pub(crate) fn fork(&self) -> Self {
+        Self {
+            tools: self.tools.clone(),
+            input_validation: self.input_validation,
+            output_validation: self.output_validation,
+            property_ordering: self.property_ordering,
+            tool_examples: self.tool_examples,
+            require_descriptions: self.require_descriptions,
+            schema_transform: self.schema_transform.clone(),
+            arg_preprocessor: self.arg_preprocessor.clone(),
+            result_envelope: self.result_envelope.clone(),
+            result_redactor: self.result_redactor.clone(),
+            max_result_chars: self.max_result_chars,
+            float_precision: self.float_precision,
+            duplicate_call_ids: self.duplicate_call_ids,
+            middleware: self.middleware.clone(),
+            descriptions: self.descriptions.clone(),
+            blocking: self.blocking.clone(),
+            advertised_names: self.advertised_names.clone(),
+            schema_cache: RwLock::new(self.schema_cache.read().unwrap_or_else(PoisonError::into_inner).clone()),
+            input_validators: RwLock::new(self.input_validators.read().unwrap_or_else(PoisonError::into_inner).clone()),
+            output_validators: RwLock::new(self.output_validators.read().unwrap_or_else(PoisonError::into_inner).clone()),
+            initialized: RwLock::new(self.initialized.read().unwrap_or_else(PoisonError::into_inner).clone()),
+        }
+    }
+
+    /// Validate tool arguments against the advertised parameter schema before deserializing them.
+    ///
+    /// serde stops at the first problem and its messages can be terse. With input
+    /// validation enabled, a call whose arguments don't match the schema fails with
+    /// [`RegistryError::InputValidation`], listing every violation with a JSON
+    /// pointer to the offending value. This gives the model more actionable
+    /// feedback. Disabled by default.
+    pub fn with_input_validation(mut self, enabled: bool) -> Self {
+        self.input_validation = enabled;
+        self
+    }
+
+    /// Validate tool results against their output schema before returning them.
+    ///
+    /// Only tools that advertise an output schema (see
+    /// [`ToolFunction::output_schema`]) are checked. A result that does not match
+    /// fails the call with [`RegistryError::OutputValidation`]. This is intended
+    /// as a development-time guard for tool authors; it is disabled by default.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let mut registry = ToolRegistry::new().with_output_validation(true);
+    /// registry.register_function(get_weather_tool());
+    /// ```
+    pub fn with_output_validation(mut self, enabled: bool) -> Self {
+        self.output_validation = enabled;
+        self
+    }
+
+    /// Refuse to register tools that still have the placeholder description
+    /// `#[tool_function]` generates when `description` is omitted.
+    ///
+    /// Catches undocumented tools before the model sees them. Disabled by
+    /// default. With it enabled, [`register_function`](Self::register_function)
+    /// and the other infallible `register_*` methods panic on such a tool, and
+    /// [`try_register_function`](Self::try_register_function) returns
+    /// [`RegistryError::MissingDescription`]. A description set beforehand with
+    /// [`set_description`](Self::set_description) counts.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let mut registry = ToolRegistry::new().with_require_descriptions(true);
+    /// registry.try_register_function(undocumented_tool())?; // Err(MissingDescription)
+    /// ```
+    pub fn with_require_descriptions(mut self, enabled: bool) -> Self {
+        self.require_descriptions = enabled;
+        self
+    }
+
+    /// Add `propertyOrdering` to every advertised schema, listing parameters in
+    /// the order their fields are declared.
+    ///
+    /// Gemini generates arguments in that order, which helps when later fields
+    /// depend on earlier ones; other providers ignore the keyword. The order
+    /// comes from [`ToolFunction::property_order`]; tools without one are left
+    /// as they are. Disabled by default. Runs before the
+    /// [schema transform](Self::with_schema_transform), which can still adjust
+    /// it with [`schema::with_property_ordering`].
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let mut registry = ToolRegistry::new().with_property_ordering(true);
+    /// registry.register_function(book_flight_tool());
+    /// // {"properties": {...}, "propertyOrdering": ["origin", "destination", "date"], ...}
+    /// ```
+    pub fn with_property_ordering(mut self, enabled: bool) -> Self {
+        self.property_ordering = enabled;
+        self.invalidate_schemas();
+        self
+    }
+
+    /// Attach each tool's [examples](ToolHandler::examples) to the tools
+    /// returned by [`get_tools`](Self::get_tools), as
+    /// `{"input_examples": [...]}` in the tool's `config`.
+    ///
+    /// Providers that accept example calls at the tool level use them as
+    /// few-shot demonstrations; the examples are in the schema's `examples`
+    /// either way. Tools without examples are left as they are. Disabled by
+    /// default.
+    pub fn with_tool_examples(mut self, enabled: bool) -> Self {
+        self.tool_examples = enabled;
+        self
+    }
+
+    /// Post-process every tool's schema before it is advertised to the model.
+    ///
+    /// The transform runs on the schema returned by each tool and its result is
+    /// what [`get_tools`](Self::get_tools) emits. This is the general escape hatch
+    /// for provider quirks: strict mode, `$ref` inlining, stripping unsupported
+    /// keywords, and so on. Transformed schemas are cached, so the closure runs
+    /// once per tool rather than on every `get_tools()` call.
+    ///
+    /// Arguments are still deserialized into the tool's params type. With
+    /// [input validation](Self::with_input_validation) enabled they are checked
+    /// against the transformed schema, so constraints added here are enforced.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let registry = ToolRegistry::new().with_schema_transform(|mut schema| {
+    ///     if let Some(obj) = schema.as_object_mut() {
+    ///         obj.remove("$schema");
+    ///     }
+    ///     schema
+    /// });
+    /// ```
+    pub fn with_schema_transform<F>(mut self, transform: F) -> Self
+    where
+        F: Fn(Value) -> Value + Send + Sync + 'static,
+    {
+        self.schema_transform = Some(Arc::new(transform));
+        self.invalidate_schemas();
+        self
+    }
+
+    /// Rewrite every call's arguments before they are validated and deserialized.
+    ///
+    /// The closure receives the tool name and the arguments as sent by the
+    /// model. This centralizes normalization of model quirks that would
+    /// otherwise be repeated in every tool, such as stray whitespace or
+    /// miscapitalized enum values. It runs before input validation and
+    /// middleware.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// fn trim_strings(value: Value) -> Value {
+    ///     match value {
+    ///         Value::String(s) => Value::String(s.trim().to_string()),
+    ///         Value::Array(items) => items.into_iter().map(trim_strings).collect(),
+    ///         Value::Object(map) => Value::Object(map.into_iter().map(|(k, v)| (k, trim_strings(v))).collect()),
+    ///         other => other,
+    ///     }
+    /// }
+    ///
+    /// let registry = ToolRegistry::new().with_arg_preprocessor(|_name, args| trim_strings(args));
+    /// ```
+    pub fn with_arg_preprocessor<F>(mut self, preprocessor: F) -> Self
+    where
+        F: Fn(&str, Value) -> Value + Send + Sync + 'static,
+    {
+        self.arg_preprocessor = Some(Arc::new(preprocessor));
+        self
+    }
+
+    /// Scrub every successful tool result before the model sees it, e.g. to
+    /// remove credentials that a tool returns along with its data.
+    ///
+    /// The closure receives the serialized result and runs after output
+    /// validation, before the [result envelope](Self::with_result_envelope) and
+    /// truncation. [`RedactionPolicy`](crate::RedactionPolicy) covers the
+    /// common case of masking fields by name. Results of tools registered with
+    /// [`register_response_function`](Self::register_response_function) are
+    /// sent as-is.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let policy = RedactionPolicy::mask(["password", "token"]);
+    /// let registry = ToolRegistry::new().with_result_redactor(move |result| policy.redact(result));
+    /// ```
+    pub fn with_result_redactor<F>(mut self, redactor: F) -> Self
+    where
+        F: Fn(Value) -> Value + Send + Sync + 'static,
+    {
+        self.result_redactor = Some(Arc::new(redactor));
+        self
+    }
+
+    /// Reshape every successful tool result before it becomes `ToolResponse.content`.
+    ///
+    /// The closure receives the tool name and the serialized result. Some agent
+    /// frameworks expect results in a fixed envelope; wrapping them here saves
+    /// every caller from doing it. Without an envelope the result is returned
+    /// as-is. Output validation, if enabled, checks the result before wrapping.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let registry = ToolRegistry::new()
+    ///     .with_result_envelope(|name, result| json!({ "tool": name, "result": result }));
+    /// ```
+    pub fn with_result_envelope<F>(mut self, envelope: F) -> Self
+    where
+        F: Fn(&str, Value) -> Value + Send + Sync + 'static,
+    {
+        self.result_envelope = Some(Arc::new(envelope));
+        self
+    }
+
+    /// Limit every `ToolResponse.content` to `max_chars` characters.
+    ///
+    /// Oversized results are shortened while keeping them valid JSON where
+    /// possible: the longest string values are cut first and end with a
+    /// `...[truncated]` marker, and object results gain a `"_truncated": true`
+    /// field. Only results with too little string content to cut, such as a
+    /// huge array of numbers, have their serialized text cut, ending with
+    /// `...[truncated N chars]`. The limit applies after any result envelope.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// // Keep tool output within roughly 2k tokens
+    /// let registry = ToolRegistry::new().with_max_result_chars(8_000);
+    /// ```
+    pub fn with_max_result_chars(mut self, max_chars: usize) -> Self {
+        self.max_result_chars = Some(max_chars);
+        self
+    }
+
+    /// Round every float in tool results to `decimals` decimal places.
+    ///
+    /// Values like `22.499999999` cost tokens without telling the model
+    /// anything; with a precision of 2 it sees `22.5`. Integers are never
+    /// changed. Rounding happens after redaction and before any result
+    /// envelope, and doesn't affect output validation.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let registry = ToolRegistry::new().with_float_precision(2);
+    /// ```
+    pub fn with_float_precision(mut self, decimals: u32) -> Self {
+        self.float_precision = Some(decimals);
+        self
+    }
+
+    /// Choose how [`execute_calls`](Self::execute_calls) and friends treat tool
+    /// calls that share a `call_id`.
+    ///
+    /// By default duplicates are executed and answered with their ids unchanged,
+    /// so their responses can't be correlated with the calls. See
+    /// [`DuplicateCallIds`] for the alternatives.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let registry = ToolRegistry::new().with_duplicate_call_ids(DuplicateCallIds::Disambiguate);
+    /// ```
+    pub fn with_duplicate_call_ids(mut self, policy: DuplicateCallIds) -> Self {
+        self.duplicate_call_ids = policy;
+        self
+    }
+
+    /// Create a registry containing every tool marked `#[tool_function(collect)]`.
+    ///
+    /// Collected tools are gathered at link time from all modules and crates in
+    /// the final binary, so they don't need to be listed by hand. Fails with
+    /// [`RegistryError::DuplicateTool`] if two collected tools share a name.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// #[tool_function(name = "get_weather", description = "Get weather", collect)]
+    /// pub async fn get_weather(params: WeatherParams) -> Result<WeatherResult, WeatherError> {
+    ///     // ...
+    /// }
+    ///
+    /// let registry = ToolRegistry::from_inventory()?;
+    /// assert!(registry.has_tool("get_weather"));
+    /// ```
+    pub fn from_inventory() -> Result<Self, RegistryError> {
+        let mut registry = Self::new();
+        for registration in inventory::iter::<ToolRegistration> {
+            let handler = registration.build();
+            if registry.has_tool(handler.name()) {
+                return Err(RegistryError::DuplicateTool {
+                    tool: handler.name().to_string(),
+                });
+            }
+            registry.insert_handler(handler);
+        }
+        Ok(registry)
+    }
+
+    /// Register a tool function in the registry.
+    ///
+    /// The function must implement the `ToolFunction` trait, which is typically
+    /// done automatically by the `#[tool_function]` macro.
+    ///
+    /// # Panics
+    ///
+    /// In debug builds, panics if one of the tool's
+    /// [examples](ToolFunction::examples) doesn't deserialize into its params
+    /// type.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let mut registry = ToolRegistry::new();
+    /// registry.register_function(get_weather);
+    /// ```
+    pub fn register_function<T>(&mut self, tool: T) -> &mut Self
+    where
+        T: ToolFunction,
+    {
+        check_examples(&tool);
+        self.insert_handler(Box::new(tool))
+    }
+
+    /// Register a tool function and return the name it was registered under.
+    ///
+    /// Behaves like [`register_function`](Self::register_function), for when
+    /// the name is needed later (e.g. to alias or remove the tool) and the
+    /// tool value is no longer at hand.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let name = registry.register_named(get_weather_tool());
+    /// assert!(registry.has_tool(&name));
+    /// ```
+    pub fn register_named<T>(&mut self, tool: T) -> String
+    where
+        T: ToolFunction,
+    {
+        let name = ToolFunction::name(&tool).to_string();
+        check_examples(&tool);
+        self.insert_handler(Box::new(tool));
+        name
+    }
+
+    /// Register a tool function that the model sees as `advertised_name`.
+    ///
+    /// The handler stays registered under its own name, which the rest of the
+    /// registry API (middleware, descriptions, removal, ...) keeps using, while
+    /// [`get_tools`](Self::get_tools), [`to_gemini_tools`](Self::to_gemini_tools)
+    /// and the other exports advertise `advertised_name` and calls to either
+    /// name are routed to it. This allows trying out tool
+    /// names with the model without touching the implementation.
+    ///
+    /// Fails with [`RegistryError::DuplicateTool`] if `advertised_name` is
+    /// already the name or advertised name of another tool. Registering a tool
+    /// under the same name again drops its advertised name.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let name = if experiment.variant_b { "lookup_forecast" } else { "get_weather" };
+    /// registry.register_function_as(get_weather_tool(), name)?;
+    /// ```
+    pub fn register_function_as<T>(&mut self, tool: T, advertised_name: impl Into<String>) -> Result<&mut Self, RegistryError>
+    where
+        T: ToolFunction,
+    {
+        let name = ToolFunction::name(&tool).to_string();
+        let advertised_name = advertised_name.into();
+        if self.name_taken(&name, &advertised_name) {
+            return Err(RegistryError::DuplicateTool { tool: advertised_name });
+        }
+
+        self.register_function(tool);
+        if advertised_name != name {
+            self.advertised_names.insert(name, advertised_name);
+        }
+        Ok(self)
+    }
+
+    /// Register a tool function that builds its own `ToolResponse`.
+    ///
+    /// This is an escape hatch for tools that need exact control over the
+    /// response. The returned `ToolResponse` is sent as-is, except that its
+    /// `call_id` is replaced with the id of the call being answered. The
+    /// registry's [result envelope](Self::with_result_envelope) and
+    /// [truncation](Self::with_max_result_chars) are not applied.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// #[tool_function(description = "Render a report")]
+    /// pub async fn render_report(params: ReportParams) -> Result<ToolResponse, ReportError> {
+    ///     Ok(ToolResponse::from_parts(String::new(), render(&params)?))
+    /// }
+    ///
+    /// registry.register_response_function(render_report_tool());
+    /// ```
+    pub fn register_response_function<T>(&mut self, tool: T) -> &mut Self
+    where
+        T: ToolFunction<Output = ToolResponse>,
+    {
+        check_examples(&tool);
+        self.insert_handler(Box::new(DirectResponseTool::new(Box::new(tool))))
+    }
+
+    /// Register a pre-built, type-erased handler.
+    ///
+    /// Use this for tools that are only available as `dyn ToolHandler`, such as
+    /// tools loaded from plugins or implemented by hand. The handler takes part
+    /// in every registry feature exactly like a registered function.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// for plugin in plugins {
+    ///     registry.register_handler(plugin.create_tool());
+    /// }
+    /// ```
+    pub fn register_handler(&mut self, handler: Box<dyn ToolHandler>) -> &mut Self {
+        self.insert_handler(handler)
+    }
+
+    /// Register a handler that is shared with its owner, e.g. a plugin host that
+    /// keeps its own reference.
+    pub fn register_shared_handler(&mut self, handler: Arc<dyn ToolHandler>) -> &mut Self {
+        self.insert_shared(handler)
+    }
+
+    /// Register a tool that has to do async work to construct itself, such as
+    /// fetching its configuration.
+    ///
+    /// Awaits `build` and registers the tool it returns. This saves a separate
+    /// [`init`](ToolHandler::init) step for tools that can't exist until the
+    /// work is done. The tool then takes part in the rest of the lifecycle as
+    /// if registered directly: [`init_all`](Self::init_all) still runs its
+    /// `init` if it requires one.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let mut registry = ToolRegistry::new();
+    /// registry
+    ///     .register_async(|| async { SearchTool::new(fetch_search_config().await) })
+    ///     .await
+    ///     .register_function(get_weather_tool());
+    /// registry.init_all().await?;
+    /// ```
+    pub async fn register_async<F, Fut, T>(&mut self, build: F) -> &mut Self
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = T>,
+        T: ToolHandler + 'static,
+    {
+        let tool = build().await;
+        self.insert_handler(Box::new(tool))
+    }
+
+    /// Register a tool function after checking that its schema can be generated.
+    ///
+    /// Unlike [`register_function`](Self::register_function), a tool whose schema
+    /// fails to serialize is rejected with [`RegistryError::Schema`] instead of
+    /// being registered, and a tool without a description is rejected with
+    /// [`RegistryError::MissingDescription`] instead of panicking when
+    /// [descriptions are required](Self::with_require_descriptions).
+    pub fn try_register_function<T>(&mut self, tool: T) -> Result<&mut Self, RegistryError>
+    where
+        T: ToolFunction,
+    {
+        schema_of(&tool)?;
+        self.check_description(&tool)?;
+        Ok(self.register_function(tool))
+    }
+
+    /// Register a tool function that is only callable while `guard` returns `true`.
+    ///
+    /// The guard is evaluated on every call, so availability can change at runtime
+    /// (feature flags, business hours, ...) without re-registering the tool. Calls
+    /// made while the guard returns `false` fail with [`RegistryError::Unavailable`],
+    /// and [`get_available_tools`](Self::get_available_tools) omits the tool.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let flags = feature_flags.clone();
+    /// registry.register_function_with_guard(advanced_search_tool(), move || {
+    ///     let flags = flags.clone();
+    ///     async move { flags.is_enabled("advanced_search").await }
+    /// });
+    /// ```
+    pub fn register_function_with_guard<T, F, Fut>(&mut self, tool: T, guard: F) -> &mut Self
+    where
+        T: ToolFunction,
+        F: Fn() -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = bool> + Send + 'static,
+    {
+        check_examples(&tool);
+        self.insert_handler(Box::new(GuardedTool::new(Box::new(tool), guard)))
+    }
+
+    /// Register a tool with a hand-written parameter schema.
+    ///
+    /// `schema` is advertised by [`get_tools`](Self::get_tools) and used for
+    /// [input validation](Self::with_input_validation) in place of the schema
+    /// generated from the params type, while calls still deserialize into that
+    /// type. The two must agree: arguments the override allows but the params
+    /// type rejects fail with [`RegistryError::InvalidArguments`] at call time.
+    /// Prefer adjusting the generated schema with
+    /// [`with_schema_transform`](Self::with_schema_transform) where possible.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// registry.register_function_with_schema(
+    ///     search_tool(),
+    ///     json!({
+    ///         "type": "object",
+    ///         "properties": { "query": { "type": "string", "minLength": 3 } },
+    ///         "required": ["query"]
+    ///     }),
+    /// );
+    /// ```
+    pub fn register_function_with_schema<T>(&mut self, tool: T, schema: Value) -> &mut Self
+    where
+        T: ToolFunction,
+    {
+        check_examples(&tool);
+        self.insert_handler(Box::new(SchemaOverrideTool::new(Box::new(tool), schema)))
+    }
+
+    /// Register a variant of a tool under `name` with some arguments pre-bound.
+    ///
+    /// The fields of `defaults` are merged into every call's arguments before
+    /// they are deserialized, overriding any value the model sent, and are
+    /// removed from the advertised schema. This lets one implementation back
+    /// several narrowly scoped tools.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `defaults` is not a JSON object.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// registry
+    ///     .register_with_defaults(send_message_tool(), "send_to_slack", json!({ "channel": "slack" }))
+    ///     .register_with_defaults(send_message_tool(), "send_to_email", json!({ "channel": "email" }));
+    /// ```
+    pub fn register_with_defaults<T>(&mut self, tool: T, name: impl Into<String>, defaults: Value) -> &mut Self
+    where
+        T: ToolFunction,
+    {
+        check_examples(&tool);
+        self.insert_handler(Box::new(BoundTool::new(name.into(), Box::new(tool), defaults)))
+    }
+
+    /// Attach a middleware to a single registered tool.
+    ///
+    /// The middleware receives the [`ToolInvocation`] and a [`Next`] handle,
+    /// and decides whether (and with which arguments) to continue to the tool
+    /// by calling `next.run(invocation)`. Returning without calling it
+    /// short-circuits the tool. Middlewares attached to the same tool compose
+    /// in registration order: the first one attached runs first and wraps all
+    /// later ones. They run after input validation and before output
+    /// validation, and are dropped along with the tool by
+    /// [`remove_tool`](Self::remove_tool).
+    ///
+    /// Fails with [`RegistryError::ToolNotFound`] if no tool named `name` is registered.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// registry.wrap_tool("send_email", |invocation, next| {
+    ///     Box::pin(async move {
+    ///         audit_log.record(&invocation.arguments);
+    ///         next.run(invocation).await
+    ///     })
+    /// })?;
+    /// ```
+    pub fn wrap_tool<F>(&mut self, name: &str, middleware: F) -> Result<&mut Self, RegistryError>
+    where
+        F: for<'a> Fn(ToolInvocation, Next<'a>) -> BoxFuture<'a, Result<Value, BoxError>> + Send + Sync + 'static,
+    {
+        if !self.has_tool(name) {
+            return Err(self.not_found(name));
+        }
+        self.middleware
+            .entry(name.to_string())
+            .or_default()
+            .push(Arc::new(middleware));
+        Ok(self)
+    }
+
+    /// Advertise `description` for the tool `name` instead of its compile-time one.
+    ///
+    /// Applies to [`get_tools`](Self::get_tools) and everything built on it,
+    /// such as [`export_definitions`](Self::export_definitions), without
+    /// touching the handler. Useful for localizing the tool catalog.
+    ///
+    /// Fails with [`RegistryError::ToolNotFound`] if no tool named `name` is registered.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// registry.set_description("get_weather", "Obtenir la météo actuelle d'une ville")?;
+    /// ```
+    pub fn set_description(&mut self, name: &str, description: impl Into<String>) -> Result<&mut Self, RegistryError> {
+        if !self.has_tool(name) {
+            return Err(self.not_found(name));
+        }
+        self.descriptions.entry(name.to_string()).or_default().tool = Some(description.into());
+        Ok(self)
+    }
+
+    /// Choose whether calls to the tool `name` run on tokio's blocking thread
+    /// pool, overriding [`ToolHandler::is_cpu_bound`].
+    ///
+    /// CPU-heavy tools (image processing, parsing, ...) otherwise stall every
+    /// other task on the executor thread they run on. With blocking enabled,
+    /// the tool's `call_json` runs inside `tokio::task::spawn_blocking` and its
+    /// result is awaited and returned as usual; middleware and validation stay
+    /// on the async side. Tools marked `#[tool_function(cpu_bound)]` are
+    /// blocking without calling this.
+    ///
+    /// Calls must then be made from within a tokio runtime, and a
+    /// multi-threaded one is needed for the rest of the application to keep
+    /// running alongside them. Requires the `blocking` feature.
+    ///
+    /// Fails with [`RegistryError::ToolNotFound`] if no tool named `name` is registered.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// registry.register_function(resize_image_tool());
+    /// registry.set_blocking("resize_image", true)?;
+    /// ```
+    #[cfg(feature = "blocking")]
+    pub fn set_blocking(&mut self, name: &str, enabled: bool) -> Result<&mut Self, RegistryError> {
+        if !self.has_tool(name) {
+            return Err(self.not_found(name));
+        }
+        self.blocking.insert(name.to_string(), enabled);
+        Ok(self)
+    }
+
+    /// Override the `description` of parameters in the advertised schema of the tool `name`.
+    ///
+    /// `fields` maps parameter names to descriptions and is merged with any
+    /// earlier overrides. Names that aren't parameters of the tool are
+    /// ignored. Overrides are applied before the
+    /// [schema transform](Self::with_schema_transform).
+    ///
+    /// Fails with [`RegistryError::ToolNotFound`] if no tool named `name` is registered.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// registry.set_tool_field_descriptions("get_weather", [
+    ///     ("city", "Nom de la ville"),
+    ///     ("country", "Pays de la ville"),
+    /// ])?;
+    /// ```
+    pub fn set_tool_field_descriptions<I, K, V>(&mut self, name: &str, fields: I) -> Result<&mut Self, RegistryError>
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: Into<String>,
+        V: Into<String>,
+    {
+        if !self.has_tool(name) {
+            return Err(self.not_found(name));
+        }
+        self.descriptions
+            .entry(name.to_string())
+            .or_default()
+            .fields
+            .extend(fields.into_iter().map(|(field, description)| (field.into(), description.into())));
+        self.invalidate_schema(name);
+        Ok(self)
+    }
+
+    /// Register multiple tool functions at once.
+    ///
+    /// Accepts anything iterable: a `Vec`, an array, an `Option`, or an iterator.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let mut registry = ToolRegistry::new();
+    /// registry.register_functions([get_weather, search_web, calculate]);
+    /// registry.register_functions(is_admin.then(delete_user_tool));
+    /// ```
+    pub fn register_functions<I, T>(&mut self, tools: I) -> &mut Self
+    where
+        I: IntoIterator<Item = T>,
+        T: ToolFunction,
+    {
+        for tool in tools {
+            self.register_function(tool);
+        }
+        self
+    }
+
+    /// Register a composite tool that chains several tools into a pipeline.
+    ///
+    /// The JSON output of each stage is passed as the input of the next, and the
+    /// last stage's output is the pipeline's result. The pipeline is exposed to
+    /// the model as a single tool with the first stage's input schema. If a stage
+    /// fails, the remaining stages are skipped and the call fails with
+    /// [`RegistryError::PipelineStage`].
+    ///
+    /// The stages are owned by the pipeline and are not registered individually.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `stages` is empty.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// registry.register_pipeline(
+    ///     "fetch_and_summarize",
+    ///     "Fetch a document and summarize it",
+    ///     vec![Box::new(fetch_document_tool()), Box::new(summarize_tool())],
+    /// );
+    /// ```
+    pub fn register_pipeline(
+        &mut self,
+        name: impl Into<String>,
+        description: impl Into<String>,
+        stages: Vec<Box<dyn ToolHandler>>,
+    ) -> &mut Self {
+        let pipeline = ToolPipeline::new(name.into(), description.into(), stages);
+        self.insert_handler(Box::new(pipeline))
+    }
+
+    /// Register a tool that proxies calls to a remote HTTP endpoint.
+    ///
+    /// Each call sends the arguments as the JSON body of a `method` request to
+    /// `url` and returns the JSON response body as the result. Non-2xx responses
+    /// fail with [`HttpToolError`](crate::HttpToolError). Requires the `http` feature.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// registry.register_http_tool(
+    ///     "lookup_order",
+    ///     "Look up an order by id",
+    ///     json!({"type": "object", "properties": {"id": {"type": "string"}}}),
+    ///     "https://orders.internal/lookup",
+    ///     HttpMethod::POST,
+    /// );
+    /// ```
+    #[cfg(feature = "http")]
+    pub fn register_http_tool(
+        &mut self,
+        name: impl Into<String>,
+        description: impl Into<String>,
+        schema: Value,
+        url: impl Into<String>,
+        method: reqwest::Method,
+    ) -> &mut Self {
+        let tool = crate::http::HttpTool::new(name, description, schema, url, method);
+        self.insert_handler(Box::new(tool))
+    }
+
+    fn insert_handler(&mut self, handler: Box<dyn ToolHandler>) -> &mut Self {
+        self.insert_shared(Arc::from(handler))
+    }
+
+    fn insert_shared(&mut self, handler: Arc<dyn ToolHandler>) -> &mut Self {
+        if let Err(e) = self.check_description(handler.as_ref()) {
+            panic!("{}", e);
+        }
+        let name = handler.name().to_string();
+        self.invalidate_schema(&name);
+        self.initialized
+            .get_mut()
+            .unwrap_or_else(PoisonError::into_inner)
+            .remove(&name);
+        // The tool's own name takes precedence over advertised names
+        self.advertised_names.remove(&name);
+        self.advertised_names.retain(|_, advertised| *advertised != name);
+        self.tools.insert(name, handler);
+        self
+    }
+
+    fn check_description(&self, handler: &dyn ToolHandler) -> Result<(), RegistryError> {
+        self.require_description(handler.name(), self.advertised_description(handler))
+    }
+
+    fn require_description(&self, tool: &str, description: &str) -> Result<(), RegistryError> {
+        // The macro's fallback is "Tool function: {name}"
+        if self.require_descriptions && (description.trim().is_empty() || description.starts_with("Tool function: ")) {
+            return Err(RegistryError::MissingDescription { tool: tool.to_string() });
+        }
+        Ok(())
+    }
+
+    /// Run the async initialization of every registered tool.
+    ///
+    /// Tools are initialized concurrently. Tools that have already been
+    /// initialized are skipped, so calling this again after registering more
+    /// tools only initializes the new ones. Tools that require initialization
+    /// (see [`ToolFunction::requires_init`]) fail with
+    /// [`RegistryError::NotInitialized`] until this has completed for them.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let mut registry = ToolRegistry::new();
+    /// registry.register_function(query_database_tool());
+    /// registry.init_all().await?;
+    /// ```
+    pub async fn init_all(&self) -> Result<(), RegistryError> {
+        let pending = self
+            .tools
+            .values()
+            .filter(|handler| !self.is_initialized(handler.name()))
+            .map(|handler| async move {
+                handler.init().await.map_err(|source| RegistryError::InitFailed {
+                    tool: handler.name().to_string(),
+                    source,
+                })?;
+                self.initialized
+                    .write()
+                    .unwrap_or_else(PoisonError::into_inner)
+                    .insert(handler.name().to_string());
+                Ok::<(), RegistryError>(())
+            });
+
+        futures::future::try_join_all(pending).await?;
+        Ok(())
+    }
+
+    fn is_initialized(&self, name: &str) -> bool {
+        self.initialized
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .contains(name)
+    }
+
+    /// Get all registered tools as `genai::chat::Tool` objects.
+    ///
+    /// This method converts the registered tool functions into the format
+    /// expected by the genai library for sending to LLMs.
+    ///
+    /// Tools whose schema cannot be generated are omitted; use
+    /// [`try_get_tools`](Self::try_get_tools) to surface that error instead.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let tools = registry.get_tools();
+    /// let chat_req = ChatRequest::new(messages).with_tools(tools);
+    /// ```
+    pub fn get_tools(&self) -> Vec<Tool> {
+        self.tools
+            .values()
+            .filter_map(|handler| self.to_tool(handler.as_ref()).ok())
+            .collect()
+    }
+
+    /// Get all registered tools, failing with [`RegistryError::Schema`] if any
+    /// tool's schema cannot be generated.
+    pub fn try_get_tools(&self) -> Result<Vec<Tool>, RegistryError> {
+        self.tools
+            .values()
+            .map(|handler| self.to_tool(handler.as_ref()))
+            .collect()
+    }
+
+    /// Compute and cache every tool's advertised schema ahead of time.
+    ///
+    /// Call this after registration, e.g. at startup, so the first
+    /// [`get_tools`](Self::get_tools) on a request path doesn't pay for schema
+    /// generation. Returns the number of cached schemas. Schemas that are already
+    /// cached are not recomputed, so calling it again is cheap. Fails with
+    /// [`RegistryError::Schema`] if any tool's schema cannot be generated.
+    pub fn precompute_schemas(&self) -> Result<usize, RegistryError> {
+        for handler in self.tools.values() {
+            self.advertised_schema(handler.as_ref())?;
+        }
+        Ok(self.tools.len())
+    }
+
+    /// Check that every tool advertises a usable schema: one that can be
+    /// generated, is a JSON object, and is valid against the JSON Schema
+    /// meta-schema.
+    ///
+    /// Meant for a startup self-check or a test, so a hand-written `JsonSchema`
+    /// impl that produces garbage is caught before a provider rejects the
+    /// request. On failure, returns every offending tool with the reason,
+    /// ordered by tool name.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// if let Err(problems) = registry.validate_all_schemas() {
+    ///     for (tool, error) in problems {
+    ///         eprintln!("tool '{}' has an invalid schema: {}", tool, error);
+    ///     }
+    /// }
+    /// ```
+    pub fn validate_all_schemas(&self) -> Result<(), Vec<(String, String)>> {
+        let mut problems: Vec<(String, String)> = self
+            .tools
+            .iter()
+            .filter_map(|(name, handler)| {
+                let checked = match self.advertised_schema(handler.as_ref()) {
+                    Ok(schema) => validation::check_schema(&schema),
+                    Err(e) => Err(e.to_string()),
+                };
+                checked.err().map(|error| (name.clone(), error))
+            })
+            .collect();
+        if problems.is_empty() {
+            return Ok(());
+        }
+        problems.sort_unstable();
+        Err(problems)
+    }
+
+    /// Check that every property a tool advertises is a field its params type
+    /// deserializes.
+    ///
+    /// The two can drift apart when a schema is hand-written, replaced with
+    /// [`register_function_with_schema`](Self::register_function_with_schema)
+    /// or a transform, or renamed with `#[schemars(rename = "...")]` but not
+    /// `#[serde(rename = "...")]`. The model then fills a key that serde
+    /// ignores, and the tool silently sees the field's default. On failure,
+    /// returns every mismatched property as `(tool, message)`, ordered by
+    /// tool name.
+    ///
+    /// Tools whose params don't deserialize as a plain struct (enums, structs
+    /// with `#[serde(flatten)]` fields, tools not built from a
+    /// [`ToolFunction`]) and tools whose schema can't be generated are
+    /// skipped.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// #[test]
+    /// fn schemas_match_params() {
+    ///     assert_eq!(registry().check_schema_serde_consistency(), Ok(()));
+    /// }
+    /// ```
+    pub fn check_schema_serde_consistency(&self) -> Result<(), Vec<(String, String)>> {
+        let mut problems = Vec::new();
+        for (name, handler) in &self.tools {
+            let Some(accepted) = handler.accepted_fields() else {
+                continue;
+            };
+            let Ok(schema) = self.advertised_schema(handler.as_ref()) else {
+                continue;
+            };
+            let Some(properties) = schema.get("properties").and_then(Value::as_object) else {
+                continue;
+            };
+            problems.extend(
+                properties
+                    .keys()
+                    .filter(|property| !accepted.contains(&property.as_str()))
+                    .map(|property| {
+                        let message = format!(
+                            "schema property '{}' is not a field of {}; serde accepts {:?}",
+                            property,
+                            handler.params_type_name(),
+                            accepted,
+                        );
+                        (name.clone(), message)
+                    }),
+            );
+        }
+        if problems.is_empty() {
+            return Ok(());
+        }
+        problems.sort_unstable();
+        Err(problems)
+    }
+
+    /// The advertised name, description and parameter schema of the tool
+    /// `name` in one object, like [`ToolHandler::full_schema`].
+    ///
+    /// Unlike the handler method, this reflects description overrides and the
+    /// schema transform, and reuses the cached schema. Fails with
+    /// [`RegistryError::ToolNotFound`] for unknown tools and
+    /// [`RegistryError::Schema`] if the schema cannot be generated.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// log::debug!("calling {}", registry.full_schema("get_weather")?);
+    /// ```
+    pub fn full_schema(&self, name: &str) -> Result<Value, RegistryError> {
+        let handler = self
+            .handler(name)
+            .ok_or_else(|| self.not_found(name))?;
+        Ok(traits::full_schema(
+            handler.name(),
+            self.advertised_description(handler),
+            self.advertised_schema(handler)?,
+        ))
+    }
+
+    /// Every tool's advertised schema by tool name, for golden-file tests.
+    ///
+    /// Tools are ordered by name and object keys within each schema are sorted
+    /// recursively, so the snapshot is stable across runs and registration
+    /// orders. Schema transforms are applied, exactly as in
+    /// [`get_tools`](Self::get_tools).
+    ///
+    /// # Panics
+    ///
+    /// Panics if a tool's schema cannot be generated, so a broken tool fails
+    /// the snapshot rather than silently disappearing from it.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// #[test]
+    /// fn tool_schemas_are_stable() {
+    ///     insta::assert_json_snapshot!(registry().snapshot_schemas());
+    /// }
+    /// ```
+    pub fn snapshot_schemas(&self) -> BTreeMap<String, Value> {
+        self.tools
+            .iter()
+            .map(|(name, handler)| {
+                let advertised = self
+                    .advertised_schema(handler.as_ref())
+                    .unwrap_or_else(|e| panic!("{}", e));
+                (name.clone(), schema::sort_keys(advertised))
+            })
+            .collect()
+    }
+
+    /// A compact outline of every tool, one `name: summary` line per tool in
+    /// name order, for logs.
+    ///
+    /// Each summary is [`schema::summarize`] of the advertised schema, e.g.
+    /// `get_weather: WeatherParams{city: string, unit?: enum[C,F]}`.
+    pub fn describe_compact(&self) -> String {
+        let mut names = self.tool_names();
+        names.sort_unstable();
+        names
+            .into_iter()
+            .map(|name| {
+                let summary = match self.advertised_schema(self.tools[name].as_ref()) {
+                    Ok(schema) => schema::summarize(&schema),
+                    Err(e) => format!("<{}>", e),
+                };
+                format!("{}: {}", name, summary)
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Get the registered tools that are currently available.
+    ///
+    /// Like [`get_tools`](Self::get_tools), but evaluates each tool's availability
+    /// guard and omits tools that are currently unavailable.
+    pub async fn get_available_tools(&self) -> Vec<Tool> {
+        let mut tools = Vec::with_capacity(self.tools.len());
+        for handler in self.tools.values() {
+            if handler.is_available().await {
+                tools.extend(self.to_tool(handler.as_ref()).ok());
+            }
+        }
+        tools
+    }
+
+    pub(crate) fn to_tool(&self, handler: &dyn ToolHandler) -> Result<Tool, RegistryError> {
+        let tool = Tool::new(self.advertised_name(handler.name()))
+            .with_description(self.advertised_description(handler))
+            .with_schema(self.advertised_schema(handler)?);
+        Ok(match handler.examples() {
+            examples if self.tool_examples && !examples.is_empty() => {
+                tool.with_config(json!({ "input_examples": examples }))
+            }
+            _ => tool,
+        })
+    }
+
+    /// The name the model sees for the tool `name`.
+    pub(crate) fn advertised_name<'a>(&'a self, name: &'a str) -> &'a str {
+        self.advertised_names.get(name).map_or(name, String::as_str)
+    }
+
+    /// Whether `advertised_name` is the name or advertised name of a tool
+    /// other than `name`.
+    fn name_taken(&self, name: &str, advertised_name: &str) -> bool {
+        self.tools
+            .keys()
+            .filter(|other| *other != name)
+            .any(|other| other == advertised_name || self.advertised_name(other) == advertised_name)
+    }
+
+    /// The tool name for `name`, which may be an advertised name.
+    pub(crate) fn resolve_name<'a>(&'a self, name: &'a str) -> &'a str {
+        self.advertised_names
+            .iter()
+            .find(|(_, advertised)| *advertised == name)
+            .map_or(name, |(name, _)| name.as_str())
+    }
+
+    /// The description advertised for `handler`, after any override.
+    pub(crate) fn advertised_description<'a>(&'a self, handler: &'a dyn ToolHandler) -> &'a str {
+        self.descriptions
+            .get(handler.name())
+            .and_then(|overrides| overrides.tool.as_deref())
+            .unwrap_or_else(|| handler.description())
+    }
+
+    /// The schema advertised for `handler`, after the schema transform.
+    pub(crate) fn advertised_schema(&self, handler: &dyn ToolHandler) -> Result<Value, RegistryError> {
+        let cached = self
+            .schema_cache
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(handler.name())
+            .cloned();
+        if let Some(schema) = cached {
+            return Ok(schema);
+        }
+
+        let mut schema = schema_of(handler)?;
+        if let Some(overrides) = self.descriptions.get(handler.name()) {
+            overrides.apply_to(&mut schema);
+        }
+        if let Some(order) = handler.property_order().filter(|_| self.property_ordering) {
+            schema::with_property_ordering(&mut schema, order);
+        }
+        if let Some(transform) = &self.schema_transform {
+            schema = transform(schema);
+        }
+        self.schema_cache
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(handler.name().to_string(), schema.clone());
+        Ok(schema)
+    }
+
+    fn invalidate_schema(&mut self, name: &str) {
+        self.schema_cache
+            .get_mut()
+            .unwrap_or_else(PoisonError::into_inner)
+            .remove(name);
+        self.input_validators
+            .get_mut()
+            .unwrap_or_else(PoisonError::into_inner)
+            .remove(name);
+        self.output_validators
+            .get_mut()
+            .unwrap_or_else(PoisonError::into_inner)
+            .remove(name);
+    }
+
+    fn invalidate_schemas(&mut self) {
+        self.schema_cache
+            .get_mut()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clear();
+        self.input_validators
+            .get_mut()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clear();
+        self.output_validators
+            .get_mut()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clear();
+    }
+
+    /// Execute a tool call received from an LLM.
+    ///
+    /// This method takes a `ToolCall` from the LLM response, finds the
+    /// corresponding registered function, executes it with the provided
+    /// arguments, and returns a `ToolResponse`.
+    ///
+    /// # Arguments
+    ///
+    /// * `tool_call` - The tool call from the LLM containing the function name and arguments
+    ///
+    /// # Returns
+    ///
+    /// A `ToolResponse` containing the result of the function execution, or an error
+    /// if the tool is not found or execution fails.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let tool_calls = chat_response.into_tool_calls().unwrap();
+    /// for tool_call in tool_calls {
+    ///     let response = registry.execute_call(&tool_call).await?;
+    ///     // Handle the response...
+    /// }
+    /// ```
+    pub async fn execute_call<C>(&self, tool_call: &C) -> Result<ToolResponse, Box<dyn Error + Send + Sync>>
+    where
+        C: ToolCallLike + ?Sized,
+    {
+        self.execute_call_within(tool_call, self.max_result_chars).await
+    }
+
+    /// [`execute_call`](Self::execute_call), truncating the content to
+    /// `max_chars` rather than the registry's limit.
+    pub(crate) async fn execute_call_within<C>(&self, tool_call: &C, max_chars: Option<usize>) -> Result<ToolResponse, BoxError>
+    where
+        C: ToolCallLike + ?Sized,
+    {
+        let handler = self.ready_handler(tool_call.fn_name()).await?;
+        let arguments = self.preprocess(handler, tool_call.fn_arguments().clone());
+        self.validate_input(handler, &arguments)?;
+        let result = self.call_handler(handler, tool_call.call_id(), arguments, None).await?;
+        self.respond_within(handler, tool_call.call_id().to_string(), result, max_chars)
     }
 
-    /// Register a tool function in the registry.
+    /// Execute a tool call and report how long the tool took.
     ///
-    /// The function must implement the `ToolFunction` trait, which is typically
-    /// done automatically by the `#[tool_function]` macro.
+    /// Behaves like [`execute_call`](Self::execute_call). The duration covers
+    /// only running the tool, middleware included; looking it up, validating
+    /// the arguments and serializing the result are not counted.
     ///
     /// # Example
     ///
     /// ```ignore
-    /// let mut registry = ToolRegistry::new();
-    /// registry.register_function(get_weather);
+    /// let (response, elapsed) = registry.execute_call_timed(&tool_call).await?;
+    /// log::debug!("{} took {:?}", tool_call.fn_name(), elapsed);
     /// ```
-    pub fn register_function<T>(&mut self, tool: T) -> &mut Self
+    pub async fn execute_call_timed<C>(&self, tool_call: &C) -> Result<(ToolResponse, Duration), BoxError>
     where
-        T: ToolFunction,
+        C: ToolCallLike + ?Sized,
     {
-        let name = tool.name().to_string();
-        self.tools.insert(name, Box::new(tool));
-        self
+        let handler = self.ready_handler(tool_call.fn_name()).await?;
+        let arguments = self.preprocess(handler, tool_call.fn_arguments().clone());
+        self.validate_input(handler, &arguments)?;
+        let started = Instant::now();
+        let result = self.call_handler(handler, tool_call.call_id(), arguments, None).await?;
+        let elapsed = started.elapsed();
+        let response = self.respond(handler, tool_call.call_id().to_string(), result)?;
+        Ok((response, elapsed))
     }
 
-    /// Register multiple tool functions at once.
+    /// Execute a tool call and return the tool's raw payload with its MIME
+    /// type, for tools whose output is a [`BinaryOutput`](crate::BinaryOutput).
+    ///
+    /// For data that doesn't belong in the model's context, such as a file to
+    /// save or stream to a client. The arguments are checked as in
+    /// [`execute_call`](Self::execute_call), but middleware and the result
+    /// options (redaction, truncation, envelopes) work on JSON and are not
+    /// applied. Tools with any other output type fail with
+    /// [`RegistryError::NotBinary`] without running.
     ///
     /// # Example
     ///
     /// ```ignore
-    /// let mut registry = ToolRegistry::new();
-    /// registry.register_functions(vec![get_weather, search_web, calculate]);
+    /// let (mime_type, bytes) = registry.execute_call_bytes(&tool_call).await?;
+    /// std::fs::write("report.pdf", &bytes)?;
     /// ```
-    pub fn register_functions<T>(&mut self, tools: Vec<T>) -> &mut Self
+    pub async fn execute_call_bytes<C>(&self, tool_call: &C) -> Result<(String, Bytes), BoxError>
     where
-        T: ToolFunction,
+        C: ToolCallLike + ?Sized,
     {
-        for tool in tools {
-            self.register_function(tool);
-        }
-        self
+        let handler = self.ready_handler(tool_call.fn_name()).await?;
+        let arguments = self.preprocess(handler, tool_call.fn_arguments().clone());
+        self.validate_input(handler, &arguments)?;
+        let output = handler.call_binary(arguments).await?;
+        Ok((output.mime_type, output.bytes))
     }
 
-    /// Get all registered tools as `genai::chat::Tool` objects.
+    /// Execute a tool call, forwarding the tool's progress updates to `progress`.
     ///
-    /// This method converts the registered tool functions into the format
-    /// expected by the genai library for sending to LLMs.
+    /// Tools declared with `#[tool_function(progress)]` can report
+    /// [`Progress`] while they run; other tools never send anything. The
+    /// channel is dropped once the call completes, so a receiver can simply be
+    /// drained until it ends.
     ///
     /// # Example
     ///
     /// ```ignore
-    /// let tools = registry.get_tools();
-    /// let chat_req = ChatRequest::new(messages).with_tools(tools);
+    /// let (tx, mut rx) = futures::channel::mpsc::channel(16);
+    /// let call = registry.execute_call_with_progress(&tool_call, tx);
+    /// let show = async {
+    ///     while let Some(update) = rx.next().await {
+    ///         println!("{:>3.0}% {}", update.percent, update.message);
+    ///     }
+    /// };
+    /// let (response, ()) = futures::join!(call, show);
     /// ```
-    pub fn get_tools(&self) -> Vec<Tool> {
-        self.tools
-            .values()
-            .map(|handler| {
-                Tool::new(handler.name())
-                    .with_description(handler.description())
-                    .with_schema(handler.schema())
-            })
-            .collect()
+    pub async fn execute_call_with_progress<C>(
+        &self,
+        tool_call: &C,
+        progress: Sender<Progress>,
+    ) -> Result<ToolResponse, BoxError>
+    where
+        C: ToolCallLike + ?Sized,
+    {
+        let handler = self.ready_handler(tool_call.fn_name()).await?;
+        let arguments = self.preprocess(handler, tool_call.fn_arguments().clone());
+        self.validate_input(handler, &arguments)?;
+        let progress = Some(ProgressReporter::new(progress));
+        let result = self.call_handler(handler, tool_call.call_id(), arguments, progress).await?;
+        self.respond(handler, tool_call.call_id().to_string(), result)
     }
 
-    /// Execute a tool call received from an LLM.
+    /// Execute a tool call, giving up at `deadline`.
     ///
-    /// This method takes a `ToolCall` from the LLM response, finds the
-    /// corresponding registered function, executes it with the provided
-    /// arguments, and returns a `ToolResponse`.
+    /// For callers that track a deadline for the whole request, e.g. an agent
+    /// loop with a fixed time per turn. A call still running at the deadline is
+    /// dropped and fails with [`RegistryError::Timeout`], reporting the time
+    /// that was left when the call started; a deadline that has already passed
+    /// fails without running the tool. Timeouts inside the tool itself still
+    /// apply if they expire first.
     ///
-    /// # Arguments
+    /// # Example
     ///
-    /// * `tool_call` - The tool call from the LLM containing the function name and arguments
+    /// ```ignore
+    /// let turn_deadline = Instant::now() + Duration::from_secs(30);
+    /// for tool_call in &tool_calls {
+    ///     let response = registry.execute_call_with_deadline(tool_call, turn_deadline).await?;
+    ///     chat_req = chat_req.append_message(response);
+    /// }
+    /// ```
+    pub async fn execute_call_with_deadline<C>(&self, tool_call: &C, deadline: Instant) -> Result<ToolResponse, BoxError>
+    where
+        C: ToolCallLike + ?Sized,
+    {
+        let budget = deadline.saturating_duration_since(Instant::now());
+        if budget.is_zero() {
+            return Err(Box::new(RegistryError::Timeout {
+                tool: tool_call.fn_name().to_string(),
+                budget,
+            }));
+        }
+        Deadline::after(budget)
+            .bound(tool_call.fn_name(), budget, self.execute_call(tool_call))
+            .await
+    }
+
+    /// Execute a tool call, reporting failures to the model instead of the caller.
     ///
-    /// # Returns
+    /// genai's `ToolResponse` has no dedicated error flag, so the richest
+    /// representation available is the response content: on failure it is a
+    /// [`ToolErrorPayload`] envelope,
+    /// `{"error": {"type": "...", "message": "...", "recoverable": bool}}`.
+    /// This lets the model see what went wrong and whether to retry, rather
+    /// than aborting the conversation.
     ///
-    /// A `ToolResponse` containing the result of the function execution, or an error
-    /// if the tool is not found or execution fails.
+    /// # Example
+    ///
+    /// ```ignore
+    /// for tool_call in chat_res.into_tool_calls().unwrap_or_default() {
+    ///     chat_req = chat_req.append_message(registry.execute_call_as_genai(&tool_call).await);
+    /// }
+    /// ```
+    pub async fn execute_call_as_genai<C>(&self, tool_call: &C) -> ToolResponse
+    where
+        C: ToolCallLike + ?Sized,
+    {
+        match self.execute_call(tool_call).await {
+            Ok(response) => response,
+            Err(error) => ToolResponse::from_parts(
+                tool_call.call_id().to_string(),
+                ToolErrorPayload::from_error(error.as_ref()).to_envelope().to_string(),
+            ),
+        }
+    }
+
+    /// Execute a tool call and decode its result as `T`.
+    ///
+    /// The call runs like [`execute_call_as_genai`](Self::execute_call_as_genai),
+    /// so every failure arrives as response content. If that content is a
+    /// [`ToolErrorPayload`] envelope it is returned as [`ToolCallError::Tool`];
+    /// otherwise it is deserialized into `T`. Results shaped exactly like an
+    /// envelope are indistinguishable from one, and a
+    /// [`with_result_envelope`](Self::with_result_envelope) wrapper or result
+    /// truncation must be accounted for in `T`.
     ///
     /// # Example
     ///
     /// ```ignore
-    /// let tool_calls = chat_response.into_tool_calls().unwrap();
-    /// for tool_call in tool_calls {
-    ///     let response = registry.execute_call(&tool_call).await?;
-    ///     // Handle the response...
+    /// match registry.execute_call_result::<WeatherResult>(&tool_call).await {
+    ///     Ok(weather) => println!("{}°", weather.temperature),
+    ///     Err(ToolCallError::Tool(error)) if error.recoverable => retry(error.message),
+    ///     Err(error) => return Err(error.into()),
     /// }
     /// ```
-    pub async fn execute_call(&self, tool_call: &ToolCall) -> Result<ToolResponse, Box<dyn Error + Send + Sync>> {
-        let handler = self.tools
-            .get(&tool_call.fn_name)
-            .ok_or_else(|| format!("Tool '{}' not found in registry", tool_call.fn_name))?;
+    pub async fn execute_call_result<T>(&self, tool_call: &(impl ToolCallLike + ?Sized)) -> Result<T, ToolCallError>
+    where
+        T: DeserializeOwned,
+    {
+        let response = self.execute_call_as_genai(tool_call).await;
+        let decode_error = |source| ToolCallError::Decode {
+            tool: tool_call.fn_name().to_string(),
+            source,
+        };
+        let content: Value = serde_json::from_str(response.content()).map_err(decode_error)?;
+        if let Some(payload) = ToolErrorPayload::from_envelope(&content) {
+            return Err(ToolCallError::Tool(payload));
+        }
+        serde_json::from_value(content).map_err(decode_error)
+    }
 
-        let result = handler.call_json(tool_call.fn_arguments.clone()).await?;
+    /// Execute a tool call, taking ownership of it.
+    ///
+    /// Behaves like [`execute_call`](Self::execute_call) but moves the
+    /// arguments into the tool instead of cloning them.
+    pub async fn execute_call_owned<C: ToolCallLike>(&self, tool_call: C) -> Result<ToolResponse, BoxError> {
+        let (call_id, fn_name, arguments) = tool_call.into_parts();
+        let handler = self.ready_handler(&fn_name).await?;
+        let arguments = self.preprocess(handler, arguments);
+        self.validate_input(handler, &arguments)?;
+        let result = self.call_handler(handler, &call_id, arguments, None).await?;
+        self.respond(handler, call_id, result)
+    }
 
-        Ok(ToolResponse::new(
-            tool_call.call_id.clone(),
-            serde_json::to_string(&result)?,
-        ))
+    /// Execute a tool call whose arguments are still unparsed JSON text.
+    ///
+    /// Tools generated by `#[tool_function]` deserialize the text directly into
+    /// their parameter type, so no intermediate `serde_json::Value` is built.
+    /// This matters for tools that receive large arrays. When input validation,
+    /// an argument preprocessor, or middleware for the tool is in use, the
+    /// arguments must be parsed into a `Value` anyway, and the call takes the
+    /// same path as [`execute_call`](Self::execute_call).
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let arguments: &RawValue = serde_json::from_str(&raw_arguments_text)?;
+    /// let response = registry.execute_raw("call-1", "sum_samples", arguments).await?;
+    /// ```
+    pub async fn execute_raw(
+        &self,
+        call_id: impl Into<String>,
+        fn_name: &str,
+        arguments: &RawValue,
+    ) -> Result<ToolResponse, BoxError> {
+        let call_id = call_id.into();
+        let handler = self.ready_handler(fn_name).await?;
+        let result = if self.input_validation
+            || self.arg_preprocessor.is_some()
+            || self.middleware.contains_key(handler.name())
+            || !handler.server_only_fields().is_empty()
+            || self.runs_blocking(handler)
+        {
+            let arguments: Value = serde_json::from_str(arguments.get())?;
+            let arguments = self.preprocess(handler, arguments);
+            self.validate_input(handler, &arguments)?;
+            self.call_handler(handler, &call_id, arguments, None).await?
+        } else {
+            handler.call_raw(arguments).await?
+        };
+        self.respond(handler, call_id, result)
+    }
+
+    fn preprocess(&self, handler: &dyn ToolHandler, mut arguments: Value) -> Value {
+        // Server-only fields are never taken from the model
+        if let Value::Object(map) = &mut arguments {
+            for field in handler.server_only_fields() {
+                map.remove(*field);
+            }
+        }
+        match &self.arg_preprocessor {
+            Some(preprocessor) => preprocessor(handler.name(), arguments),
+            None => arguments,
+        }
+    }
+
+    /// Call a tool through its middleware chain, if it has one.
+    fn call_handler<'a>(
+        &'a self,
+        handler: &'a dyn ToolHandler,
+        call_id: &str,
+        arguments: Value,
+        progress: Option<ProgressReporter>,
+    ) -> BoxFuture<'a, Result<Value, BoxError>> {
+        #[cfg(feature = "blocking")]
+        if let Some(shared) = self.tools.get(handler.name()).filter(|_| self.runs_blocking(handler)) {
+            let tool = crate::blocking::BlockingTool::new(Arc::clone(shared));
+            let chain = self.middleware.get(handler.name()).map_or(&[][..], Vec::as_slice);
+            let invocation = ToolInvocation {
+                call_id: call_id.to_string(),
+                tool: handler.name().to_string(),
+                arguments,
+            };
+            return Box::pin(async move { Next::new(&tool, chain, progress).run(invocation).await });
+        }
+        let Some(chain) = self.middleware.get(handler.name()) else {
+            return call_tool(handler, arguments, progress);
+        };
+        let invocation = ToolInvocation {
+            call_id: call_id.to_string(),
+            tool: handler.name().to_string(),
+            arguments,
+        };
+        Next::new(handler, chain, progress).run(invocation)
+    }
+
+    /// Whether calls to `handler` are offloaded to the blocking thread pool.
+    fn runs_blocking(&self, handler: &dyn ToolHandler) -> bool {
+        cfg!(feature = "blocking")
+            && self
+                .blocking
+                .get(handler.name())
+                .copied()
+                .unwrap_or_else(|| handler.is_cpu_bound())
+    }
+
+    /// Look up a tool and check that it is initialized and available.
+    async fn ready_handler(&self, name: &str) -> Result<&dyn ToolHandler, BoxError> {
+        let handler = self
+            .handler(name)
+            .ok_or_else(|| self.not_found(name))?;
+
+        if handler.requires_init() && !self.is_initialized(handler.name()) {
+            return Err(Box::new(RegistryError::NotInitialized {
+                tool: name.to_string(),
+            }));
+        }
+
+        if !handler.is_available().await {
+            return Err(Box::new(RegistryError::Unavailable {
+                tool: name.to_string(),
+            }));
+        }
+
+        Ok(handler)
+    }
+
+    fn validate_input(&self, handler: &dyn ToolHandler, arguments: &Value) -> Result<(), RegistryError> {
+        if self.input_validation {
+            // Positional arguments are validated as the object they map to
+            let named;
+            let arguments = if arguments.is_array() {
+                named = handler.name_arguments(arguments.clone())?;
+                &named
+            } else {
+                arguments
+            };
+            let validator = cached(&self.input_validators, handler.name(), || {
+                Ok(Arc::new(CompiledSchema::new(&self.advertised_schema(handler)?)))
+            })?;
+            validator.validate(arguments).map_err(|violations| {
+                RegistryError::InputValidation {
+                    tool: handler.name().to_string(),
+                    violations,
+                }
+            })?;
+        }
+        Ok(())
+    }
+
+    fn validate_output(&self, handler: &dyn ToolHandler, result: &Value) -> Result<(), RegistryError> {
+        if self.output_validation {
+            let validator = cached(&self.output_validators, handler.name(), || {
+                Ok(handler.output_schema().map(|schema| Arc::new(CompiledSchema::new(&schema))))
+            })?;
+            if let Some(validator) = validator {
+                validator.validate(result).map_err(|violations| {
+                    RegistryError::OutputValidation {
+                        tool: handler.name().to_string(),
+                        violations,
+                    }
+                })?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Run a tool with this registry's checks, middleware and redaction, and
+    /// return its result unserialized, for a tool that wraps this registry.
+    pub(crate) async fn call_nested(
+        &self,
+        name: &str,
+        arguments: Value,
+        progress: Option<ProgressReporter>,
+    ) -> Result<Value, BoxError> {
+        let handler = self.ready_handler(name).await?;
+        let arguments = self.preprocess(handler, arguments);
+        self.validate_input(handler, &arguments)?;
+        let result = self.call_handler(handler, "", arguments, progress).await?;
+        self.validate_output(handler, &result)?;
+        Ok(self.postprocess(result))
+    }
+
+    /// Apply the redactor and float rounding to a successful result.
+    fn postprocess(&self, result: Value) -> Value {
+        let mut result = match &self.result_redactor {
+            Some(redactor) => redactor(result),
+            None => result,
+        };
+        if let Some(decimals) = self.float_precision {
+            round_floats(&mut result, decimals);
+        }
+        result
+    }
+
+    /// Validate a tool's result against its output schema and wrap it in a response.
+    fn respond(&self, handler: &dyn ToolHandler, call_id: String, result: Value) -> Result<ToolResponse, BoxError> {
+        self.respond_within(handler, call_id, result, self.max_result_chars)
+    }
+
+    fn respond_within(
+        &self,
+        handler: &dyn ToolHandler,
+        call_id: String,
+        result: Value,
+        max_chars: Option<usize>,
+    ) -> Result<ToolResponse, BoxError> {
+        self.validate_output(handler, &result)?;
+
+        if handler.responds_directly() {
+            let mut response: ToolResponse = serde_json::from_value(result)?;
+            response.set_call_id(call_id);
+            return Ok(response);
+        }
+
+        let result = self.postprocess(result);
+        let result = match &self.result_envelope {
+            Some(envelope) => envelope(handler.name(), result),
+            None => result,
+        };
+
+        let content = match max_chars {
+            Some(max_chars) => truncate_result(result, max_chars)?,
+            None => serde_json::to_string(&result)?,
+        };
+        Ok(ToolResponse::from_parts(call_id, content))
     }
 
     /// Execute multiple tool calls concurrently.
@@ -145,13 +1776,139 @@ impl ToolRegistry {
     /// let tool_calls = chat_response.into_tool_calls().unwrap();
     /// let responses = registry.execute_calls(&tool_calls).await?;
     /// ```
-    pub async fn execute_calls(&self, tool_calls: &[ToolCall]) -> Result<Vec<ToolResponse>, Box<dyn Error + Send + Sync>> {
+    pub async fn execute_calls<C: ToolCallLike>(&self, tool_calls: &[C]) -> Result<Vec<ToolResponse>, Box<dyn Error + Send + Sync>> {
+        let call_ids = self
+            .duplicate_call_ids
+            .resolve(tool_calls.iter().map(|call| call.call_id()))?;
         let futures: Vec<_> = tool_calls
             .iter()
             .map(|call| self.execute_call(call))
             .collect();
 
-        futures::future::try_join_all(futures).await
+        Ok(with_call_ids(futures::future::try_join_all(futures).await?, call_ids))
+    }
+
+    /// Execute multiple owned tool calls concurrently.
+    ///
+    /// Like [`execute_calls`](Self::execute_calls), without cloning each call's arguments.
+    pub async fn execute_calls_owned<C: ToolCallLike>(&self, tool_calls: Vec<C>) -> Result<Vec<ToolResponse>, BoxError> {
+        let call_ids = self
+            .duplicate_call_ids
+            .resolve(tool_calls.iter().map(|call| call.call_id()))?;
+        let futures: Vec<_> = tool_calls
+            .into_iter()
+            .map(|call| self.execute_call_owned(call))
+            .collect();
+
+        Ok(with_call_ids(futures::future::try_join_all(futures).await?, call_ids))
+    }
+
+    /// Execute multiple tool calls concurrently within a total time budget.
+    ///
+    /// Returns one result per call, in order, once every call has settled or
+    /// `budget` has passed, whichever comes first. Calls that finished in time
+    /// keep their response or error; calls still running are dropped and
+    /// reported as [`RegistryError::Timeout`], which maps to the `timeout`
+    /// category in [`ToolErrorPayload`]. Only duplicate call ids (see
+    /// [`with_duplicate_call_ids`](Self::with_duplicate_call_ids)) fail the
+    /// whole batch.
+    ///
+    /// The budget bounds the batch, not individual calls. A tool with its own
+    /// timeout still fails on that timeout if it comes first, and its error is
+    /// returned as-is; a per-call timeout longer than the remaining budget
+    /// never fires. Dropping a call cancels it at its next `.await`, so tools
+    /// should not rely on running to completion.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let results = registry.execute_calls_within(&tool_calls, Duration::from_secs(10)).await?;
+    /// for (call, result) in tool_calls.iter().zip(results) {
+    ///     let content = match result {
+    ///         Ok(response) => response.content().to_string(),
+    ///         Err(e) => json!({ "error": ToolErrorPayload::from_error(e.as_ref()) }).to_string(),
+    ///     };
+    ///     chat_req = chat_req.append_message(ToolResponse::from_parts(call.call_id().to_string(), content));
+    /// }
+    /// ```
+    pub async fn execute_calls_within<C: ToolCallLike>(
+        &self,
+        tool_calls: &[C],
+        budget: Duration,
+    ) -> Result<Vec<Result<ToolResponse, BoxError>>, RegistryError> {
+        let call_ids = self
+            .duplicate_call_ids
+            .resolve(tool_calls.iter().map(|call| call.call_id()))?;
+        let deadline = Deadline::after(budget);
+        let futures = tool_calls
+            .iter()
+            .map(|call| deadline.bound(call.fn_name(), budget, self.execute_call(call)));
+
+        let mut results = future::join_all(futures).await;
+        if let Some(call_ids) = call_ids {
+            for (result, call_id) in results.iter_mut().zip(call_ids) {
+                if let Ok(response) = result {
+                    response.set_call_id(call_id);
+                }
+            }
+        }
+        Ok(results)
+    }
+
+    pub(crate) fn duplicate_call_ids(&self) -> DuplicateCallIds {
+        self.duplicate_call_ids
+    }
+
+    /// Attach every registered tool to a chat request.
+    ///
+    /// Tools already on the request are kept. Equivalent to appending each
+    /// tool from [`get_tools`](Self::get_tools).
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let chat_req = registry.apply_to_request(ChatRequest::new(messages));
+    /// let chat_res = client.exec_chat("gpt-4", chat_req, None).await?;
+    /// ```
+    pub fn apply_to_request(&self, request: ChatRequest) -> ChatRequest {
+        self.get_tools()
+            .into_iter()
+            .fold(request, |request, tool| request.append_tool(tool))
+    }
+
+    /// Execute every tool call in a chat response concurrently.
+    ///
+    /// Returns an empty list if the response contains no tool calls.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let chat_res = client.exec_chat("gpt-4", chat_req, None).await?;
+    /// for response in registry.handle_response(&chat_res).await? {
+    ///     chat_req = chat_req.append_message(response);
+    /// }
+    /// ```
+    pub async fn handle_response(&self, response: &ChatResponse) -> Result<Vec<ToolResponse>, BoxError> {
+        let tool_calls = response.tool_calls().unwrap_or_default();
+        let call_ids = self
+            .duplicate_call_ids
+            .resolve(tool_calls.iter().map(|call| call.call_id()))?;
+        let futures: Vec<_> = tool_calls
+            .into_iter()
+            .map(|call| self.execute_call(call))
+            .collect();
+
+        Ok(with_call_ids(futures::future::try_join_all(futures).await?, call_ids))
+    }
+
+    /// Iterate over the registered tools as `(name, handler)` pairs.
+    ///
+    /// The iteration order is unspecified. `&ToolRegistry` also implements
+    /// `IntoIterator`, so `for (name, handler) in &registry` works too.
+    pub fn iter(&self) -> ToolIter<'_> {
+        ToolIter {
+            inner: self.tools.iter(),
+        }
     }
 
     /// Get the names of all registered tools.
@@ -159,11 +1916,63 @@ impl ToolRegistry {
         self.tools.keys().map(|s| s.as_str()).collect()
     }
 
+    /// Create a view exposing only the tools named in `allowed`.
+    ///
+    /// The view borrows this registry's handlers rather than copying them, which
+    /// makes it cheap to serve many tenants with overlapping tool sets from one
+    /// registry. Names in `allowed` that are not registered are ignored.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let allowed: HashSet<String> = ["get_weather".to_string()].into();
+    /// let view = registry.view(&allowed);
+    /// let tools = view.get_tools();
+    /// ```
+    pub fn view<'a>(&'a self, allowed: &'a HashSet<String>) -> RegistryView<'a> {
+        RegistryView::new(self, allowed)
+    }
+
+    /// A [`RegistryError::ToolNotFound`] suggesting the registered names closest to `name`.
+    pub(crate) fn not_found(&self, name: &str) -> RegistryError {
+        RegistryError::ToolNotFound {
+            tool: name.to_string(),
+            suggestions: closest_names(name, self.tools.keys().map(|name| self.advertised_name(name))),
+        }
+    }
+
+    pub(crate) fn handler(&self, name: &str) -> Option<&dyn ToolHandler> {
+        self.tools.get(self.resolve_name(name)).map(|handler| handler.as_ref())
+    }
+
     /// Check if a tool with the given name is registered.
     pub fn has_tool(&self, name: &str) -> bool {
         self.tools.contains_key(name)
     }
 
+    /// Check if a tool with the given name is registered and terminal, i.e. its
+    /// result is meant for the user rather than the model.
+    pub fn is_terminal(&self, name: &str) -> bool {
+        self.handler(name).is_some_and(|handler| handler.is_terminal())
+    }
+
+    /// Check that every tool in `required` is registered.
+    pub fn contains_all(&self, required: &[&str]) -> bool {
+        required.iter().all(|name| self.has_tool(name))
+    }
+
+    /// Get the names in `required` that are not registered, in their original order.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let missing = registry.missing(&["get_weather", "search_web"]);
+    /// assert!(missing.is_empty(), "missing tools: {:?}", missing);
+    /// ```
+    pub fn missing<'a>(&self, required: &'a [&'a str]) -> Vec<&'a str> {
+        required.iter().copied().filter(|name| !self.has_tool(name)).collect()
+    }
+
     /// Get the number of registered tools.
     pub fn len(&self) -> usize {
         self.tools.len()
@@ -178,11 +1987,29 @@ impl ToolRegistry {
     ///
     /// Returns `true` if the tool was found and removed, `false` otherwise.
     pub fn remove_tool(&mut self, name: &str) -> bool {
+        self.invalidate_schema(name);
+        self.initialized
+            .get_mut()
+            .unwrap_or_else(PoisonError::into_inner)
+            .remove(name);
+        self.middleware.remove(name);
+        self.descriptions.remove(name);
+        self.blocking.remove(name);
+        self.advertised_names.remove(name);
         self.tools.remove(name).is_some()
     }
 
     /// Clear all tools from the registry.
     pub fn clear(&mut self) {
+        self.invalidate_schemas();
+        self.initialized
+            .get_mut()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clear();
+        self.middleware.clear();
+        self.descriptions.clear();
+        self.blocking.clear();
+        self.advertised_names.clear();
         self.tools.clear();
     }
 
@@ -190,11 +2017,215 @@ impl ToolRegistry {
     ///
     /// Tools from the other registry will be added to this one.
     /// If there are name conflicts, the tools from the other registry will overwrite
-    /// the existing ones.
-    pub fn merge(&mut self, other: ToolRegistry) -> &mut Self {
-        self.tools.extend(other.tools);
+    /// the existing ones. An advertised name from the other registry that is
+    /// already the name or advertised name of a tool here is dropped, and the
+    /// tool is advertised under its own name.
+    ///
+    /// # Panics
+    ///
+    /// Panics, before anything is merged, if
+    /// [descriptions are required](Self::with_require_descriptions) and a tool
+    /// from the other registry has none.
+    pub fn merge(&mut self, mut other: ToolRegistry) -> &mut Self {
+        for (name, handler) in &other.tools {
+            // Overrides from the other registry replace ours
+            let description = other
+                .descriptions
+                .get(name)
+                .or_else(|| self.descriptions.get(name))
+                .and_then(|overrides| overrides.tool.as_deref())
+                .unwrap_or_else(|| handler.description());
+            if let Err(e) = self.require_description(name, description) {
+                panic!("{}", e);
+            }
+        }
+        for (name, chain) in other.middleware.drain() {
+            self.middleware.entry(name).or_default().extend(chain);
+        }
+        self.descriptions.extend(other.descriptions.drain());
+        self.blocking.extend(other.blocking.drain());
+        for (_, handler) in other.tools {
+            self.insert_shared(handler);
+        }
+        // Imported tools stay initialized; replaced ones were reset above
+        self.initialized
+            .get_mut()
+            .unwrap_or_else(PoisonError::into_inner)
+            .extend(other.initialized.into_inner().unwrap_or_else(PoisonError::into_inner));
+        for (name, advertised) in other.advertised_names {
+            if !self.name_taken(&name, &advertised) {
+                self.advertised_names.insert(name, advertised);
+            }
+        }
         self
     }
+
+    /// Merge another registry into this one, prefixing each imported tool name.
+    ///
+    /// The prefix is prepended as-is, so include any separator you want (e.g.
+    /// `"fs_"`). Unlike [`merge`](Self::merge), existing tools are never
+    /// overwritten: if any prefixed name or prefixed advertised name is already
+    /// taken, nothing is imported and [`RegistryError::DuplicateTool`] is
+    /// returned. Likewise, if [descriptions are required](Self::with_require_descriptions)
+    /// and an imported tool has none, nothing is imported and
+    /// [`RegistryError::MissingDescription`] is returned.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let mut registry = ToolRegistry::new();
+    /// registry.merge_prefixed("fs_", filesystem_tools())?;
+    /// registry.merge_prefixed("web_", web_tools())?;
+    /// assert!(registry.has_tool("fs_read_file"));
+    /// ```
+    pub fn merge_prefixed(&mut self, prefix: &str, other: ToolRegistry) -> Result<&mut Self, RegistryError> {
+        if let Some(name) = other
+            .tools
+            .keys()
+            .map(|name| format!("{}{}", prefix, name))
+            .find(|name| self.has_tool(name) || self.resolve_name(name) != name)
+        {
+            return Err(RegistryError::DuplicateTool { tool: name });
+        }
+        if let Some(advertised) = other
+            .advertised_names
+            .values()
+            .map(|advertised| format!("{}{}", prefix, advertised))
+            .find(|advertised| self.has_tool(advertised) || self.resolve_name(advertised) != advertised)
+        {
+            return Err(RegistryError::DuplicateTool { tool: advertised });
+        }
+        for (name, handler) in &other.tools {
+            self.require_description(&format!("{}{}", prefix, name), other.advertised_description(handler.as_ref()))?;
+        }
+
+        for (name, chain) in other.middleware {
+            self.middleware.insert(format!("{}{}", prefix, name), chain);
+        }
+        for (name, overrides) in other.descriptions {
+            self.descriptions.insert(format!("{}{}", prefix, name), overrides);
+        }
+        for (name, blocking) in other.blocking {
+            self.blocking.insert(format!("{}{}", prefix, name), blocking);
+        }
+        for (name, handler) in other.tools {
+            let prefixed = format!("{}{}", prefix, name);
+            self.insert_handler(Box::new(RenamedTool::new(prefixed, Box::new(SharedTool::new(handler)))));
+        }
+        let initialized = other.initialized.into_inner().unwrap_or_else(PoisonError::into_inner);
+        self.initialized
+            .get_mut()
+            .unwrap_or_else(PoisonError::into_inner)
+            .extend(initialized.into_iter().map(|name| format!("{}{}", prefix, name)));
+        for (name, advertised) in other.advertised_names {
+            self.advertised_names.insert(format!("{}{}", prefix, name), format!("{}{}", prefix, advertised));
+        }
+        Ok(self)
+    }
+}
+
+/// Descriptions set at runtime with `set_description` and `set_tool_field_descriptions`.
+#[derive(Clone, Default)]
+struct DescriptionOverride {
+    tool: Option<String>,
+    fields: HashMap<String, String>,
+}
+
+impl DescriptionOverride {
+    fn apply_to(&self, schema: &mut Value) {
+        let Some(properties) = schema.get_mut("properties").and_then(Value::as_object_mut) else {
+            return;
+        };
+        for (field, description) in &self.fields {
+            if let Some(Value::Object(property)) = properties.get_mut(field) {
+                property.insert("description".to_string(), Value::String(description.clone()));
+            }
+        }
+    }
+}
+
+/// The entry for `name` in `cache`, built and stored on first use.
+fn cached<T: Clone>(
+    cache: &RwLock<HashMap<String, T>>,
+    name: &str,
+    build: impl FnOnce() -> Result<T, RegistryError>,
+) -> Result<T, RegistryError> {
+    if let Some(entry) = cache.read().unwrap_or_else(PoisonError::into_inner).get(name) {
+        return Ok(entry.clone());
+    }
+    let entry = build()?;
+    cache
+        .write()
+        .unwrap_or_else(PoisonError::into_inner)
+        .insert(name.to_string(), entry.clone());
+    Ok(entry)
+}
+
+/// Apply the ids chosen by [`DuplicateCallIds::resolve`], if any.
+fn with_call_ids(mut responses: Vec<ToolResponse>, call_ids: Option<Vec<String>>) -> Vec<ToolResponse> {
+    if let Some(call_ids) = call_ids {
+        for (response, call_id) in responses.iter_mut().zip(call_ids) {
+            response.set_call_id(call_id);
+        }
+    }
+    responses
+}
+
+/// In debug builds, panic if one of the tool's examples doesn't deserialize
+/// into its params type, so a stale example fails tests instead of teaching
+/// the model a call that can't succeed.
+fn check_examples<T: ToolFunction>(tool: &T) {
+    if !cfg!(debug_assertions) {
+        return;
+    }
+    for example in ToolFunction::examples(tool) {
+        if let Err(e) = serde_json::from_value::<T::Params>(example.clone()) {
+            panic!(
+                "Example {} of tool '{}' does not deserialize into {}: {}",
+                example,
+                ToolFunction::name(tool),
+                std::any::type_name::<T::Params>(),
+                e
+            );
+        }
+    }
+}
+
+fn schema_of(handler: &dyn ToolHandler) -> Result<Value, RegistryError> {
+    handler.try_schema().map_err(|source| RegistryError::Schema {
+        tool: handler.name().to_string(),
+        source,
+    })
+}
+
+/// Iterator over the tools in a [`ToolRegistry`], created by [`ToolRegistry::iter`].
+pub struct ToolIter<'a> {
+    inner: hash_map::Iter<'a, String, Arc<dyn ToolHandler>>,
+}
+
+impl<'a> Iterator for ToolIter<'a> {
+    type Item = (&'a str, &'a dyn ToolHandler);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner
+            .next()
+            .map(|(name, handler)| (name.as_str(), handler.as_ref()))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl ExactSizeIterator for ToolIter<'_> {}
+
+impl<'a> IntoIterator for &'a ToolRegistry {
+    type Item = (&'a str, &'a dyn ToolHandler);
+    type IntoIter = ToolIter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
 }
 
 impl Default for ToolRegistry {
@@ -0,0 +1,125 @@
+use crate::binary::BinaryOutput;
+use crate::cost::CostHint;
+use crate::error::RegistryError;
+use crate::progress::ProgressReporter;
+use crate::traits::{BoxError, BoxFuture, ToolHandler};
+use serde_json::value::RawValue;
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// Marks a handler whose result is a serialized `ToolResponse` to send as-is.
+pub(crate) struct DirectResponseTool {
+    inner: Box<dyn ToolHandler>,
+}
+
+impl DirectResponseTool {
+    pub(crate) fn new(inner: Box<dyn ToolHandler>) -> Self {
+        Self { inner }
+    }
+}
+
+impl ToolHandler for DirectResponseTool {
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    fn description(&self) -> &str {
+        self.inner.description()
+    }
+
+    fn schema(&self) -> Value {
+        self.inner.schema()
+    }
+
+    fn try_schema(&self) -> Result<Value, serde_json::Error> {
+        self.inner.try_schema()
+    }
+
+    fn output_schema(&self) -> Option<Value> {
+        self.inner.output_schema()
+    }
+
+    fn params_type_name(&self) -> &'static str {
+        self.inner.params_type_name()
+    }
+
+    fn error_type_name(&self) -> &'static str {
+        self.inner.error_type_name()
+    }
+
+    fn positional_fields(&self) -> Option<&'static [&'static str]> {
+        self.inner.positional_fields()
+    }
+
+    fn name_arguments(&self, arguments: Value) -> Result<Value, RegistryError> {
+        self.inner.name_arguments(arguments)
+    }
+
+    fn property_order(&self) -> Option<&'static [&'static str]> {
+        self.inner.property_order()
+    }
+
+    fn accepted_fields(&self) -> Option<&'static [&'static str]> {
+        self.inner.accepted_fields()
+    }
+
+    fn server_only_fields(&self) -> &'static [&'static str] {
+        self.inner.server_only_fields()
+    }
+
+    fn cost_hint(&self) -> CostHint {
+        self.inner.cost_hint()
+    }
+
+    fn is_cpu_bound(&self) -> bool {
+        self.inner.is_cpu_bound()
+    }
+
+    fn metadata(&self) -> HashMap<String, String> {
+        self.inner.metadata()
+    }
+
+    fn examples(&self) -> &[Value] {
+        self.inner.examples()
+    }
+
+    fn is_terminal(&self) -> bool {
+        self.inner.is_terminal()
+    }
+
+    fn requires_init(&self) -> bool {
+        self.inner.requires_init()
+    }
+
+    fn init(&self) -> BoxFuture<'_, Result<(), BoxError>> {
+        self.inner.init()
+    }
+
+    fn call_json(&self, params: Value) -> BoxFuture<'_, Result<Value, BoxError>> {
+        self.inner.call_json(params)
+    }
+
+    fn call_json_with_progress(
+        &self,
+        params: Value,
+        progress: ProgressReporter,
+    ) -> BoxFuture<'_, Result<Value, BoxError>> {
+        self.inner.call_json_with_progress(params, progress)
+    }
+
+    fn call_raw(&self, params: &RawValue) -> BoxFuture<'_, Result<Value, BoxError>> {
+        self.inner.call_raw(params)
+    }
+
+    fn call_binary(&self, params: Value) -> BoxFuture<'_, Result<BinaryOutput, BoxError>> {
+        self.inner.call_binary(params)
+    }
+
+    fn is_available(&self) -> BoxFuture<'_, bool> {
+        self.inner.is_available()
+    }
+
+    fn responds_directly(&self) -> bool {
+        true
+    }
+}
@@ -0,0 +1,768 @@
+//! Helpers for adjusting generated JSON schemas.
+//!
+//! schemars derives a schema from the shape of a Rust type, which cannot
+//! express every constraint a tool cares about. These functions patch a schema
+//! in place and are meant to be called from
+//! [`ToolRegistry::with_schema_transform`](crate::ToolRegistry::with_schema_transform).
+//! Some are also applied by `#[tool_function]` for constraints declared on the
+//! macro, such as `one_of(...)`.
+
+use serde::de::{DeserializeOwned, Deserializer, Error as _, Visitor};
+use serde_json::{json, Map, Value};
+use std::any::TypeId;
+use std::collections::{BTreeSet, HashMap};
+use std::sync::{OnceLock, PoisonError, RwLock};
+
+/// The JSON schema of params type `P`, generated once per process.
+///
+/// Tools that share a params type share the generated schema: the first call
+/// runs schemars and later calls clone the cached value. Both the default
+/// [`ToolFunction::try_schema`](crate::ToolFunction::try_schema) and the one
+/// `#[tool_function]` generates start from it, so per-tool adjustments such as
+/// `one_of(...)` are applied to a copy and never leak between tools.
+pub fn params_schema<P: schemars::JsonSchema + 'static>() -> Result<Value, serde_json::Error> {
+    static CACHE: OnceLock<RwLock<HashMap<TypeId, Value>>> = OnceLock::new();
+    let cache = CACHE.get_or_init(Default::default);
+
+    let cached = cache
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .get(&TypeId::of::<P>())
+        .cloned();
+    if let Some(schema) = cached {
+        return Ok(schema);
+    }
+    let schema = serde_json::to_value(schemars::schema_for!(P))?;
+    cache
+        .write()
+        .unwrap_or_else(PoisonError::into_inner)
+        .insert(TypeId::of::<P>(), schema.clone());
+    Ok(schema)
+}
+
+/// Require `requires` whenever `field` is present, using `dependentRequired`.
+///
+/// Existing `dependentRequired` entries for `field` are extended rather than
+/// replaced, and names already listed are not repeated. Boolean schemas are
+/// left untouched.
+///
+/// # Example
+///
+/// ```ignore
+/// let registry = ToolRegistry::new().with_schema_transform(|mut schema| {
+///     if schema["title"] == "CalculatorParams" {
+///         schema::add_dependent_required(&mut schema, "divisor", &["dividend"]);
+///     }
+///     schema
+/// });
+/// ```
+pub fn add_dependent_required(schema: &mut Value, field: &str, requires: &[&str]) {
+    let Some(object) = schema.as_object_mut() else {
+        return;
+    };
+
+    let dependencies = object
+        .entry("dependentRequired")
+        .or_insert_with(|| Value::Object(Map::new()));
+    let Some(dependencies) = dependencies.as_object_mut() else {
+        return;
+    };
+
+    let required = dependencies
+        .entry(field)
+        .or_insert_with(|| Value::Array(Vec::new()));
+    let Some(required) = required.as_array_mut() else {
+        return;
+    };
+
+    for name in requires {
+        if !required.iter().any(|existing| existing == name) {
+            required.push(Value::String(name.to_string()));
+        }
+    }
+}
+
+/// Require exactly one of `fields` to be present, using an object-level `oneOf`.
+///
+/// If the schema already has a `oneOf`, the new group is added under `allOf`
+/// so both constraints apply. Fails if a field is not one of the schema's
+/// `properties`, which catches typos in the field names.
+///
+/// # Example
+///
+/// ```ignore
+/// // `by_id` XOR `by_name`
+/// schema::add_one_of(&mut schema, &["by_id", "by_name"])?;
+/// ```
+pub fn add_one_of(schema: &mut Value, fields: &[&str]) -> Result<(), serde_json::Error> {
+    let properties = schema.get("properties").and_then(Value::as_object);
+    if let Some(field) = fields
+        .iter()
+        .find(|field| !properties.is_some_and(|properties| properties.contains_key(**field)))
+    {
+        return Err(serde_json::Error::custom(format!(
+            "one_of field '{}' is not a parameter",
+            field
+        )));
+    }
+
+    let group: Vec<Value> = fields.iter().map(|field| json!({ "required": [field] })).collect();
+    let Some(object) = schema.as_object_mut() else {
+        return Ok(());
+    };
+    if object.contains_key("oneOf") {
+        let all_of = object.entry("allOf").or_insert_with(|| Value::Array(Vec::new()));
+        if let Some(all_of) = all_of.as_array_mut() {
+            all_of.push(json!({ "oneOf": group }));
+        }
+    } else {
+        object.insert("oneOf".to_string(), Value::Array(group));
+    }
+    Ok(())
+}
+
+/// Require `then_required` whenever `when_field` equals `equals`, using `if`/`then`.
+///
+/// This expresses fields that only apply to one value of an enum
+/// discriminant, which schemars cannot infer from the Rust type. If the schema
+/// already has an `if`, the new condition is added under `allOf` so both
+/// apply. Boolean schemas are left untouched.
+///
+/// # Example
+///
+/// ```ignore
+/// let registry = ToolRegistry::new().with_schema_transform(|mut schema| {
+///     if schema["title"] == "ShipParams" {
+///         // A delivery address is only needed for deliveries
+///         schema::add_conditional(&mut schema, "method", json!("delivery"), &["address"]);
+///     }
+///     schema
+/// });
+/// ```
+pub fn add_conditional(schema: &mut Value, when_field: &str, equals: Value, then_required: &[&str]) {
+    let Some(object) = schema.as_object_mut() else {
+        return;
+    };
+    let condition = json!({ "properties": { when_field: { "const": equals } }, "required": [when_field] });
+    let consequence = json!({ "required": then_required });
+    if object.contains_key("if") {
+        let all_of = object.entry("allOf").or_insert_with(|| Value::Array(Vec::new()));
+        if let Some(all_of) = all_of.as_array_mut() {
+            all_of.push(json!({ "if": condition, "then": consequence }));
+        }
+    } else {
+        object.insert("if".to_string(), condition);
+        object.insert("then".to_string(), consequence);
+    }
+}
+
+/// Remove `fields` from the schema's `properties` and `required`.
+///
+/// Unknown field names are ignored. `#[tool_function(server_only(...))]` uses
+/// this to hide parameters the model must not set.
+pub fn remove_properties(schema: &mut Value, fields: &[&str]) {
+    if let Some(properties) = schema.get_mut("properties").and_then(Value::as_object_mut) {
+        for field in fields {
+            properties.remove(*field);
+        }
+    }
+    if let Some(required) = schema.get_mut("required").and_then(Value::as_array_mut) {
+        required.retain(|name| !fields.iter().any(|field| name == field));
+    }
+}
+
+/// Remove the root `$schema` and `title` keywords, which strict providers
+/// reject.
+///
+/// `#[tool_function(schema_meta = false)]` uses this for a single tool; apply
+/// it in a schema transform to strip every tool.
+pub fn remove_meta(schema: &mut Value) {
+    if let Some(object) = schema.as_object_mut() {
+        object.remove("$schema");
+        object.remove("title");
+    }
+}
+
+/// Append `example` to the schema's `examples`.
+///
+/// Boolean schemas are left untouched.
+pub fn add_example(schema: &mut Value, example: Value) {
+    let Some(object) = schema.as_object_mut() else {
+        return;
+    };
+    let examples = object.entry("examples").or_insert_with(|| Value::Array(Vec::new()));
+    if let Some(examples) = examples.as_array_mut() {
+        examples.push(example);
+    }
+}
+
+/// Mark `fields` as `readOnly`: produced by the tool's owner, not to be supplied by the model.
+///
+/// Fields can also be marked on the params type with
+/// `#[schemars(extend("readOnly" = true))]`. Unknown field names are ignored.
+pub fn mark_read_only(schema: &mut Value, fields: &[&str]) {
+    annotate_properties(schema, fields, "readOnly");
+}
+
+/// Mark `fields` as `writeOnly`: accepted as input but never returned.
+///
+/// Fields can also be marked on the params type with
+/// `#[schemars(extend("writeOnly" = true))]`. Unknown field names are ignored.
+pub fn mark_write_only(schema: &mut Value, fields: &[&str]) {
+    annotate_properties(schema, fields, "writeOnly");
+}
+
+fn annotate_properties(schema: &mut Value, fields: &[&str], keyword: &str) {
+    let Some(properties) = schema.get_mut("properties").and_then(Value::as_object_mut) else {
+        return;
+    };
+    for field in fields {
+        if let Some(Value::Object(property)) = properties.get_mut(*field) {
+            property.insert(keyword.to_string(), Value::Bool(true));
+        }
+    }
+}
+
+/// Set `propertyOrdering`, the order in which Gemini generates the object's
+/// properties.
+///
+/// Properties named in `order` come first, in that order; the rest follow in
+/// the order they appear in `properties`. Names that aren't properties are
+/// ignored, and schemas without `properties` are left untouched. Only the root
+/// object is ordered.
+///
+/// [`ToolRegistry::with_property_ordering`](crate::ToolRegistry::with_property_ordering)
+/// applies this to every tool using the params struct's declaration order.
+pub fn with_property_ordering(schema: &mut Value, order: &[&str]) {
+    let Some(properties) = schema.get("properties").and_then(Value::as_object) else {
+        return;
+    };
+    let mut ordering: Vec<&str> = order
+        .iter()
+        .copied()
+        .filter(|name| properties.contains_key(*name))
+        .collect();
+    let rest: Vec<&str> = properties
+        .keys()
+        .map(String::as_str)
+        .filter(|name| !ordering.contains(name))
+        .collect();
+    ordering.extend(rest);
+    let ordering = ordering.into_iter().map(|name| Value::String(name.to_string())).collect();
+    if let Some(object) = schema.as_object_mut() {
+        object.insert("propertyOrdering".to_string(), Value::Array(ordering));
+    }
+}
+
+/// The serialized field names of struct `T` in declaration order, as known to
+/// its `Deserialize` impl, or `None` if `T` doesn't deserialize as a plain
+/// struct (e.g. it has `#[serde(flatten)]` fields or is an enum).
+pub(crate) fn serde_field_order<T: DeserializeOwned>() -> Option<&'static [&'static str]> {
+    let mut fields = None;
+    let _ = T::deserialize(FieldCollector(&mut fields));
+    fields
+}
+
+/// A deserializer that fails immediately, recording the field list it was
+/// asked for if the target is a struct.
+struct FieldCollector<'a>(&'a mut Option<&'static [&'static str]>);
+
+impl<'de> Deserializer<'de> for FieldCollector<'_> {
+    type Error = serde::de::value::Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, _visitor: V) -> Result<V::Value, Self::Error> {
+        Err(Self::Error::custom("not a struct"))
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        fields: &'static [&'static str],
+        _visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        *self.0 = Some(fields);
+        Err(Self::Error::custom("field names collected"))
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct newtype_struct seq tuple
+        tuple_struct map enum identifier ignored_any
+    }
+}
+
+/// Replace `$ref`s into the root `$defs` with the referenced definitions,
+/// for providers that don't resolve references.
+///
+/// Keywords next to a `$ref`, such as a field's `description`, take precedence
+/// over the definition's. Recursive types can't be expanded fully, so the
+/// reference that closes a cycle is kept, along with the `$defs` entries it
+/// needs; `$defs` is removed once nothing refers to it. References to the
+/// root (`#`) are always kept.
+///
+/// # Example
+///
+/// ```ignore
+/// let registry = ToolRegistry::new().with_schema_transform(|mut schema| {
+///     schema::inline_refs(&mut schema);
+///     schema
+/// });
+/// ```
+pub fn inline_refs(schema: &mut Value) {
+    let Some(Value::Object(defs)) = schema.as_object_mut().and_then(|object| object.remove("$defs")) else {
+        return;
+    };
+
+    let mut recursive = BTreeSet::new();
+    inline_refs_in(schema, &defs, &mut Vec::new(), &mut recursive);
+
+    // Definitions that are still referenced are themselves inlined, except
+    // for their own recursive references
+    let mut kept = Map::new();
+    let mut pending: Vec<String> = recursive.into_iter().collect();
+    while let Some(name) = pending.pop() {
+        if kept.contains_key(&name) {
+            continue;
+        }
+        let mut definition = defs[&name].clone();
+        let mut referenced = BTreeSet::new();
+        inline_refs_in(&mut definition, &defs, &mut vec![name.clone()], &mut referenced);
+        pending.extend(referenced);
+        kept.insert(name, definition);
+    }
+    if !kept.is_empty() {
+        schema["$defs"] = Value::Object(kept);
+    }
+}
+
+/// Inline references in `value`. `expanding` lists the definitions currently
+/// being inlined; references back to one of them are kept and recorded in
+/// `recursive`.
+fn inline_refs_in(value: &mut Value, defs: &Map<String, Value>, expanding: &mut Vec<String>, recursive: &mut BTreeSet<String>) {
+    match value {
+        Value::Array(items) => {
+            for item in items {
+                inline_refs_in(item, defs, expanding, recursive);
+            }
+        }
+        Value::Object(object) => {
+            for child in object.values_mut() {
+                inline_refs_in(child, defs, expanding, recursive);
+            }
+
+            let Some(name) = object
+                .get("$ref")
+                .and_then(Value::as_str)
+                .and_then(|reference| reference.strip_prefix("#/$defs/"))
+                .map(|name| name.replace("~1", "/").replace("~0", "~"))
+            else {
+                return;
+            };
+            let Some(definition) = defs.get(&name) else {
+                return;
+            };
+            if expanding.contains(&name) {
+                recursive.insert(name);
+                return;
+            }
+
+            let mut inlined = definition.clone();
+            expanding.push(name);
+            inline_refs_in(&mut inlined, defs, expanding, recursive);
+            expanding.pop();
+
+            object.remove("$ref");
+            match inlined {
+                Value::Object(definition) => {
+                    for (key, keyword) in definition {
+                        object.entry(key).or_insert(keyword);
+                    }
+                }
+                // `false` rejects everything, whatever else the reference said
+                Value::Bool(false) => *value = Value::Bool(false),
+                _ => {}
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Serialize `value` as compact JSON with object keys sorted recursively.
+///
+/// Values that are equal regardless of key order produce identical strings,
+/// which makes the output suitable for cache keys and argument hashing.
+///
+/// # Example
+///
+/// ```ignore
+/// let a = schema::canonical_json(&json!({ "b": 1, "a": { "y": 2, "x": 3 } }));
+/// assert_eq!(a, r#"{"a":{"x":3,"y":2},"b":1}"#);
+/// ```
+pub fn canonical_json(value: &Value) -> String {
+    let mut out = String::new();
+    write_canonical(value, &mut out);
+    out
+}
+
+fn write_canonical(value: &Value, out: &mut String) {
+    match value {
+        Value::Array(items) => {
+            out.push('[');
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_canonical(item, out);
+            }
+            out.push(']');
+        }
+        Value::Object(map) => {
+            let mut entries: Vec<_> = map.iter().collect();
+            entries.sort_by_key(|(key, _)| *key);
+            out.push('{');
+            for (i, (key, item)) in entries.into_iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                out.push_str(&Value::String(key.clone()).to_string());
+                out.push(':');
+                write_canonical(item, out);
+            }
+            out.push('}');
+        }
+        scalar => out.push_str(&scalar.to_string()),
+    }
+}
+
+/// Rebuild `value` with object keys in sorted order, recursively.
+///
+/// Serializing the result gives the same key order as
+/// [`canonical_json`], whether or not serde_json preserves insertion order.
+pub(crate) fn sort_keys(value: Value) -> Value {
+    match value {
+        Value::Array(items) => Value::Array(items.into_iter().map(sort_keys).collect()),
+        Value::Object(map) => {
+            let mut entries: Vec<_> = map.into_iter().collect();
+            entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+            Value::Object(entries.into_iter().map(|(key, item)| (key, sort_keys(item))).collect())
+        }
+        scalar => scalar,
+    }
+}
+
+/// Render a JSON schema as TypeScript declarations, e.g. for generating UIs
+/// from a tool's parameters.
+///
+/// The root becomes `export interface {type_name}` (or `export type` if it
+/// isn't an object), and each entry of `$defs` gets a declaration of its own,
+/// referenced by name. Optional properties are marked with `?`, `enum` and
+/// `oneOf`/`anyOf` become union types, and descriptions become doc comments.
+/// Constraints without a TypeScript counterpart, such as numeric bounds, are
+/// dropped; anything unrecognized is typed `unknown`.
+///
+/// # Example
+///
+/// ```ignore
+/// let ts = schema::to_typescript(&get_weather_tool().schema(), "WeatherParams");
+/// // export interface WeatherParams {
+/// //   /** The city name */
+/// //   city: string;
+/// //   unit: TemperatureUnit;
+/// // }
+/// // ...
+/// ```
+pub fn to_typescript(schema: &Value, type_name: &str) -> String {
+    let mut out = String::new();
+    write_declaration(&mut out, type_name, schema, type_name);
+    if let Some(defs) = schema.get("$defs").and_then(Value::as_object) {
+        for (name, def) in defs {
+            out.push('\n');
+            write_declaration(&mut out, name, def, type_name);
+        }
+    }
+    out
+}
+
+/// `root` names the root declaration, which recursive schemas reference as `#`.
+fn write_declaration(out: &mut String, name: &str, schema: &Value, root: &str) {
+    write_doc_comment(out, schema, "");
+    match schema.get("properties").and_then(Value::as_object) {
+        Some(properties) => {
+            out.push_str(&format!("export interface {} {{\n", name));
+            let required = required_fields(schema);
+            for (field, property) in properties {
+                write_doc_comment(out, property, "  ");
+                let optional = if required.contains(&field.as_str()) { "" } else { "?" };
+                out.push_str(&format!("  {}{}: {};\n", ts_key(field), optional, ts_type(property, root)));
+            }
+            out.push_str("}\n");
+        }
+        None => out.push_str(&format!("export type {} = {};\n", name, ts_type(schema, root))),
+    }
+}
+
+fn write_doc_comment(out: &mut String, schema: &Value, indent: &str) {
+    let Some(description) = schema.get("description").and_then(Value::as_str) else {
+        return;
+    };
+    let description = description.replace("*/", "*\\/");
+    if !description.contains('\n') {
+        out.push_str(&format!("{}/** {} */\n", indent, description));
+        return;
+    }
+    out.push_str(&format!("{}/**\n", indent));
+    for line in description.lines() {
+        match line {
+            "" => out.push_str(&format!("{} *\n", indent)),
+            line => out.push_str(&format!("{} * {}\n", indent, line)),
+        }
+    }
+    out.push_str(&format!("{} */\n", indent));
+}
+
+fn required_fields(schema: &Value) -> Vec<&str> {
+    schema
+        .get("required")
+        .and_then(Value::as_array)
+        .map(|required| required.iter().filter_map(Value::as_str).collect())
+        .unwrap_or_default()
+}
+
+/// Property names that aren't plain identifiers have to be quoted.
+fn ts_key(name: &str) -> String {
+    let mut chars = name.chars();
+    let is_identifier = chars
+        .next()
+        .is_some_and(|first| first.is_ascii_alphabetic() || first == '_' || first == '$')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$');
+    if is_identifier {
+        name.to_string()
+    } else {
+        Value::String(name.to_string()).to_string()
+    }
+}
+
+fn ts_type(schema: &Value, root: &str) -> String {
+    let object = match schema {
+        Value::Bool(false) => return "never".to_string(),
+        Value::Object(object) => object,
+        _ => return "unknown".to_string(),
+    };
+
+    if let Some(reference) = object.get("$ref").and_then(Value::as_str) {
+        if reference == "#" {
+            return root.to_string();
+        }
+        return reference.rsplit('/').next().unwrap_or(reference).to_string();
+    }
+    // JSON literals are valid TypeScript literal types
+    if let Some(value) = object.get("const") {
+        return value.to_string();
+    }
+    if let Some(values) = object.get("enum").and_then(Value::as_array) {
+        return union(values.iter().map(Value::to_string));
+    }
+    if let Some(variants) = ["oneOf", "anyOf"].iter().find_map(|key| object.get(*key).and_then(Value::as_array)) {
+        return union(variants.iter().map(|variant| ts_type(variant, root)));
+    }
+    if let Some(parts) = object.get("allOf").and_then(Value::as_array) {
+        return parts.iter().map(|part| grouped(ts_type(part, root))).collect::<Vec<_>>().join(" & ");
+    }
+
+    match object.get("type") {
+        Some(Value::String(kind)) => ts_primitive(kind, schema, root),
+        Some(Value::Array(kinds)) => {
+            union(kinds.iter().filter_map(Value::as_str).map(|kind| ts_primitive(kind, schema, root)))
+        }
+        _ if object.contains_key("properties") => ts_primitive("object", schema, root),
+        _ => "unknown".to_string(),
+    }
+}
+
+fn ts_primitive(kind: &str, schema: &Value, root: &str) -> String {
+    match kind {
+        "string" => "string".to_string(),
+        "integer" | "number" => "number".to_string(),
+        "boolean" => "boolean".to_string(),
+        "null" => "null".to_string(),
+        "array" => {
+            if let Some(items) = schema.get("prefixItems").and_then(Value::as_array) {
+                let items: Vec<_> = items.iter().map(|item| ts_type(item, root)).collect();
+                return format!("[{}]", items.join(", "));
+            }
+            match schema.get("items") {
+                Some(items) => format!("{}[]", grouped(ts_type(items, root))),
+                None => "unknown[]".to_string(),
+            }
+        }
+        "object" => match schema.get("properties").and_then(Value::as_object) {
+            Some(properties) => {
+                let required = required_fields(schema);
+                let fields: Vec<_> = properties
+                    .iter()
+                    .map(|(field, property)| {
+                        let optional = if required.contains(&field.as_str()) { "" } else { "?" };
+                        format!("{}{}: {}", ts_key(field), optional, ts_type(property, root))
+                    })
+                    .collect();
+                format!("{{ {} }}", fields.join("; "))
+            }
+            None => match schema.get("additionalProperties") {
+                Some(values @ Value::Object(_)) => format!("Record<string, {}>", ts_type(values, root)),
+                _ => "Record<string, unknown>".to_string(),
+            },
+        },
+        _ => "unknown".to_string(),
+    }
+}
+
+/// Join distinct member types with `|`; an empty union is `never`.
+fn union(members: impl Iterator<Item = String>) -> String {
+    let distinct = distinct(members);
+    if distinct.is_empty() {
+        return "never".to_string();
+    }
+    distinct.join(" | ")
+}
+
+/// Parenthesize compound types before appending `[]` or joining with `&`.
+fn grouped(ts: String) -> String {
+    if ts.contains(" | ") || ts.contains(" & ") {
+        format!("({})", ts)
+    } else {
+        ts
+    }
+}
+
+/// Objects nested deeper than this are abbreviated by [`summarize`].
+const SUMMARY_DEPTH: usize = 2;
+
+/// A one-line, human-readable outline of a schema, for logs.
+///
+/// Objects are shown as `Title{field: type, optional?: type}`, enums as
+/// `enum[A,B]`, arrays as `type[]` and unions as `a|b`. References into
+/// `$defs` are followed; objects more than one level below the root are
+/// abbreviated to `Title{…}`, and a reference met again while it is being
+/// expanded is shown by its definition name, which keeps recursive schemas
+/// finite.
+///
+/// # Example
+///
+/// ```ignore
+/// let summary = schema::summarize(&get_weather_tool().schema());
+/// assert_eq!(summary, "WeatherParams{city: string, country: string, unit?: enum[C,F]}");
+/// ```
+pub fn summarize(schema: &Value) -> String {
+    summarize_at(schema, schema, 0, &[])
+}
+
+/// `expanding` holds the references being expanded above `schema`.
+fn summarize_at(schema: &Value, root: &Value, depth: usize, expanding: &[&str]) -> String {
+    let object = match schema {
+        Value::Bool(false) => return "never".to_string(),
+        Value::Object(object) => object,
+        _ => return "any".to_string(),
+    };
+
+    if let Some(reference) = object.get("$ref").and_then(Value::as_str) {
+        let target = match reference {
+            "#" => Some(root),
+            _ => reference
+                .strip_prefix("#/$defs/")
+                .and_then(|name| root.get("$defs")?.get(name)),
+        };
+        let name = reference.rsplit('/').next().unwrap_or(reference);
+        if expanding.contains(&reference) {
+            return name.to_string();
+        }
+        let expanding = &[expanding, &[reference]].concat();
+        return match target {
+            // Definitions are named by their key rather than a `title`
+            Some(Value::Object(target)) if depth <= SUMMARY_DEPTH && !target.contains_key("title") => {
+                let mut titled = target.clone();
+                titled.insert("title".to_string(), Value::String(name.to_string()));
+                summarize_at(&Value::Object(titled), root, depth, expanding)
+            }
+            Some(target) if depth <= SUMMARY_DEPTH => summarize_at(target, root, depth, expanding),
+            _ => name.to_string(),
+        };
+    }
+    if let Some(value) = object.get("const") {
+        return value.to_string();
+    }
+    if let Some(values) = object.get("enum").and_then(Value::as_array) {
+        return summarize_enum(values.iter());
+    }
+    if let Some(variants) = ["oneOf", "anyOf"].iter().find_map(|key| object.get(*key).and_then(Value::as_array)) {
+        // Unit enums with documented variants come out of schemars as a `oneOf` of consts
+        if variants.iter().all(|variant| variant.get("const").is_some()) {
+            return summarize_enum(variants.iter().filter_map(|variant| variant.get("const")));
+        }
+        return union_summary(variants.iter().map(|variant| summarize_at(variant, root, depth, expanding)), "|");
+    }
+    if let Some(parts) = object.get("allOf").and_then(Value::as_array) {
+        return union_summary(parts.iter().map(|part| summarize_at(part, root, depth, expanding)), "&");
+    }
+
+    match object.get("type") {
+        Some(Value::String(kind)) => summarize_kind(kind, schema, root, depth, expanding),
+        Some(Value::Array(kinds)) => union_summary(
+            kinds
+                .iter()
+                .filter_map(Value::as_str)
+                .map(|kind| summarize_kind(kind, schema, root, depth, expanding)),
+            "|",
+        ),
+        _ if object.contains_key("properties") => summarize_kind("object", schema, root, depth, expanding),
+        _ => "any".to_string(),
+    }
+}
+
+fn summarize_kind(kind: &str, schema: &Value, root: &Value, depth: usize, expanding: &[&str]) -> String {
+    match kind {
+        "array" => match schema.get("items") {
+            Some(items) => format!("{}[]", summarize_at(items, root, depth, expanding)),
+            None => "any[]".to_string(),
+        },
+        "object" => {
+            let Some(properties) = schema.get("properties").and_then(Value::as_object) else {
+                return "object".to_string();
+            };
+            let title = schema.get("title").and_then(Value::as_str).unwrap_or_default();
+            if depth >= SUMMARY_DEPTH {
+                return format!("{}{{…}}", title);
+            }
+            let required = required_fields(schema);
+            let fields: Vec<_> = properties
+                .iter()
+                .map(|(field, property)| {
+                    let optional = if required.contains(&field.as_str()) { "" } else { "?" };
+                    format!("{}{}: {}", field, optional, summarize_at(property, root, depth + 1, expanding))
+                })
+                .collect();
+            format!("{}{{{}}}", title, fields.join(", "))
+        }
+        kind => kind.to_string(),
+    }
+}
+
+fn summarize_enum<'a>(values: impl Iterator<Item = &'a Value>) -> String {
+    let values: Vec<_> = values
+        .map(|value| value.as_str().map_or_else(|| value.to_string(), str::to_string))
+        .collect();
+    format!("enum[{}]", values.join(","))
+}
+
+fn union_summary(members: impl Iterator<Item = String>, separator: &str) -> String {
+    distinct(members).join(separator)
+}
+
+fn distinct(members: impl Iterator<Item = String>) -> Vec<String> {
+    let mut distinct: Vec<String> = Vec::new();
+    for member in members {
+        if !distinct.contains(&member) {
+            distinct.push(member);
+        }
+    }
+    distinct
+}
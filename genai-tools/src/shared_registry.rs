@@ -0,0 +1,133 @@
+use crate::adapter::ToolCallLike;
+use crate::registry::ToolRegistry;
+use crate::traits::{BoxError, ToolFunction, ToolHandler};
+use genai::chat::{Tool, ToolResponse};
+use std::future::Future;
+use std::sync::{Arc, PoisonError, RwLock};
+
+/// A [`ToolRegistry`] that can be changed while calls are running.
+///
+/// Every method takes `&self`, so one instance (typically in an `Arc`) can be
+/// shared by request handlers and by whatever registers or removes tools.
+/// Reads take a snapshot of the current registry and never wait for calls in
+/// progress; writes copy the registry, apply the change, and publish the copy,
+/// so a call that started before a write finishes against the tools it saw.
+/// The copy shares the tool handlers, so writes are cheap but not free; this
+/// suits registries that change occasionally, not on every request.
+///
+/// # Example
+///
+/// ```ignore
+/// let registry = Arc::new(SharedToolRegistry::new(ToolRegistry::new()));
+///
+/// // In a request handler
+/// let responses = registry.execute_calls(&tool_calls).await?;
+///
+/// // Elsewhere, at any time
+/// registry.register_function(new_plugin_tool());
+/// registry.remove_tool("retired_tool");
+/// ```
+pub struct SharedToolRegistry {
+    current: RwLock<Arc<ToolRegistry>>,
+}
+
+impl SharedToolRegistry {
+    /// Share `registry`, keeping its tools and options.
+    pub fn new(registry: ToolRegistry) -> Self {
+        Self {
+            current: RwLock::new(Arc::new(registry)),
+        }
+    }
+
+    /// The registry as it is now. Later writes don't affect the snapshot.
+    pub fn snapshot(&self) -> Arc<ToolRegistry> {
+        self.current.read().unwrap_or_else(PoisonError::into_inner).clone()
+    }
+
+    /// Apply `change` to a copy of the registry and publish it.
+    ///
+    /// Writes are serialized, so concurrent updates never overwrite each
+    /// other. Readers keep using the previous registry until `change` returns.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// registry.update(|registry| {
+    ///     registry.remove_tool("search_v1");
+    ///     registry.register_function(search_v2_tool());
+    /// });
+    /// ```
+    pub fn update<R>(&self, change: impl FnOnce(&mut ToolRegistry) -> R) -> R {
+        let mut current = self.current.write().unwrap_or_else(PoisonError::into_inner);
+        let mut next = current.fork();
+        let result = change(&mut next);
+        *current = Arc::new(next);
+        result
+    }
+
+    /// Register a tool function. See [`ToolRegistry::register_function`].
+    pub fn register_function<T: ToolFunction>(&self, tool: T) {
+        self.update(|registry| {
+            registry.register_function(tool);
+        });
+    }
+
+    /// Construct a tool asynchronously and register it. See
+    /// [`ToolRegistry::register_async`].
+    ///
+    /// The current registry stays in use while `build` runs.
+    pub async fn register_async<F, Fut, T>(&self, build: F)
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = T>,
+        T: ToolHandler + 'static,
+    {
+        let tool = build().await;
+        self.update(|registry| {
+            registry.register_handler(Box::new(tool));
+        });
+    }
+
+    /// Remove a tool, returning whether it was registered. Calls already
+    /// running keep the tool until they finish.
+    pub fn remove_tool(&self, name: &str) -> bool {
+        self.update(|registry| registry.remove_tool(name))
+    }
+
+    /// The currently registered tools. See [`ToolRegistry::get_tools`].
+    pub fn get_tools(&self) -> Vec<Tool> {
+        self.snapshot().get_tools()
+    }
+
+    /// Execute a tool call against the current registry. See [`ToolRegistry::execute_call`].
+    pub async fn execute_call<C>(&self, tool_call: &C) -> Result<ToolResponse, BoxError>
+    where
+        C: ToolCallLike + ?Sized,
+    {
+        self.snapshot().execute_call(tool_call).await
+    }
+
+    /// Execute several tool calls against the same snapshot, concurrently.
+    /// See [`ToolRegistry::execute_calls`].
+    pub async fn execute_calls<C: ToolCallLike>(&self, tool_calls: &[C]) -> Result<Vec<ToolResponse>, BoxError> {
+        self.snapshot().execute_calls(tool_calls).await
+    }
+}
+
+impl Default for SharedToolRegistry {
+    fn default() -> Self {
+        Self::new(ToolRegistry::new())
+    }
+}
+
+impl From<ToolRegistry> for SharedToolRegistry {
+    fn from(registry: ToolRegistry) -> Self {
+        Self::new(registry)
+    }
+}
+
+impl std::fmt::Debug for SharedToolRegistry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("SharedToolRegistry").field(&self.snapshot()).finish()
+    }
+}
@@ -0,0 +1,189 @@
+use crate::error::RegistryError;
+use crate::progress::ProgressReporter;
+use crate::registry::ToolRegistry;
+use crate::schema;
+use crate::traits::{BoxError, BoxFuture, ToolHandler};
+use serde::de::Error as _;
+use serde_json::{json, Map, Value};
+
+/// A single tool that dispatches to the tools of a nested registry, selected
+/// by an `op` field in the arguments.
+///
+/// The schema is a `oneOf` with one branch per sub-tool: its parameters plus
+/// `op` fixed to its name. The remaining arguments go to the sub-tool through
+/// the nested registry, so its validation, middleware and redaction apply.
+pub(crate) struct SubRegistryTool {
+    name: String,
+    description: String,
+    registry: ToolRegistry,
+}
+
+impl SubRegistryTool {
+    pub(crate) fn new(name: String, description: String, registry: ToolRegistry) -> Self {
+        Self {
+            name,
+            description,
+            registry,
+        }
+    }
+
+    /// The sub-tools' advertised names.
+    fn operations(&self) -> Vec<&str> {
+        let mut operations: Vec<&str> = self
+            .registry
+            .tool_names()
+            .into_iter()
+            .map(|name| self.registry.advertised_name(name))
+            .collect();
+        operations.sort_unstable();
+        operations
+    }
+
+    fn dispatch(&self, mut params: Value, progress: Option<ProgressReporter>) -> BoxFuture<'_, Result<Value, BoxError>> {
+        Box::pin(async move {
+            let operation = match params.as_object_mut().and_then(|map| map.remove("op")) {
+                Some(Value::String(op)) if self.registry.handler(&op).is_some() => op,
+                op => {
+                    return Err(Box::new(RegistryError::UnknownOperation {
+                        tool: self.name.clone(),
+                        operation: op.map(|op| match op {
+                            Value::String(op) => op,
+                            other => other.to_string(),
+                        }),
+                        valid: self.operations().into_iter().map(str::to_string).collect(),
+                    }) as BoxError)
+                }
+            };
+            self.registry.call_nested(&operation, params, progress).await
+        })
+    }
+
+    /// The sub-tool's advertised schema with `op` added, moving its `$defs`
+    /// into `defs`.
+    fn branch(&self, handler: &dyn ToolHandler, defs: &mut Map<String, Value>) -> Result<Value, serde_json::Error> {
+        let mut branch = self.registry.advertised_schema(handler).map_err(|e| match e {
+            RegistryError::Schema { source, .. } => source,
+            other => serde_json::Error::custom(other),
+        })?;
+        // Definitions that clash with another sub-tool's are inlined instead
+        let clashes = |branch: &Value| {
+            branch.get("$defs").and_then(Value::as_object).is_some_and(|branch_defs| {
+                branch_defs
+                    .iter()
+                    .any(|(name, def)| defs.get(name).is_some_and(|existing| existing != def))
+            })
+        };
+        if clashes(&branch) {
+            schema::inline_refs(&mut branch);
+        }
+
+        let op_name = self.registry.advertised_name(handler.name());
+        let Value::Object(object) = &mut branch else {
+            return Ok(json!({ "type": "object", "properties": { "op": { "const": op_name } }, "required": ["op"] }));
+        };
+        object.remove("$schema");
+        if let Some(Value::Object(branch_defs)) = object.remove("$defs") {
+            defs.extend(branch_defs);
+        }
+        let op = json!({ "const": op_name, "description": self.registry.advertised_description(handler) });
+        if let Value::Object(properties) = object.entry("properties").or_insert_with(|| json!({})) {
+            properties.insert("op".to_string(), op);
+        }
+        match object.entry("required").or_insert_with(|| json!([])) {
+            Value::Array(required) => required.insert(0, json!("op")),
+            required => *required = json!(["op"]),
+        }
+        Ok(branch)
+    }
+}
+
+impl ToolHandler for SubRegistryTool {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn description(&self) -> &str {
+        &self.description
+    }
+
+    fn schema(&self) -> Value {
+        self.try_schema().expect("Failed to serialize schema")
+    }
+
+    fn try_schema(&self) -> Result<Value, serde_json::Error> {
+        let mut defs = Map::new();
+        let operations = self.operations();
+        let branches = operations
+            .iter()
+            .filter_map(|name| self.registry.handler(name))
+            .map(|handler| self.branch(handler, &mut defs))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut schema = json!({
+            "type": "object",
+            "properties": { "op": { "type": "string", "enum": operations } },
+            "required": ["op"],
+            "oneOf": branches,
+        });
+        if !defs.is_empty() {
+            schema["$defs"] = Value::Object(defs);
+        }
+        Ok(schema)
+    }
+
+    fn output_schema(&self) -> Option<Value> {
+        None
+    }
+
+    fn requires_init(&self) -> bool {
+        self.registry.iter().any(|(_, handler)| handler.requires_init())
+    }
+
+    fn init(&self) -> BoxFuture<'_, Result<(), BoxError>> {
+        Box::pin(async move { self.registry.init_all().await.map_err(BoxError::from) })
+    }
+
+    fn call_json(&self, params: Value) -> BoxFuture<'_, Result<Value, BoxError>> {
+        self.dispatch(params, None)
+    }
+
+    fn call_json_with_progress(
+        &self,
+        params: Value,
+        progress: ProgressReporter,
+    ) -> BoxFuture<'_, Result<Value, BoxError>> {
+        self.dispatch(params, Some(progress))
+    }
+}
+
+impl ToolRegistry {
+    /// Register the tools of `registry` as a single tool that selects one of
+    /// them with an `op` field.
+    ///
+    /// Keeps the number of tools the model sees small while exposing related
+    /// operations in full. The tool's schema is a `oneOf` with one branch per
+    /// sub-tool, and a call like `{"op": "read_file", "path": "a.txt"}` runs
+    /// `read_file` with the remaining arguments. The nested registry's options,
+    /// such as input validation and middleware, apply to its own tools. An
+    /// unknown or missing `op` fails with [`RegistryError::UnknownOperation`],
+    /// which lists the valid operations.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let mut filesystem = ToolRegistry::new();
+    /// filesystem.register_functions([read_file_tool(), write_file_tool(), list_dir_tool()]);
+    ///
+    /// let mut registry = ToolRegistry::new();
+    /// registry.register_sub_registry("filesystem", "Read and write files", filesystem);
+    /// ```
+    pub fn register_sub_registry(
+        &mut self,
+        name: impl Into<String>,
+        description: impl Into<String>,
+        registry: ToolRegistry,
+    ) -> &mut Self {
+        let tool = SubRegistryTool::new(name.into(), description.into(), registry);
+        self.register_handler(Box::new(tool))
+    }
+}
@@ -0,0 +1,38 @@
+/// The names in `candidates` closest to `name` by edit distance, for "did you
+/// mean" hints.
+///
+/// Only names within a third of `name`'s length (at least two edits) are
+/// considered close; ties are all returned, sorted, so the result is
+/// deterministic.
+pub(crate) fn closest_names<'a>(name: &str, candidates: impl IntoIterator<Item = &'a str>) -> Vec<String> {
+    let max_distance = (name.chars().count() / 3).max(2);
+    let mut best = max_distance + 1;
+    let mut closest = Vec::new();
+    for candidate in candidates {
+        let distance = edit_distance(name, candidate);
+        if distance < best {
+            best = distance;
+            closest.clear();
+        }
+        if distance == best {
+            closest.push(candidate.to_string());
+        }
+    }
+    closest.sort_unstable();
+    closest
+}
+
+/// Levenshtein distance over chars.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, a_char) in a.chars().enumerate() {
+        let mut current = vec![i + 1; b.len() + 1];
+        for (j, b_char) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(a_char != *b_char);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
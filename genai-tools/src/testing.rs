@@ -0,0 +1,203 @@
+//! Test doubles for code that drives a [`ToolRegistry`].
+//!
+//! Available with the `testing` feature.
+
+use crate::adapter::ToolCallLike;
+use crate::error::RegistryError;
+use crate::middleware::ToolInvocation;
+use crate::registry::ToolRegistry;
+use crate::traits::{BoxError, BoxFuture, ToolHandler};
+use genai::chat::{Tool, ToolResponse};
+use serde_json::{json, Value};
+use std::sync::{Arc, Mutex, PoisonError};
+
+/// A registry of stand-in tools that record their calls and answer with
+/// canned responses, for testing orchestration logic without running real
+/// tool bodies.
+///
+/// Each tool returns the response configured with
+/// [`with_response`](Self::with_response) or
+/// [`with_error`](Self::with_error). Every call that reaches a tool is
+/// recorded with its call id and arguments, in call order, and can be
+/// inspected with [`calls`](Self::calls). [`mirror`](Self::mirror) copies the
+/// names, descriptions and schemas of a real registry, so the model-facing
+/// side is unchanged.
+///
+/// # Example
+///
+/// ```ignore
+/// let mock = MockRegistry::mirror(&production_registry())?
+///     .with_response("get_weather", json!("sunny"));
+///
+/// orchestrator.run(mock.registry(), "Plan my picnic").await?;
+///
+/// let calls = mock.calls();
+/// assert_eq!(calls[0].tool, "get_weather");
+/// assert_eq!(calls[0].arguments["city"], "Paris");
+/// ```
+pub struct MockRegistry {
+    registry: ToolRegistry,
+    calls: Arc<Mutex<Vec<ToolInvocation>>>,
+}
+
+impl MockRegistry {
+    /// Create a mock registry without tools.
+    pub fn new() -> Self {
+        Self {
+            registry: ToolRegistry::new(),
+            calls: Arc::new(Mutex::new(Vec::new())),
+        }
+    }
+
+    /// Create a mock registry with a stand-in for every tool of `registry`,
+    /// advertised with the same name, description and schema. Tools
+    /// registered with [`register_function_as`](ToolRegistry::register_function_as)
+    /// are mocked under their advertised name. They answer `null` until given
+    /// a response.
+    ///
+    /// Fails with [`RegistryError::Schema`] if a tool's schema cannot be generated.
+    pub fn mirror(registry: &ToolRegistry) -> Result<Self, RegistryError> {
+        let mut mock = Self::new();
+        for definition in registry.export_definitions()? {
+            mock.insert(MockTool {
+                name: definition.name,
+                description: definition.description,
+                schema: definition.schema,
+                response: Ok(Value::Null),
+            });
+        }
+        Ok(mock)
+    }
+
+    /// Answer calls to the tool `name` with `response`, adding the tool with
+    /// an open schema if it isn't mocked yet.
+    pub fn with_response(mut self, name: impl Into<String>, response: Value) -> Self {
+        self.set_response(name.into(), Ok(response));
+        self
+    }
+
+    /// Fail calls to the tool `name` with an error carrying `message`, adding
+    /// the tool with an open schema if it isn't mocked yet.
+    pub fn with_error(mut self, name: impl Into<String>, message: impl Into<String>) -> Self {
+        self.set_response(name.into(), Err(message.into()));
+        self
+    }
+
+    /// The registry of stand-in tools, to hand to the code under test.
+    pub fn registry(&self) -> &ToolRegistry {
+        &self.registry
+    }
+
+    /// Get the mocked tools as `genai::chat::Tool` objects.
+    pub fn get_tools(&self) -> Vec<Tool> {
+        self.registry.get_tools()
+    }
+
+    /// Execute a tool call against the stand-in tools, recording it.
+    pub async fn execute_call<C>(&self, tool_call: &C) -> Result<ToolResponse, BoxError>
+    where
+        C: ToolCallLike + ?Sized,
+    {
+        self.registry.execute_call(tool_call).await
+    }
+
+    /// Every call that reached a tool so far, in call order.
+    pub fn calls(&self) -> Vec<ToolInvocation> {
+        self.calls.lock().unwrap_or_else(PoisonError::into_inner).clone()
+    }
+
+    /// The arguments of every call to the tool `name` so far, in call order.
+    pub fn calls_to(&self, name: &str) -> Vec<Value> {
+        self.calls
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .iter()
+            .filter(|call| call.tool == name)
+            .map(|call| call.arguments.clone())
+            .collect()
+    }
+
+    /// Forget the recorded calls, e.g. between test cases sharing a mock.
+    pub fn clear_calls(&self) {
+        self.calls.lock().unwrap_or_else(PoisonError::into_inner).clear();
+    }
+
+    fn set_response(&mut self, name: String, response: Result<Value, String>) {
+        let (description, schema) = match self.registry.handler(&name) {
+            Some(tool) => (tool.description().to_string(), tool.schema()),
+            None => (format!("Mock of {}", name), json!({ "type": "object" })),
+        };
+        // Drops the old stand-in's recorder along with it
+        self.registry.remove_tool(&name);
+        self.insert(MockTool {
+            name,
+            description,
+            schema,
+            response,
+        });
+    }
+
+    fn insert(&mut self, tool: MockTool) {
+        let name = tool.name.clone();
+        self.registry.register_handler(Box::new(tool));
+        let calls = Arc::clone(&self.calls);
+        self.registry
+            .wrap_tool(&name, move |invocation, next| {
+                calls
+                    .lock()
+                    .unwrap_or_else(PoisonError::into_inner)
+                    .push(invocation.clone());
+                next.run(invocation)
+            })
+            .expect("registered above");
+    }
+}
+
+impl Default for MockRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A tool that answers every call with the same canned response.
+struct MockTool {
+    name: String,
+    description: String,
+    schema: Value,
+    response: Result<Value, String>,
+}
+
+impl ToolHandler for MockTool {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn description(&self) -> &str {
+        &self.description
+    }
+
+    fn schema(&self) -> Value {
+        self.schema.clone()
+    }
+
+    fn try_schema(&self) -> Result<Value, serde_json::Error> {
+        Ok(self.schema.clone())
+    }
+
+    fn output_schema(&self) -> Option<Value> {
+        None
+    }
+
+    fn requires_init(&self) -> bool {
+        false
+    }
+
+    fn init(&self) -> BoxFuture<'_, Result<(), BoxError>> {
+        Box::pin(async { Ok(()) })
+    }
+
+    fn call_json(&self, _params: Value) -> BoxFuture<'_, Result<Value, BoxError>> {
+        let response = self.response.clone().map_err(BoxError::from);
+        Box::pin(async move { response })
+    }
+}
@@ -1,8 +1,22 @@
+use crate::binary::BinaryOutput;
+use crate::cost::CostHint;
+use crate::error::RegistryError;
+use crate::positional::named_arguments;
+use crate::progress::ProgressReporter;
+use serde_json::value::RawValue;
 use serde_json::Value;
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
 use std::error::Error;
 use std::future::Future;
 use std::pin::Pin;
 
+/// A boxed, thread-safe error as returned by type-erased tool execution.
+pub type BoxError = Box<dyn Error + Send + Sync>;
+
+/// A boxed `Send` future, as returned by tool execution methods.
+pub type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
+
 /// A trait for types that can be used as tool function parameters.
 ///
 /// This trait is automatically implemented for types that implement both
@@ -25,11 +39,15 @@ where
 {}
 
 /// A trait for errors that can be returned from tool functions.
-pub trait ToolError: Error + Send + Sync + 'static {}
+///
+/// This trait is automatically implemented for every type that converts into
+/// a [`BoxError`]: any `Error + Send + Sync + 'static`, as well as
+/// `Box<dyn Error + Send + Sync>` itself, which does not implement `Error`.
+pub trait ToolError: Into<BoxError> + Send + 'static {}
 
 impl<T> ToolError for T 
 where 
-    T: Error + Send + Sync + 'static 
+    T: Into<BoxError> + Send + 'static 
 {}
 
 /// The core trait that defines a tool function's metadata and execution.
@@ -52,27 +70,249 @@ pub trait ToolFunction: Send + Sync + 'static {
     fn description(&self) -> &'static str;
     
     /// Get the JSON schema for the parameters
+    ///
+    /// # Panics
+    ///
+    /// Panics if the schema cannot be serialized. Use [`try_schema`](Self::try_schema)
+    /// to handle that case instead; the registry always does.
     fn schema(&self) -> Value {
-        let schema = schemars::schema_for!(Self::Params);
-        serde_json::to_value(schema).expect("Failed to serialize schema")
+        self.try_schema().expect("Failed to serialize schema")
+    }
+
+    /// Get the JSON schema for the parameters, or the error that prevented
+    /// serializing it.
+    ///
+    /// The schema is generated once per params type and shared by every tool
+    /// that uses it; see [`schema::params_schema`](crate::schema::params_schema).
+    fn try_schema(&self) -> Result<Value, serde_json::Error> {
+        crate::schema::params_schema::<Self::Params>()
+    }
+
+    /// The Rust type name of [`Self::Params`], used in argument errors.
+    fn params_type_name(&self) -> &'static str {
+        std::any::type_name::<Self::Params>()
+    }
+
+    /// The Rust type name of [`Self::Error`], for describing the errors a tool
+    /// can return.
+    fn error_type_name(&self) -> &'static str {
+        std::any::type_name::<Self::Error>()
+    }
+
+    /// Field names of [`Self::Params`] in declaration order, if the tool accepts
+    /// its arguments as a positional JSON array.
+    ///
+    /// Returns `None` by default. `#[tool_function]` implements it for tools
+    /// with a synthesized params struct, so `[2, 3]` is accepted in place of
+    /// `{"a": 2, "b": 3}`.
+    fn positional_fields(&self) -> Option<&'static [&'static str]> {
+        None
+    }
+
+    /// Field names of [`Self::Params`] in declaration order, for
+    /// [`ToolRegistry::with_property_ordering`](crate::ToolRegistry::with_property_ordering).
+    ///
+    /// Uses the order `#[tool_function]` captured for synthesized params
+    /// structs, and otherwise the field list of the params type's
+    /// `Deserialize` impl. `None` if neither is available.
+    fn property_order(&self) -> Option<&'static [&'static str]> {
+        self.positional_fields()
+            .or_else(crate::schema::serde_field_order::<Self::Params>)
+    }
+
+    /// Every argument field name the `Deserialize` impl of [`Self::Params`]
+    /// recognizes, aliases included, for
+    /// [`ToolRegistry::check_schema_serde_consistency`](crate::ToolRegistry::check_schema_serde_consistency).
+    ///
+    /// `None` if the params type doesn't deserialize as a plain struct.
+    fn accepted_fields(&self) -> Option<&'static [&'static str]> {
+        crate::schema::serde_field_order::<Self::Params>()
+    }
+
+    /// Parameter fields the model may not set.
+    ///
+    /// The registry drops them from incoming arguments before anything else
+    /// sees them, so they take their serde default unless middleware
+    /// injects a server-side value. Returns an empty slice by default;
+    /// `#[tool_function(server_only(...))]` sets it and also removes the
+    /// fields from the schema.
+    fn server_only_fields(&self) -> &'static [&'static str] {
+        &[]
+    }
+
+    /// Get the JSON schema for the output, if the tool advertises one.
+    ///
+    /// Returns `None` by default. The `#[tool_function(output_schema)]` flag
+    /// generates an implementation from `Self::Output`, which must then also
+    /// implement `schemars::JsonSchema`.
+    fn output_schema(&self) -> Option<Value> {
+        None
+    }
+
+    /// How expensive the tool is to call. Returns [`CostHint::Unknown`] by default.
+    fn cost_hint(&self) -> CostHint {
+        CostHint::Unknown
+    }
+
+    /// Whether the tool does enough synchronous work to block an async
+    /// executor, such as image processing or parsing.
+    ///
+    /// Returns `false` by default. The `#[tool_function(cpu_bound)]` flag sets
+    /// it to `true`. With the `blocking` feature, the registry runs such tools
+    /// on tokio's blocking thread pool; see `ToolRegistry::set_blocking`.
+    fn is_cpu_bound(&self) -> bool {
+        false
+    }
+
+    /// Free-form key-value tags, such as an icon or a UI group, for frameworks
+    /// built on the registry.
+    ///
+    /// Empty by default. Set with
+    /// `#[tool_function(meta(icon = "cloud", group = "weather"))]`.
+    fn metadata(&self) -> HashMap<String, String> {
+        HashMap::new()
+    }
+
+    /// Example arguments, as JSON objects, for providers that accept example
+    /// calls alongside the tool.
+    ///
+    /// Empty by default. `#[tool_function(example_fn = ...)]` fills it with
+    /// the serialized example, which it also adds to the schema's `examples`.
+    fn examples(&self) -> &[Value] {
+        &[]
+    }
+
+    /// Whether the tool's result should be shown to the user instead of being
+    /// sent back to the model.
+    ///
+    /// Returns `false` by default. The `#[tool_function(terminal)]` flag sets
+    /// it to `true`.
+    fn is_terminal(&self) -> bool {
+        false
+    }
+
+    /// Whether [`init`](Self::init) must complete before the tool can be called.
+    ///
+    /// Returns `false` by default. The `#[tool_function(init = ...)]` argument
+    /// sets it to `true`.
+    fn requires_init(&self) -> bool {
+        false
+    }
+
+    /// Run one-time async setup, such as warming a connection pool or fetching
+    /// credentials. Called by `ToolRegistry::init_all`; does nothing by default.
+    fn init(&self) -> BoxFuture<'_, Result<(), BoxError>> {
+        Box::pin(async { Ok(()) })
     }
     
     /// Execute the tool with the given parameters
-    fn call(&self, params: Self::Params) -> Pin<Box<dyn Future<Output = Result<Self::Output, Self::Error>> + Send + '_>>;
+    fn call(&self, params: Self::Params) -> BoxFuture<'_, Result<Self::Output, Self::Error>>;
+
+    /// Execute the tool, reporting progress through `progress`.
+    ///
+    /// Ignores the reporter and delegates to [`call`](Self::call) by default.
+    /// `#[tool_function(progress)]` implements it for tools that report progress.
+    fn call_with_progress(
+        &self,
+        params: Self::Params,
+        progress: ProgressReporter,
+    ) -> BoxFuture<'_, Result<Self::Output, Self::Error>> {
+        let _ = progress;
+        self.call(params)
+    }
     
     /// Execute the tool with raw JSON parameters
-    fn call_json(&self, params: Value) -> Pin<Box<dyn Future<Output = Result<Value, Box<dyn Error + Send + Sync>>> + Send + '_>> {
+    fn call_json(&self, params: Value) -> BoxFuture<'_, Result<Value, BoxError>> {
         Box::pin(async move {
+            let params = positional_arguments(self, params)?;
             let parsed_params: Self::Params = serde_json::from_value(params)
-                .map_err(|e| Box::new(e) as Box<dyn Error + Send + Sync>)?;
+                .map_err(|source| invalid_arguments(self, source))?;
                 
-            let result = self.call(parsed_params).await
-                .map_err(|e| Box::new(e) as Box<dyn Error + Send + Sync>)?;
+            let result = self.call(parsed_params).await.map_err(Into::into)?;
                 
             serde_json::to_value(result)
                 .map_err(|e| Box::new(e) as Box<dyn Error + Send + Sync>)
         })
     }
+
+    /// Execute the tool with raw JSON parameters, reporting progress through `progress`.
+    fn call_json_with_progress(
+        &self,
+        params: Value,
+        progress: ProgressReporter,
+    ) -> BoxFuture<'_, Result<Value, BoxError>> {
+        Box::pin(async move {
+            let params = positional_arguments(self, params)?;
+            let parsed_params: Self::Params = serde_json::from_value(params)
+                .map_err(|source| invalid_arguments(self, source))?;
+            let result = self.call_with_progress(parsed_params, progress).await.map_err(Into::into)?;
+            Ok(serde_json::to_value(result)?)
+        })
+    }
+
+    /// Execute the tool with unparsed JSON parameters.
+    ///
+    /// The arguments are deserialized straight from the JSON text into
+    /// `Self::Params`, skipping the intermediate `Value` that
+    /// [`call_json`](Self::call_json) needs. Prefer this for tools that take
+    /// large arrays or strings.
+    fn call_raw(&self, params: &RawValue) -> BoxFuture<'_, Result<Value, BoxError>> {
+        if self.positional_fields().is_some() && params.get().trim_start().starts_with('[') {
+            return match serde_json::from_str(params.get()) {
+                Ok(params) => self.call_json(params),
+                Err(e) => Box::pin(async move { Err(Box::new(e) as BoxError) }),
+            };
+        }
+        let parsed_params: Result<Self::Params, _> =
+            serde_json::from_str(params.get()).map_err(|source| invalid_arguments(self, source));
+        Box::pin(async move {
+            let result = self.call(parsed_params?).await.map_err(Into::into)?;
+            Ok(serde_json::to_value(result)?)
+        })
+    }
+
+    /// Execute the tool with raw JSON parameters, returning its output as bytes
+    /// instead of JSON.
+    ///
+    /// Only tools whose output type is [`BinaryOutput`] support this; others
+    /// fail with [`RegistryError::NotBinary`] without running.
+    fn call_binary(&self, params: Value) -> BoxFuture<'_, Result<BinaryOutput, BoxError>> {
+        Box::pin(async move {
+            if TypeId::of::<Self::Output>() != TypeId::of::<BinaryOutput>() {
+                return Err(Box::new(RegistryError::NotBinary {
+                    tool: self.name().to_string(),
+                }) as BoxError);
+            }
+            let params = positional_arguments(self, params)?;
+            let parsed_params: Self::Params = serde_json::from_value(params)
+                .map_err(|source| invalid_arguments(self, source))?;
+            let result: Box<dyn Any> = Box::new(self.call(parsed_params).await.map_err(Into::into)?);
+            Ok(*result.downcast::<BinaryOutput>().expect("output type was checked"))
+        })
+    }
+}
+
+pub(crate) fn full_schema(name: &str, description: &str, parameters: Value) -> Value {
+    serde_json::json!({
+        "name": name,
+        "description": description,
+        "parameters": parameters,
+    })
+}
+
+fn positional_arguments<T: ToolFunction + ?Sized>(tool: &T, params: Value) -> Result<Value, RegistryError> {
+    match tool.positional_fields() {
+        Some(fields) => named_arguments(tool.name(), tool.params_type_name(), fields, params),
+        None => Ok(params),
+    }
+}
+
+fn invalid_arguments<T: ToolFunction + ?Sized>(tool: &T, source: serde_json::Error) -> RegistryError {
+    RegistryError::InvalidArguments {
+        tool: tool.name().to_string(),
+        params_type: tool.params_type_name(),
+        source,
+    }
 }
 
 /// A type-erased tool function for storage in the registry
@@ -80,7 +320,148 @@ pub trait ToolHandler: Send + Sync {
     fn name(&self) -> &str;
     fn description(&self) -> &str;
     fn schema(&self) -> Value;
-    fn call_json(&self, params: Value) -> Pin<Box<dyn Future<Output = Result<Value, Box<dyn Error + Send + Sync>>> + Send + '_>>;
+    fn try_schema(&self) -> Result<Value, serde_json::Error>;
+    fn output_schema(&self) -> Option<Value>;
+    fn requires_init(&self) -> bool;
+    fn init(&self) -> BoxFuture<'_, Result<(), BoxError>>;
+    fn call_json(&self, params: Value) -> BoxFuture<'_, Result<Value, BoxError>>;
+
+    /// Execute the tool, reporting progress through `progress`.
+    ///
+    /// Ignores the reporter and delegates to `call_json` by default.
+    fn call_json_with_progress(
+        &self,
+        params: Value,
+        progress: ProgressReporter,
+    ) -> BoxFuture<'_, Result<Value, BoxError>> {
+        let _ = progress;
+        self.call_json(params)
+    }
+
+    /// The Rust type name of the tool's parameters, for diagnostics.
+    ///
+    /// Defaults to `serde_json::Value` for handlers without a typed params struct.
+    fn params_type_name(&self) -> &'static str {
+        std::any::type_name::<Value>()
+    }
+
+    /// The Rust type name of the errors the tool returns, for diagnostics.
+    ///
+    /// Defaults to `BoxError` for handlers without a typed error.
+    fn error_type_name(&self) -> &'static str {
+        std::any::type_name::<BoxError>()
+    }
+
+    /// Execute the tool with unparsed JSON parameters.
+    ///
+    /// Parses the arguments into a `Value` and delegates to `call_json` by
+    /// default. Handlers that can consume the JSON text directly should
+    /// override this.
+    fn call_raw(&self, params: &RawValue) -> BoxFuture<'_, Result<Value, BoxError>> {
+        match serde_json::from_str(params.get()) {
+            Ok(params) => self.call_json(params),
+            Err(e) => Box::pin(async move { Err(Box::new(e) as BoxError) }),
+        }
+    }
+
+    /// Execute the tool, returning its output as raw bytes.
+    ///
+    /// Fails with [`RegistryError::NotBinary`] by default.
+    fn call_binary(&self, params: Value) -> BoxFuture<'_, Result<BinaryOutput, BoxError>> {
+        let _ = params;
+        let tool = self.name().to_string();
+        Box::pin(async move { Err(Box::new(RegistryError::NotBinary { tool }) as BoxError) })
+    }
+
+    /// Field names in declaration order, if the tool accepts positional arguments.
+    ///
+    /// Returns `None` by default.
+    fn positional_fields(&self) -> Option<&'static [&'static str]> {
+        None
+    }
+
+    /// Map positional arguments onto the named fields they stand for, as the
+    /// tool does when called, so they can be validated as an object.
+    ///
+    /// Maps arrays onto [`positional_fields`](Self::positional_fields) by
+    /// default and returns anything else unchanged.
+    fn name_arguments(&self, arguments: Value) -> Result<Value, RegistryError> {
+        match self.positional_fields() {
+            Some(fields) => crate::positional::named_arguments(self.name(), self.params_type_name(), fields, arguments),
+            None => Ok(arguments),
+        }
+    }
+
+    /// Parameter names in declaration order, for `propertyOrdering`.
+    ///
+    /// Returns `None` by default.
+    fn property_order(&self) -> Option<&'static [&'static str]> {
+        None
+    }
+
+    /// Argument field names the tool deserializes, if known. Returns `None` by default.
+    fn accepted_fields(&self) -> Option<&'static [&'static str]> {
+        None
+    }
+
+    /// Parameter fields the model may not set. Returns an empty slice by default.
+    fn server_only_fields(&self) -> &'static [&'static str] {
+        &[]
+    }
+
+    /// How expensive the tool is to call. Returns [`CostHint::Unknown`] by default.
+    fn cost_hint(&self) -> CostHint {
+        CostHint::Unknown
+    }
+
+    /// Whether the tool blocks while it runs and should be kept off the async
+    /// executor. Returns `false` by default.
+    fn is_cpu_bound(&self) -> bool {
+        false
+    }
+
+    /// Free-form key-value tags attached to the tool. Empty by default.
+    fn metadata(&self) -> HashMap<String, String> {
+        HashMap::new()
+    }
+
+    /// Example arguments for the tool. Empty by default.
+    fn examples(&self) -> &[Value] {
+        &[]
+    }
+
+    /// Whether the tool can currently be called. Tools are always available by default.
+    fn is_available(&self) -> BoxFuture<'_, bool> {
+        Box::pin(async { true })
+    }
+
+    /// Whether the tool's result is meant for the user rather than the model.
+    /// Tools are non-terminal by default.
+    fn is_terminal(&self) -> bool {
+        false
+    }
+
+    /// The tool's name, description and parameter schema in one object:
+    /// `{"name": ..., "description": ..., "parameters": ...}`.
+    ///
+    /// Built from [`name`](Self::name), [`description`](Self::description) and
+    /// [`schema`](Self::schema), so wrappers that change those stay consistent.
+    /// `ToolRegistry::full_schema` returns the same shape using the registry's
+    /// cached, transformed schema.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the schema cannot be serialized, like `schema`.
+    fn full_schema(&self) -> Value {
+        full_schema(self.name(), self.description(), self.schema())
+    }
+
+    /// Whether the tool's result is a serialized `ToolResponse` to be sent as-is.
+    ///
+    /// Returns `false` by default; see `ToolRegistry::register_response_function`.
+    fn responds_directly(&self) -> bool {
+        false
+    }
 }
 
 impl<T: ToolFunction> ToolHandler for T {
@@ -95,8 +476,84 @@ impl<T: ToolFunction> ToolHandler for T {
     fn schema(&self) -> Value {
         ToolFunction::schema(self)
     }
+
+    fn try_schema(&self) -> Result<Value, serde_json::Error> {
+        ToolFunction::try_schema(self)
+    }
+
+    fn output_schema(&self) -> Option<Value> {
+        ToolFunction::output_schema(self)
+    }
+
+    fn params_type_name(&self) -> &'static str {
+        ToolFunction::params_type_name(self)
+    }
+
+    fn error_type_name(&self) -> &'static str {
+        ToolFunction::error_type_name(self)
+    }
+
+    fn positional_fields(&self) -> Option<&'static [&'static str]> {
+        ToolFunction::positional_fields(self)
+    }
+
+    fn property_order(&self) -> Option<&'static [&'static str]> {
+        ToolFunction::property_order(self)
+    }
+
+    fn accepted_fields(&self) -> Option<&'static [&'static str]> {
+        ToolFunction::accepted_fields(self)
+    }
+
+    fn server_only_fields(&self) -> &'static [&'static str] {
+        ToolFunction::server_only_fields(self)
+    }
+
+    fn cost_hint(&self) -> CostHint {
+        ToolFunction::cost_hint(self)
+    }
+
+    fn is_cpu_bound(&self) -> bool {
+        ToolFunction::is_cpu_bound(self)
+    }
+
+    fn metadata(&self) -> HashMap<String, String> {
+        ToolFunction::metadata(self)
+    }
+
+    fn examples(&self) -> &[Value] {
+        ToolFunction::examples(self)
+    }
+
+    fn is_terminal(&self) -> bool {
+        ToolFunction::is_terminal(self)
+    }
+
+    fn requires_init(&self) -> bool {
+        ToolFunction::requires_init(self)
+    }
+
+    fn init(&self) -> BoxFuture<'_, Result<(), BoxError>> {
+        ToolFunction::init(self)
+    }
     
-    fn call_json(&self, params: Value) -> Pin<Box<dyn Future<Output = Result<Value, Box<dyn Error + Send + Sync>>> + Send + '_>> {
+    fn call_json(&self, params: Value) -> BoxFuture<'_, Result<Value, BoxError>> {
         ToolFunction::call_json(self, params)
     }
+
+    fn call_raw(&self, params: &RawValue) -> BoxFuture<'_, Result<Value, BoxError>> {
+        ToolFunction::call_raw(self, params)
+    }
+
+    fn call_binary(&self, params: Value) -> BoxFuture<'_, Result<BinaryOutput, BoxError>> {
+        ToolFunction::call_binary(self, params)
+    }
+
+    fn call_json_with_progress(
+        &self,
+        params: Value,
+        progress: ProgressReporter,
+    ) -> BoxFuture<'_, Result<Value, BoxError>> {
+        ToolFunction::call_json_with_progress(self, params, progress)
+    }
 } 
\ No newline at end of file
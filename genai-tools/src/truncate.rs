@@ -0,0 +1,83 @@
+use serde_json::Value;
+
+const MARKER: &str = "...[truncated]";
+
+/// Serialize `value` in at most `max_chars` characters.
+///
+/// The longest string values are shortened first, each ending with a
+/// `...[truncated]` marker, so the result stays valid JSON; objects also get
+/// a `"_truncated": true` field. Only if that is not enough (e.g. a huge array
+/// of numbers) is the serialized text itself cut, which breaks JSON validity.
+pub(crate) fn truncate_result(mut value: Value, max_chars: usize) -> Result<String, serde_json::Error> {
+    let text = serde_json::to_string(&value)?;
+    if text.chars().count() <= max_chars {
+        return Ok(text);
+    }
+
+    if let Value::Object(map) = &mut value {
+        map.insert("_truncated".to_string(), Value::Bool(true));
+    }
+
+    loop {
+        let text = serde_json::to_string(&value)?;
+        let len = text.chars().count();
+        if len <= max_chars {
+            return Ok(text);
+        }
+        match longest_string(&mut value) {
+            Some(string) => shorten(string, len - max_chars),
+            None => return Ok(cut_text(&text, max_chars)),
+        }
+    }
+}
+
+/// The longest string in `value` that shortening would actually make shorter.
+fn longest_string(value: &mut Value) -> Option<&mut String> {
+    match value {
+        Value::String(string) if can_shorten(string) => Some(string),
+        Value::Array(items) => items
+            .iter_mut()
+            .filter_map(longest_string)
+            .max_by_key(|string| body_len(string)),
+        Value::Object(map) => map
+            .values_mut()
+            .filter_map(longest_string)
+            .max_by_key(|string| body_len(string)),
+        _ => None,
+    }
+}
+
+fn can_shorten(string: &str) -> bool {
+    if string.ends_with(MARKER) {
+        body_len(string) > 0
+    } else {
+        string.chars().count() > MARKER.chars().count()
+    }
+}
+
+fn body_len(string: &str) -> usize {
+    string.strip_suffix(MARKER).unwrap_or(string).chars().count()
+}
+
+/// Remove at least `excess` characters from `string`, marking it as truncated.
+fn shorten(string: &mut String, excess: usize) {
+    let marked = string.ends_with(MARKER);
+    let body = string.strip_suffix(MARKER).unwrap_or(string);
+    let added = if marked { 0 } else { MARKER.chars().count() };
+    let keep = body.chars().count().saturating_sub(excess + added);
+    *string = body.chars().take(keep).chain(MARKER.chars()).collect();
+}
+
+fn cut_text(text: &str, max_chars: usize) -> String {
+    let total = text.chars().count();
+    let mut keep = max_chars;
+    loop {
+        let marker = format!("...[truncated {} chars]", total - keep);
+        let marker_len = marker.chars().count();
+        if keep + marker_len <= max_chars || keep == 0 {
+            // A limit shorter than the marker alone cuts the marker too
+            return text.chars().take(keep).chain(marker.chars()).take(max_chars).collect();
+        }
+        keep = max_chars.saturating_sub(marker_len);
+    }
+}
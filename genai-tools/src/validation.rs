@@ -0,0 +1,54 @@
+use crate::error::SchemaViolation;
+use serde_json::Value;
+
+/// A schema compiled once for validating many instances.
+///
+/// A schema that cannot be compiled reports a single root violation for
+/// every instance.
+pub(crate) struct CompiledSchema(Result<jsonschema::Validator, String>);
+
+impl CompiledSchema {
+    pub(crate) fn new(schema: &Value) -> Self {
+        Self(jsonschema::validator_for(schema).map_err(|e| format!("invalid schema: {}", e)))
+    }
+
+    /// Validate `instance`, collecting every violation.
+    pub(crate) fn validate(&self, instance: &Value) -> Result<(), Vec<SchemaViolation>> {
+        let validator = self.0.as_ref().map_err(|message| {
+            vec![SchemaViolation {
+                pointer: String::new(),
+                message: message.clone(),
+            }]
+        })?;
+
+        let violations: Vec<SchemaViolation> = validator
+            .iter_errors(instance)
+            .map(|e| SchemaViolation {
+                pointer: e.instance_path().to_string(),
+                message: e.to_string(),
+            })
+            .collect();
+
+        if violations.is_empty() {
+            Ok(())
+        } else {
+            Err(violations)
+        }
+    }
+}
+
+/// Check that `schema` is a JSON object that conforms to its JSON Schema
+/// meta-schema.
+pub(crate) fn check_schema(schema: &Value) -> Result<(), String> {
+    if !schema.is_object() {
+        return Err(format!("schema is not a JSON object: {}", schema));
+    }
+    jsonschema::meta::validate(schema).map_err(|e| {
+        let pointer = e.instance_path().to_string();
+        if pointer.is_empty() {
+            e.to_string()
+        } else {
+            format!("{}: {}", pointer, e)
+        }
+    })
+}
@@ -0,0 +1,104 @@
+use crate::adapter::ToolCallLike;
+use crate::error::RegistryError;
+use crate::registry::ToolRegistry;
+use crate::suggest::closest_names;
+use crate::traits::BoxError;
+use genai::chat::{Tool, ToolResponse};
+use std::collections::HashSet;
+
+/// A borrowed view of a [`ToolRegistry`] restricted to a subset of its tools.
+///
+/// Created with [`ToolRegistry::view`]. The view shares the parent's handlers
+/// instead of copying them, so many tenants can each get their own tool set
+/// from a single registry. Tools outside the allowed set are invisible: they
+/// are omitted from [`get_tools`](Self::get_tools) and calls to them fail as
+/// if they were not registered. The allowed set may name a tool by its name
+/// or by its advertised name.
+///
+/// # Example
+///
+/// ```ignore
+/// let allowed: HashSet<String> = tenant.enabled_tools();
+/// let view = registry.view(&allowed);
+///
+/// let chat_req = ChatRequest::new(messages).with_tools(view.get_tools());
+/// let response = view.execute_call(&tool_call).await?;
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct RegistryView<'a> {
+    registry: &'a ToolRegistry,
+    allowed: &'a HashSet<String>,
+}
+
+impl<'a> RegistryView<'a> {
+    pub(crate) fn new(registry: &'a ToolRegistry, allowed: &'a HashSet<String>) -> Self {
+        Self { registry, allowed }
+    }
+
+    /// Get the visible tools as `genai::chat::Tool` objects.
+    pub fn get_tools(&self) -> Vec<Tool> {
+        self.tool_names()
+            .into_iter()
+            .filter_map(|name| self.registry.handler(name))
+            .filter_map(|handler| self.registry.to_tool(handler).ok())
+            .collect()
+    }
+
+    /// Execute a tool call if the tool is visible in this view.
+    pub async fn execute_call<C>(&self, tool_call: &C) -> Result<ToolResponse, BoxError>
+    where
+        C: ToolCallLike + ?Sized,
+    {
+        if !self.has_tool(tool_call.fn_name()) {
+            return Err(Box::new(RegistryError::ToolNotFound {
+                tool: tool_call.fn_name().to_string(),
+                suggestions: closest_names(
+                    tool_call.fn_name(),
+                    self.tool_names().into_iter().map(|name| self.registry.advertised_name(name)),
+                ),
+            }));
+        }
+        self.registry.execute_call(tool_call).await
+    }
+
+    /// Execute multiple tool calls concurrently, failing if any tool is not visible.
+    pub async fn execute_calls<C: ToolCallLike>(&self, tool_calls: &[C]) -> Result<Vec<ToolResponse>, BoxError> {
+        let futures: Vec<_> = tool_calls
+            .iter()
+            .map(|call| self.execute_call(call))
+            .collect();
+
+        futures::future::try_join_all(futures).await
+    }
+
+    /// Get the names of the visible tools.
+    pub fn tool_names(&self) -> Vec<&'a str> {
+        self.registry
+            .tool_names()
+            .into_iter()
+            .filter(|name| self.is_allowed(name))
+            .collect()
+    }
+
+    /// Check if a tool is registered and visible in this view, by its name or
+    /// advertised name.
+    pub fn has_tool(&self, name: &str) -> bool {
+        let name = self.registry.resolve_name(name);
+        self.registry.has_tool(name) && self.is_allowed(name)
+    }
+
+    /// Whether the allowed set names the tool `name` by its name or advertised name.
+    fn is_allowed(&self, name: &str) -> bool {
+        self.allowed.contains(name) || self.allowed.contains(self.registry.advertised_name(name))
+    }
+
+    /// Get the number of visible tools.
+    pub fn len(&self) -> usize {
+        self.tool_names().len()
+    }
+
+    /// Check if no tools are visible.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
@@ -0,0 +1,54 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// A successful tool result with warnings for the model, such as "served from
+/// a stale cache".
+///
+/// Return it as a tool's output type to have the result sent as
+/// `{"result": ..., "warnings": [...]}`. `warnings` is omitted when empty, so
+/// the model only sees it when there is something to flag. Tools returning
+/// any other type are sent unwrapped as before.
+///
+/// # Example
+///
+/// ```ignore
+/// #[tool_function(description = "Look up a stock price", output_schema)]
+/// pub async fn stock_price(symbol: String) -> Result<WithWarnings<f64>, PriceError> {
+///     let (price, fresh) = cache.price(&symbol).await?;
+///     let mut output = WithWarnings::new(price);
+///     if !fresh {
+///         output = output.with_warning("price is from a cache older than 15 minutes");
+///     }
+///     Ok(output)
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct WithWarnings<T> {
+    /// The tool's result.
+    pub result: T,
+    /// Problems that didn't prevent the tool from succeeding.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
+}
+
+impl<T> WithWarnings<T> {
+    /// Wrap `result` without any warnings.
+    pub fn new(result: T) -> Self {
+        Self {
+            result,
+            warnings: Vec::new(),
+        }
+    }
+
+    /// Add a warning.
+    pub fn with_warning(mut self, warning: impl Into<String>) -> Self {
+        self.warnings.push(warning.into());
+        self
+    }
+}
+
+impl<T> From<T> for WithWarnings<T> {
+    fn from(result: T) -> Self {
+        Self::new(result)
+    }
+}
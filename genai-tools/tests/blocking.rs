@@ -0,0 +1,71 @@
+#![cfg(feature = "blocking")]
+
+use genai::chat::ToolCall;
+use genai_tools::{tool_function, BoxError, ToolHandler, ToolRegistry};
+use serde_json::json;
+
+/// The thread the tool body ran on.
+fn thread_id() -> String {
+    format!("{:?}", std::thread::current().id())
+}
+
+#[tool_function(description = "Checksum a document", cpu_bound)]
+pub async fn checksum() -> Result<String, BoxError> {
+    Ok(thread_id())
+}
+
+#[tool_function(description = "Tokenize a document")]
+pub async fn tokenize() -> Result<String, BoxError> {
+    Ok(thread_id())
+}
+
+async fn run_on(registry: &ToolRegistry, name: &str) -> String {
+    let call = ToolCall {
+        call_id: "call-1".to_string(),
+        fn_name: name.to_string(),
+        fn_arguments: json!({}),
+    };
+    registry.execute_call_result(&call).await.unwrap()
+}
+
+#[tokio::test]
+async fn test_cpu_bound_tools_run_on_the_blocking_pool() {
+    assert!(checksum_tool().is_cpu_bound());
+    assert!(!tokenize_tool().is_cpu_bound());
+
+    let mut registry = ToolRegistry::new();
+    registry.register_function(checksum_tool()).register_function(tokenize_tool());
+
+    let here = thread_id();
+    assert_ne!(run_on(&registry, "checksum").await, here);
+    assert_eq!(run_on(&registry, "tokenize").await, here);
+
+    registry.set_blocking("checksum", false).unwrap().set_blocking("tokenize", true).unwrap();
+    assert_eq!(run_on(&registry, "checksum").await, here);
+    assert_ne!(run_on(&registry, "tokenize").await, here);
+
+    assert!(registry.set_blocking("missing", true).is_err());
+}
+
+#[tokio::test]
+async fn test_blocking_tools_keep_their_middleware() {
+    let mut registry = ToolRegistry::new();
+    registry.register_function(checksum_tool());
+    registry
+        .wrap_tool("checksum", |invocation, next| {
+            Box::pin(async move {
+                let on_tool_thread = next.run(invocation).await?;
+                Ok(json!({ "tool": on_tool_thread, "middleware": thread_id() }))
+            })
+        })
+        .unwrap();
+
+    let call = ToolCall {
+        call_id: "call-1".to_string(),
+        fn_name: "checksum".to_string(),
+        fn_arguments: json!({}),
+    };
+    let result: serde_json::Value = registry.execute_call_result(&call).await.unwrap();
+    assert_eq!(result["middleware"], thread_id());
+    assert_ne!(result["tool"], thread_id());
+}
@@ -0,0 +1,102 @@
+#![cfg(feature = "http")]
+
+use genai_tools::{HttpMethod, HttpToolError, ToolRegistry};
+use serde_json::{json, Value};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+
+/// Serve a single HTTP request, echoing the JSON request body back under `"received"`.
+async fn serve_once(status: u16, reason: &'static str) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}/tool", listener.local_addr().unwrap());
+
+    tokio::spawn(async move {
+        let (mut socket, _) = listener.accept().await.unwrap();
+        let mut buf = Vec::new();
+        let mut chunk = [0u8; 1024];
+        let body = loop {
+            let n = socket.read(&mut chunk).await.unwrap();
+            buf.extend_from_slice(&chunk[..n]);
+            let text = String::from_utf8_lossy(&buf).to_string();
+            if let Some(header_end) = text.find("\r\n\r\n") {
+                let content_length = text[..header_end]
+                    .lines()
+                    .find_map(|line| line.to_ascii_lowercase().strip_prefix("content-length:").map(|v| v.trim().parse::<usize>().unwrap()))
+                    .unwrap_or(0);
+                if buf.len() >= header_end + 4 + content_length {
+                    break text[header_end + 4..].to_string();
+                }
+            }
+        };
+
+        let received: Value = serde_json::from_str(&body).unwrap();
+        let payload = json!({ "received": received }).to_string();
+        let response = format!(
+            "HTTP/1.1 {} {}\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{}",
+            status,
+            reason,
+            payload.len(),
+            payload
+        );
+        socket.write_all(response.as_bytes()).await.unwrap();
+    });
+
+    url
+}
+
+fn lookup_call() -> genai::chat::ToolCall {
+    genai::chat::ToolCall {
+        call_id: "http-1".to_string(),
+        fn_name: "lookup_order".to_string(),
+        fn_arguments: json!({ "id": "A-42" }),
+    }
+}
+
+#[tokio::test]
+async fn test_http_tool_proxies_arguments_and_result() {
+    let url = serve_once(200, "OK").await;
+
+    let mut registry = ToolRegistry::new();
+    registry.register_http_tool(
+        "lookup_order",
+        "Look up an order by id",
+        json!({"type": "object", "properties": {"id": {"type": "string"}}, "required": ["id"]}),
+        url,
+        HttpMethod::POST,
+    );
+
+    let tools = registry.get_tools();
+    assert_eq!(tools[0].name, "lookup_order");
+    assert_eq!(tools[0].schema.as_ref().unwrap()["required"], json!(["id"]));
+
+    let response = registry.execute_call(&lookup_call()).await.unwrap();
+    let result: Value = serde_json::from_str(&response.content).unwrap();
+    assert_eq!(result, json!({ "received": { "id": "A-42" } }));
+}
+
+#[tokio::test]
+async fn test_http_tool_non_success_status_is_structured_error() {
+    let url = serve_once(503, "Service Unavailable").await;
+
+    let mut registry = ToolRegistry::new();
+    registry.register_http_tool("lookup_order", "Look up an order", json!({"type": "object"}), url, HttpMethod::POST);
+
+    let error = registry.execute_call(&lookup_call()).await.unwrap_err();
+    let http_error = error.downcast_ref::<HttpToolError>().unwrap();
+    assert_eq!(http_error.tool, "lookup_order");
+    assert_eq!(http_error.status, 503);
+    assert_eq!(http_error.body["received"]["id"], json!("A-42"));
+}
+
+#[tokio::test]
+async fn test_http_tool_forwards_raw_arguments() {
+    let url = serve_once(200, "OK").await;
+
+    let mut registry = ToolRegistry::new();
+    registry.register_http_tool("lookup_order", "Look up an order", json!({"type": "object"}), url, HttpMethod::POST);
+
+    let arguments: Box<serde_json::value::RawValue> = serde_json::from_str(r#"{"id": "B-7"}"#).unwrap();
+    let response = registry.execute_raw("http-2", "lookup_order", &arguments).await.unwrap();
+    let result: Value = serde_json::from_str(&response.content).unwrap();
+    assert_eq!(result, json!({ "received": { "id": "B-7" } }));
+}
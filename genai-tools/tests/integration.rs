@@ -1,4 +1,4 @@
-use genai_tools::{tool_function, ToolRegistry, ToolFunction};
+use genai_tools::{tool_function, tool_set, RegistryError, ToolFlow, ToolRegistry, ToolFunction};
 use serde::{Deserialize, Serialize};
 use schemars::JsonSchema;
 use serde_json::json;
@@ -11,6 +11,7 @@ pub struct IntegrationParams {
     /// An optional integer field
     pub count: Option<i32>,
     /// A vector of strings
+    #[schemars(length(max = 10))]
     pub items: Vec<String>,
     /// A nested enum
     pub status: Status,
@@ -125,7 +126,7 @@ async fn test_tool_execution_with_all_parameter_types() {
     
     let result = tool.call(params).await.unwrap();
     
-    assert_eq!(result.processed, true);
+    assert!(result.processed);
     assert_eq!(result.name, "Test Tool");
     assert_eq!(result.item_count, 3);
     assert_eq!(result.status_text, "Currently active");
@@ -144,7 +145,7 @@ async fn test_tool_execution_with_optional_params() {
     
     let result = tool.call(params).await.unwrap();
     
-    assert_eq!(result.processed, true);
+    assert!(result.processed);
     assert_eq!(result.name, "Minimal Test");
     assert_eq!(result.item_count, 0);
     assert_eq!(result.status_text, "Awaiting activation");
@@ -204,7 +205,7 @@ async fn test_json_execution_roundtrip() {
     // Parse the result back
     let result: IntegrationResult = serde_json::from_value(result_json).unwrap();
     
-    assert_eq!(result.processed, true);
+    assert!(result.processed);
     assert_eq!(result.name, "JSON Test");
     assert_eq!(result.item_count, 3);
     assert_eq!(result.status_text, "Currently inactive");
@@ -373,4 +374,3954 @@ async fn test_concurrent_tool_execution() {
         assert_eq!(result.name, format!("Concurrent {}", i + 1));
         assert!(result.processed);
     }
-} 
\ No newline at end of file
+} 
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct CounterParams {
+    /// The value to report
+    pub value: u32,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct CounterResult {
+    /// Never exceeds 10 according to the contract
+    #[schemars(range(max = 10))]
+    pub count: u32,
+}
+
+#[tool_function(description = "Reports a count, sometimes breaking its contract", output_schema)]
+pub async fn report_count(params: CounterParams) -> Result<CounterResult, IntegrationError> {
+    Ok(CounterResult { count: params.value })
+}
+
+fn counter_call(value: u32) -> genai::chat::ToolCall {
+    genai::chat::ToolCall {
+        call_id: "counter-1".to_string(),
+        fn_name: "report_count".to_string(),
+        fn_arguments: json!({ "value": value }),
+    }
+}
+
+#[test]
+fn test_output_schema_is_advertised() {
+    let tool = report_count_tool();
+    let output_schema = tool.output_schema().unwrap();
+    assert_eq!(output_schema["properties"]["count"]["maximum"], json!(10));
+
+    assert!(integration_test_tool_tool().output_schema().is_none());
+}
+
+#[tokio::test]
+async fn test_output_validation_rejects_contract_violation() {
+    let mut registry = ToolRegistry::new().with_output_validation(true);
+    registry.register_function(report_count_tool());
+
+    let response = registry.execute_call(&counter_call(3)).await.unwrap();
+    assert_eq!(response.content, r#"{"count":3}"#);
+
+    let error = registry.execute_call(&counter_call(42)).await.unwrap_err();
+    match error.downcast_ref::<RegistryError>() {
+        Some(RegistryError::OutputValidation { tool, violations }) => {
+            assert_eq!(tool, "report_count");
+            assert_eq!(violations.len(), 1);
+            assert_eq!(violations[0].pointer, "/count");
+        }
+        other => panic!("expected OutputValidation error, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn test_output_validation_disabled_by_default() {
+    let mut registry = ToolRegistry::new();
+    registry.register_function(report_count_tool());
+
+    let response = registry.execute_call(&counter_call(42)).await.unwrap();
+    assert_eq!(response.content, r#"{"count":42}"#);
+}
+
+#[tokio::test]
+async fn test_input_validation_reports_all_violations() {
+    let mut registry = ToolRegistry::new().with_input_validation(true);
+    registry.register_function(integration_test_tool_tool());
+
+    let tool_call = genai::chat::ToolCall {
+        call_id: "invalid-1".to_string(),
+        fn_name: "integration_test_tool".to_string(),
+        fn_arguments: json!({
+            "name": 42,
+            "items": "not-a-list",
+            "status": "unknown"
+        }),
+    };
+
+    let error = registry.execute_call(&tool_call).await.unwrap_err();
+    match error.downcast_ref::<RegistryError>() {
+        Some(RegistryError::InputValidation { tool, violations }) => {
+            assert_eq!(tool, "integration_test_tool");
+            let mut pointers: Vec<&str> = violations.iter().map(|v| v.pointer.as_str()).collect();
+            pointers.sort();
+            assert_eq!(pointers, vec!["/items", "/name", "/status"]);
+        }
+        other => panic!("expected InputValidation error, got {:?}", other),
+    }
+
+    let message = error.to_string();
+    assert!(message.contains("/name"));
+    assert!(message.contains("/items"));
+    assert!(message.contains("/status"));
+}
+
+#[tokio::test]
+async fn test_input_validation_accepts_valid_arguments() {
+    let mut registry = ToolRegistry::new().with_input_validation(true);
+    registry.register_function(integration_test_tool_tool());
+
+    let tool_call = genai::chat::ToolCall {
+        call_id: "valid-1".to_string(),
+        fn_name: "integration_test_tool".to_string(),
+        fn_arguments: json!({
+            "name": "Valid",
+            "items": ["a"],
+            "status": "active"
+        }),
+    };
+
+    let response = registry.execute_call(&tool_call).await.unwrap();
+    let result: IntegrationResult = serde_json::from_str(&response.content).unwrap();
+    assert_eq!(result.name, "Valid");
+}
+
+#[tokio::test]
+async fn test_input_validation_follows_schema_changes() {
+    let mut registry = ToolRegistry::new().with_input_validation(true);
+    registry.register_function(integration_test_tool_tool());
+
+    let tool_call = genai::chat::ToolCall {
+        call_id: "valid-1".to_string(),
+        fn_name: "integration_test_tool".to_string(),
+        fn_arguments: json!({
+            "name": "Valid",
+            "items": ["a"],
+            "status": "active"
+        }),
+    };
+    registry.execute_call(&tool_call).await.unwrap();
+
+    let registry = registry.with_schema_transform(|mut schema| {
+        schema["properties"]["name"]["maxLength"] = json!(3);
+        schema
+    });
+    let error = registry.execute_call(&tool_call).await.unwrap_err();
+    match error.downcast_ref::<RegistryError>() {
+        Some(RegistryError::InputValidation { violations, .. }) => {
+            assert_eq!(violations.len(), 1);
+            assert_eq!(violations[0].pointer, "/name");
+        }
+        other => panic!("expected InputValidation error, got {:?}", other),
+    }
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct SummaryParams {
+    /// The name to summarize
+    pub name: String,
+    /// The number of items
+    pub item_count: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SummaryResult {
+    pub summary: String,
+}
+
+#[tool_function(description = "Summarize a processed record")]
+pub async fn summarize_record(params: SummaryParams) -> Result<SummaryResult, IntegrationError> {
+    Ok(SummaryResult {
+        summary: format!("{} has {} item(s)", params.name, params.item_count),
+    })
+}
+
+fn pipeline_registry() -> ToolRegistry {
+    let mut registry = ToolRegistry::new();
+    registry.register_pipeline(
+        "process_and_summarize",
+        "Process a record and summarize the outcome",
+        vec![Box::new(integration_test_tool_tool()), Box::new(summarize_record_tool())],
+    );
+    registry
+}
+
+#[tokio::test]
+async fn test_pipeline_chains_stage_outputs() {
+    let registry = pipeline_registry();
+
+    assert_eq!(registry.len(), 1);
+    let tools = registry.get_tools();
+    assert_eq!(tools[0].name, "process_and_summarize");
+    assert_eq!(tools[0].schema.as_ref().unwrap(), &integration_test_tool_tool().schema());
+
+    let tool_call = genai::chat::ToolCall {
+        call_id: "pipeline-1".to_string(),
+        fn_name: "process_and_summarize".to_string(),
+        fn_arguments: json!({
+            "name": "Widget",
+            "items": ["a", "b"],
+            "status": "active"
+        }),
+    };
+
+    let response = registry.execute_call(&tool_call).await.unwrap();
+    let result: SummaryResult = serde_json::from_str(&response.content).unwrap();
+    assert_eq!(result.summary, "Widget has 2 item(s)");
+}
+
+#[tokio::test]
+async fn test_pipeline_short_circuits_on_stage_error() {
+    let registry = pipeline_registry();
+
+    let tool_call = genai::chat::ToolCall {
+        call_id: "pipeline-2".to_string(),
+        fn_name: "process_and_summarize".to_string(),
+        fn_arguments: json!({
+            "name": "",
+            "items": [],
+            "status": "active"
+        }),
+    };
+
+    let error = registry.execute_call(&tool_call).await.unwrap_err();
+    match error.downcast_ref::<RegistryError>() {
+        Some(RegistryError::PipelineStage { pipeline, index, stage, source }) => {
+            assert_eq!(pipeline, "process_and_summarize");
+            assert_eq!(*index, 0);
+            assert_eq!(stage, "integration_test_tool");
+            assert!(source.to_string().contains("Name cannot be empty"));
+        }
+        other => panic!("expected PipelineStage error, got {:?}", other),
+    }
+}
+
+/// A pipeline stage whose backend is offline.
+struct OfflineStage;
+
+impl genai_tools::ToolHandler for OfflineStage {
+    fn name(&self) -> &str {
+        "offline_stage"
+    }
+
+    fn description(&self) -> &str {
+        "A stage that is never available"
+    }
+
+    fn schema(&self) -> serde_json::Value {
+        json!({ "type": "object" })
+    }
+
+    fn try_schema(&self) -> Result<serde_json::Value, serde_json::Error> {
+        Ok(self.schema())
+    }
+
+    fn output_schema(&self) -> Option<serde_json::Value> {
+        None
+    }
+
+    fn requires_init(&self) -> bool {
+        false
+    }
+
+    fn init(&self) -> genai_tools::BoxFuture<'_, Result<(), genai_tools::BoxError>> {
+        Box::pin(async { Ok(()) })
+    }
+
+    fn call_json(
+        &self,
+        _params: serde_json::Value,
+    ) -> genai_tools::BoxFuture<'_, Result<serde_json::Value, genai_tools::BoxError>> {
+        panic!("an unavailable stage must not run")
+    }
+
+    fn is_available(&self) -> genai_tools::BoxFuture<'_, bool> {
+        Box::pin(async { false })
+    }
+}
+
+#[tokio::test]
+async fn test_pipeline_is_unavailable_while_a_stage_is() {
+    let mut registry = ToolRegistry::new();
+    registry.register_pipeline(
+        "process_offline",
+        "Process a record with an offline backend",
+        vec![Box::new(integration_test_tool_tool()), Box::new(OfflineStage)],
+    );
+    assert!(registry.get_available_tools().await.is_empty());
+
+    let tool_call = genai::chat::ToolCall {
+        call_id: "pipeline-3".to_string(),
+        fn_name: "process_offline".to_string(),
+        fn_arguments: json!({ "name": "Widget", "items": [], "status": "active" }),
+    };
+    let error = registry.execute_call(&tool_call).await.unwrap_err();
+    assert!(matches!(
+        error.downcast_ref::<RegistryError>(),
+        Some(RegistryError::Unavailable { tool }) if tool == "process_offline"
+    ));
+}
+
+#[tokio::test]
+async fn test_guarded_tool_availability_changes_at_runtime() {
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::Arc;
+
+    let enabled = Arc::new(AtomicBool::new(false));
+    let flag = enabled.clone();
+
+    let mut registry = ToolRegistry::new();
+    registry.register_function(minimal_tool_tool());
+    registry.register_function_with_guard(integration_test_tool_tool(), move || {
+        let flag = flag.clone();
+        async move { flag.load(Ordering::SeqCst) }
+    });
+
+    // Unavailable tools are still registered but hidden from the available set
+    assert_eq!(registry.get_tools().len(), 2);
+    let available = registry.get_available_tools().await;
+    assert_eq!(available.len(), 1);
+    assert_eq!(available[0].name, "minimal_tool");
+
+    let tool_call = genai::chat::ToolCall {
+        call_id: "guarded-1".to_string(),
+        fn_name: "integration_test_tool".to_string(),
+        fn_arguments: json!({
+            "name": "Guarded",
+            "items": [],
+            "status": "active"
+        }),
+    };
+
+    let error = registry.execute_call(&tool_call).await.unwrap_err();
+    assert!(matches!(
+        error.downcast_ref::<RegistryError>(),
+        Some(RegistryError::Unavailable { tool }) if tool == "integration_test_tool"
+    ));
+
+    enabled.store(true, Ordering::SeqCst);
+    assert_eq!(registry.get_available_tools().await.len(), 2);
+    assert!(registry.execute_call(&tool_call).await.is_ok());
+}
+
+#[tokio::test]
+async fn test_registry_view_exposes_only_allowed_tools() {
+    use std::collections::HashSet;
+
+    let mut registry = ToolRegistry::new();
+    registry.register_function(integration_test_tool_tool());
+    registry.register_function(minimal_tool_tool());
+
+    let allowed: HashSet<String> = ["minimal_tool".to_string(), "not_registered".to_string()].into();
+    let view = registry.view(&allowed);
+
+    assert_eq!(view.len(), 1);
+    assert!(view.has_tool("minimal_tool"));
+    assert!(!view.has_tool("integration_test_tool"));
+    assert!(!view.has_tool("not_registered"));
+
+    let tools = view.get_tools();
+    assert_eq!(tools.len(), 1);
+    assert_eq!(tools[0].name, "minimal_tool");
+
+    let arguments = json!({
+        "name": "Tenant",
+        "items": [],
+        "status": "active"
+    });
+
+    let allowed_call = genai::chat::ToolCall {
+        call_id: "view-1".to_string(),
+        fn_name: "minimal_tool".to_string(),
+        fn_arguments: arguments.clone(),
+    };
+    assert!(view.execute_call(&allowed_call).await.is_ok());
+
+    let hidden_call = genai::chat::ToolCall {
+        call_id: "view-2".to_string(),
+        fn_name: "integration_test_tool".to_string(),
+        fn_arguments: arguments,
+    };
+    let error = view.execute_call(&hidden_call).await.unwrap_err();
+    assert!(error.to_string().contains("not found"));
+
+    // The parent registry is unaffected
+    assert!(registry.execute_call(&hidden_call).await.is_ok());
+
+    // Tools are called by the name the view advertises
+    registry.register_function_as(add_numbers_tool(), "sum_values").unwrap();
+    let allowed: HashSet<String> = ["add_numbers".to_string()].into();
+    let view = registry.view(&allowed);
+    assert_eq!(view.get_tools()[0].name, "sum_values");
+    assert!(view.has_tool("sum_values"));
+    let call = genai::chat::ToolCall {
+        call_id: "view-3".to_string(),
+        fn_name: "sum_values".to_string(),
+        fn_arguments: json!({ "a": 2, "b": 3 }),
+    };
+    assert_eq!(view.execute_call(&call).await.unwrap().content, "5");
+
+    // A tool allowed under both names is listed once
+    let allowed: HashSet<String> = ["add_numbers".to_string(), "sum_values".to_string()].into();
+    let view = registry.view(&allowed);
+    assert_eq!(view.get_tools().len(), 1);
+    assert_eq!(view.len(), 1);
+}
+
+#[tool_function(description = "Add two numbers")]
+pub async fn add_numbers(
+    /// The first addend
+    a: i64,
+    /// The second addend
+    #[serde(default)]
+    b: i64,
+) -> Result<i64, IntegrationError> {
+    Ok(a + b)
+}
+
+#[tool_function(description = "Echo a piece of text", flatten)]
+pub async fn echo_text(
+    /// The text to echo
+    text: String,
+) -> Result<String, IntegrationError> {
+    Ok(text)
+}
+
+#[tokio::test]
+async fn test_flattened_params_struct_is_exposed() {
+    // The synthesized struct can be built and passed to the tool directly
+    let params = AddNumbersParams { a: 2, b: 3 };
+    assert_eq!(add_numbers_tool().call(params).await.unwrap(), 5);
+
+    let schema = add_numbers_tool().schema();
+    assert_eq!(schema["title"], json!("AddNumbersParams"));
+    assert_eq!(schema["properties"]["a"]["description"], json!("The first addend"));
+    assert_eq!(schema["required"], json!(["a"]));
+
+    let result = add_numbers_tool().call_json(json!({ "a": 40 })).await.unwrap();
+    assert_eq!(result, json!(40));
+}
+
+#[tokio::test]
+async fn test_flatten_flag_wraps_single_argument() {
+    let tool = echo_text_tool();
+    let schema = tool.schema();
+    assert_eq!(schema["properties"]["text"]["description"], json!("The text to echo"));
+
+    let params = EchoTextParams { text: "hi".to_string() };
+    assert_eq!(tool.call(params).await.unwrap(), "hi");
+}
+
+#[tool_function(description = "Repeat a word")]
+pub async fn repeat_word(
+    /// The word to repeat
+    #[serde(rename = "text")]
+    word: String,
+    /// How many times to repeat it
+    times: usize,
+) -> Result<String, IntegrationError> {
+    Ok(word.repeat(times))
+}
+
+#[tokio::test]
+async fn test_positional_arguments() {
+    assert_eq!(add_numbers_tool().positional_fields(), Some(&["a", "b"][..]));
+    assert_eq!(repeat_word_tool().positional_fields(), Some(&["text", "times"][..]));
+    assert_eq!(integration_test_tool_tool().positional_fields(), None);
+
+    assert_eq!(add_numbers_tool().call_json(json!([2, 3])).await.unwrap(), json!(5));
+    assert_eq!(add_numbers_tool().call_json(json!({ "a": 2, "b": 3 })).await.unwrap(), json!(5));
+    assert_eq!(repeat_word_tool().call_json(json!(["ab", 2])).await.unwrap(), json!("abab"));
+
+    let error = add_numbers_tool().call_json(json!([1, 2, 3])).await.unwrap_err();
+    assert!(error
+        .to_string()
+        .contains("expected 2 positional arguments (a, b), got 3"));
+    assert!(matches!(
+        error.downcast_ref::<RegistryError>(),
+        Some(RegistryError::InvalidArguments { .. })
+    ));
+
+    // Positional arrays pass input validation and the raw path
+    let mut registry = ToolRegistry::new().with_input_validation(true);
+    registry.register_function(add_numbers_tool());
+    let call = genai::chat::ToolCall {
+        call_id: "positional".to_string(),
+        fn_name: "add_numbers".to_string(),
+        fn_arguments: json!([4, 5]),
+    };
+    assert_eq!(registry.execute_call(&call).await.unwrap().content, "9");
+
+    let mut registry = ToolRegistry::new();
+    registry.register_function(add_numbers_tool());
+    let raw = serde_json::value::RawValue::from_string("[4, 6]".to_string()).unwrap();
+    assert_eq!(registry.execute_raw("raw", "add_numbers", &raw).await.unwrap().content, "10");
+
+    // Positions of a bound tool skip the bound fields
+    registry.register_with_defaults(add_numbers_tool(), "add_to_ten", json!({ "a": 10 }));
+    let call = genai::chat::ToolCall {
+        call_id: "bound".to_string(),
+        fn_name: "add_to_ten".to_string(),
+        fn_arguments: json!([5]),
+    };
+    assert_eq!(registry.execute_call(&call).await.unwrap().content, "15");
+
+    // and are validated as the fields they stand for
+    let registry = registry.with_input_validation(true);
+    assert_eq!(registry.execute_call(&call).await.unwrap().content, "15");
+    let call = genai::chat::ToolCall {
+        fn_arguments: json!(["five"]),
+        ..call
+    };
+    let error = registry.execute_call(&call).await.unwrap_err();
+    assert!(matches!(
+        error.downcast_ref::<RegistryError>(),
+        Some(RegistryError::InputValidation { tool, .. }) if tool == "add_to_ten"
+    ));
+}
+
+#[test]
+fn test_registry_iteration() {
+    let mut registry = ToolRegistry::new();
+    registry.register_function(integration_test_tool_tool());
+    registry.register_function(minimal_tool_tool());
+
+    assert_eq!(registry.iter().len(), 2);
+
+    let mut seen = Vec::new();
+    for (name, handler) in &registry {
+        assert_eq!(name, handler.name());
+        seen.push(name);
+    }
+    seen.sort();
+    assert_eq!(seen, vec!["integration_test_tool", "minimal_tool"]);
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct BookingParams {
+    /// The party size
+    pub party_size: u32,
+    /// The requested time, if known
+    pub time: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct Booking {
+    pub confirmation: String,
+}
+
+#[tool_function(description = "Book a table, asking for missing details")]
+pub async fn book_table(params: BookingParams) -> Result<ToolFlow<Booking>, IntegrationError> {
+    match params.time {
+        None => Ok(ToolFlow::NeedMoreInput(json!({ "missing": ["time"] }))),
+        Some(time) => Ok(ToolFlow::Done(Booking {
+            confirmation: format!("{} people at {}", params.party_size, time),
+        })),
+    }
+}
+
+#[tokio::test]
+async fn test_tool_flow_need_more_input_is_surfaced() {
+    let mut registry = ToolRegistry::new();
+    registry.register_function(book_table_tool());
+
+    let mut tool_call = genai::chat::ToolCall {
+        call_id: "flow-1".to_string(),
+        fn_name: "book_table".to_string(),
+        fn_arguments: json!({ "party_size": 2 }),
+    };
+
+    let response = registry.execute_call(&tool_call).await.unwrap();
+    let content: serde_json::Value = serde_json::from_str(&response.content).unwrap();
+    assert_eq!(content, json!({ "status": "need_more_input", "request": { "missing": ["time"] } }));
+    let flow = ToolFlow::from_response(&response);
+    assert!(flow.needs_more_input());
+    assert_eq!(flow, ToolFlow::NeedMoreInput(json!({ "missing": ["time"] })));
+
+    // The done path serializes exactly like a plain result
+    tool_call.fn_arguments = json!({ "party_size": 2, "time": "19:00" });
+    let response = registry.execute_call(&tool_call).await.unwrap();
+    let booking: Booking = serde_json::from_str(&response.content).unwrap();
+    assert_eq!(booking.confirmation, "2 people at 19:00");
+    assert_eq!(
+        ToolFlow::from_response(&response),
+        ToolFlow::Done(json!({ "confirmation": "2 people at 19:00" }))
+    );
+}
+
+#[tool_function(description = "Reply with pong", collect)]
+pub async fn collected_ping() -> Result<String, IntegrationError> {
+    Ok("pong".to_string())
+}
+
+#[tokio::test]
+async fn test_registry_from_inventory() {
+    let registry = ToolRegistry::from_inventory().unwrap();
+
+    assert_eq!(registry.tool_names(), vec!["collected_ping"]);
+
+    let tool_call = genai::chat::ToolCall {
+        call_id: "ping-1".to_string(),
+        fn_name: "collected_ping".to_string(),
+        fn_arguments: json!({}),
+    };
+    let response = registry.execute_call(&tool_call).await.unwrap();
+    assert_eq!(response.content, r#""pong""#);
+}
+
+#[test]
+fn test_schema_transform_applies_to_advertised_schemas_once() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    let runs = Arc::new(AtomicUsize::new(0));
+    let counter = runs.clone();
+
+    let mut registry = ToolRegistry::new().with_schema_transform(move |mut schema| {
+        counter.fetch_add(1, Ordering::SeqCst);
+        schema.as_object_mut().unwrap().remove("$schema");
+        schema["additionalProperties"] = json!(false);
+        schema
+    });
+    registry.register_function(integration_test_tool_tool());
+
+    for _ in 0..3 {
+        let tools = registry.get_tools();
+        let schema = tools[0].schema.as_ref().unwrap();
+        assert!(schema.get("$schema").is_none());
+        assert_eq!(schema["additionalProperties"], json!(false));
+        assert!(schema["properties"].get("name").is_some());
+    }
+    assert_eq!(runs.load(Ordering::SeqCst), 1);
+
+    // Re-registering a tool invalidates its cached schema
+    registry.register_function(integration_test_tool_tool());
+    registry.get_tools();
+    assert_eq!(runs.load(Ordering::SeqCst), 2);
+
+    // The tool's own schema is untouched
+    assert!(integration_test_tool_tool().schema().get("$schema").is_some());
+}
+
+static LOOKUP_TABLE: std::sync::OnceLock<Vec<String>> = std::sync::OnceLock::new();
+
+async fn load_lookup_table() -> Result<(), IntegrationError> {
+    LOOKUP_TABLE.get_or_init(|| vec!["alpha".to_string(), "beta".to_string()]);
+    Ok(())
+}
+
+#[tool_function(description = "Look up an entry loaded during init", init = load_lookup_table, flatten)]
+pub async fn lookup_entry(index: usize) -> Result<String, IntegrationError> {
+    LOOKUP_TABLE
+        .get()
+        .and_then(|table| table.get(index).cloned())
+        .ok_or(IntegrationError::ProcessingError)
+}
+
+#[tokio::test]
+async fn test_tools_requiring_init_fail_until_initialized() {
+    let mut registry = ToolRegistry::new();
+    registry.register_function(lookup_entry_tool());
+    registry.register_function(minimal_tool_tool());
+
+    let tool_call = genai::chat::ToolCall {
+        call_id: "init-1".to_string(),
+        fn_name: "lookup_entry".to_string(),
+        fn_arguments: json!({ "index": 1 }),
+    };
+
+    let error = registry.execute_call(&tool_call).await.unwrap_err();
+    assert!(matches!(
+        error.downcast_ref::<RegistryError>(),
+        Some(RegistryError::NotInitialized { tool }) if tool == "lookup_entry"
+    ));
+    assert!(error.to_string().contains("init_all"));
+
+    registry.init_all().await.unwrap();
+    // Idempotent
+    registry.init_all().await.unwrap();
+
+    let response = registry.execute_call(&tool_call).await.unwrap();
+    assert_eq!(response.content, r#""beta""#);
+
+    // Merged registries keep their tools' initialization
+    let initialized = || async {
+        let mut registry = ToolRegistry::new();
+        registry.register_function(lookup_entry_tool());
+        registry.init_all().await.unwrap();
+        registry
+    };
+    let mut merged = ToolRegistry::new();
+    merged.register_function(lookup_entry_tool());
+    merged.merge(initialized().await);
+    assert!(merged.execute_call(&tool_call).await.is_ok());
+    let mut prefixed = ToolRegistry::new();
+    prefixed.merge_prefixed("kb_", initialized().await).unwrap();
+    let prefixed_call = genai::chat::ToolCall {
+        fn_name: "kb_lookup_entry".to_string(),
+        ..tool_call.clone()
+    };
+    assert!(prefixed.execute_call(&prefixed_call).await.is_ok());
+
+    // A replaced tool needs its own initialization
+    let mut uninitialized = ToolRegistry::new();
+    uninitialized.register_function(lookup_entry_tool());
+    registry.merge(uninitialized);
+    assert!(registry.execute_call(&tool_call).await.is_err());
+}
+
+/// A hand-written tool whose schema can never be serialized.
+struct BrokenSchemaTool;
+
+impl ToolFunction for BrokenSchemaTool {
+    type Params = IntegrationParams;
+    type Output = IntegrationResult;
+    type Error = IntegrationError;
+
+    fn name(&self) -> &'static str {
+        "broken_schema"
+    }
+
+    fn description(&self) -> &'static str {
+        "A tool with a broken schema"
+    }
+
+    fn try_schema(&self) -> Result<serde_json::Value, serde_json::Error> {
+        Err(serde::ser::Error::custom("schema cannot be represented"))
+    }
+
+    fn call(&self, _params: Self::Params) -> genai_tools::BoxFuture<'_, Result<Self::Output, Self::Error>> {
+        Box::pin(async { Err(IntegrationError::ProcessingError) })
+    }
+}
+
+#[test]
+fn test_schema_failures_are_handled_without_panicking() {
+    let mut registry = ToolRegistry::new();
+    registry.register_function(minimal_tool_tool());
+
+    match registry.try_register_function(BrokenSchemaTool) {
+        Err(RegistryError::Schema { tool, source }) => {
+            assert_eq!(tool, "broken_schema");
+            assert!(source.to_string().contains("cannot be represented"));
+        }
+        other => panic!("expected Schema error, got {:?}", other.map(|_| ())),
+    }
+    assert!(!registry.has_tool("broken_schema"));
+
+    registry.register_function(BrokenSchemaTool);
+    let tools = registry.get_tools();
+    assert_eq!(tools.len(), 1);
+    assert_eq!(tools[0].name, "minimal_tool");
+    assert!(matches!(registry.try_get_tools(), Err(RegistryError::Schema { .. })));
+}
+
+#[tokio::test]
+async fn test_merge_prefixed_namespaces_tools() {
+    let mut registry = ToolRegistry::new();
+    registry.register_function(minimal_tool_tool());
+
+    let mut other = ToolRegistry::new();
+    other.register_function(minimal_tool_tool());
+    other.register_function(integration_test_tool_tool());
+
+    registry.merge_prefixed("ext_", other).unwrap();
+
+    let mut names = registry.tool_names();
+    names.sort();
+    assert_eq!(names, vec!["ext_integration_test_tool", "ext_minimal_tool", "minimal_tool"]);
+    assert!(registry.get_tools().iter().any(|tool| tool.name == "ext_minimal_tool"));
+
+    let tool_call = genai::chat::ToolCall {
+        call_id: "prefixed-1".to_string(),
+        fn_name: "ext_integration_test_tool".to_string(),
+        fn_arguments: json!({
+            "name": "Prefixed",
+            "items": [],
+            "status": "active"
+        }),
+    };
+    let response = registry.execute_call(&tool_call).await.unwrap();
+    let result: IntegrationResult = serde_json::from_str(&response.content).unwrap();
+    assert_eq!(result.name, "Prefixed");
+
+    // Prefixed names that still collide are rejected without importing anything
+    let mut clashing = ToolRegistry::new();
+    clashing.register_function(minimal_tool_tool());
+    clashing.register_function(echo_text_tool());
+    match registry.merge_prefixed("ext_", clashing) {
+        Err(RegistryError::DuplicateTool { tool }) => assert_eq!(tool, "ext_minimal_tool"),
+        other => panic!("expected DuplicateTool error, got {:?}", other.map(|_| ())),
+    }
+    assert!(!registry.has_tool("ext_echo_text"));
+}
+
+#[tool_function(description = "Sum a series of samples", flatten)]
+pub async fn sum_samples(samples: Vec<f64>) -> Result<f64, IntegrationError> {
+    Ok(samples.iter().sum())
+}
+
+#[tokio::test]
+async fn test_execute_raw_parses_arguments_from_text() {
+    let samples: Vec<String> = (1..=1000).map(|n| n.to_string()).collect();
+    let text = format!(r#"{{"samples": [{}]}}"#, samples.join(","));
+    let arguments: Box<serde_json::value::RawValue> = serde_json::from_str(&text).unwrap();
+
+    let mut registry = ToolRegistry::new();
+    registry.register_function(sum_samples_tool());
+
+    let response = registry.execute_raw("raw-1", "sum_samples", &arguments).await.unwrap();
+    assert_eq!(response.call_id, "raw-1");
+    assert_eq!(response.content, "500500.0");
+
+    let bad: Box<serde_json::value::RawValue> = serde_json::from_str(r#"{"samples": "many"}"#).unwrap();
+    let error = registry.execute_raw("raw-2", "sum_samples", &bad).await.unwrap_err();
+    assert!(matches!(
+        error.downcast_ref::<RegistryError>(),
+        Some(RegistryError::InvalidArguments { .. })
+    ));
+
+    let validating = {
+        let mut registry = ToolRegistry::new().with_input_validation(true);
+        registry.register_function(sum_samples_tool());
+        registry
+    };
+    let error = validating.execute_raw("raw-3", "sum_samples", &bad).await.unwrap_err();
+    assert!(matches!(
+        error.downcast_ref::<RegistryError>(),
+        Some(RegistryError::InputValidation { .. })
+    ));
+}
+
+#[tokio::test]
+async fn test_execute_calls_owned() {
+    let mut registry = ToolRegistry::new();
+    registry.register_function(add_numbers_tool());
+
+    let calls: Vec<_> = (0..3)
+        .map(|n| genai::chat::ToolCall {
+            call_id: format!("owned-{}", n),
+            fn_name: "add_numbers".to_string(),
+            fn_arguments: json!({ "a": n, "b": 10 }),
+        })
+        .collect();
+
+    let single = registry.execute_call_owned(calls[0].clone()).await.unwrap();
+    assert_eq!(single.content, "10");
+
+    let responses = registry.execute_calls_owned(calls).await.unwrap();
+    let contents: Vec<_> = responses.iter().map(|r| (r.call_id.as_str(), r.content.as_str())).collect();
+    assert_eq!(contents, vec![("owned-0", "10"), ("owned-1", "11"), ("owned-2", "12")]);
+}
+
+#[derive(Deserialize, JsonSchema)]
+pub struct TransferParams {
+    /// Amount to transfer
+    pub amount: f64,
+    /// Currency to convert the amount into
+    pub target_currency: Option<String>,
+    /// Exchange rate to apply when converting
+    pub rate: Option<f64>,
+}
+
+#[tool_function(description = "Transfer funds, optionally converting currency")]
+pub async fn transfer_funds(params: TransferParams) -> Result<f64, IntegrationError> {
+    Ok(params.amount * params.rate.unwrap_or(1.0))
+}
+
+#[tokio::test]
+async fn test_add_dependent_required() {
+    let mut registry = ToolRegistry::new()
+        .with_input_validation(true)
+        .with_schema_transform(|mut schema| {
+            if schema["title"] == "TransferParams" {
+                genai_tools::schema::add_dependent_required(&mut schema, "target_currency", &["rate"]);
+                genai_tools::schema::add_dependent_required(&mut schema, "target_currency", &["rate", "amount"]);
+            }
+            schema
+        });
+    registry.register_function(transfer_funds_tool());
+
+    let schema = registry.get_tools()[0].schema.clone().unwrap();
+    assert_eq!(schema["dependentRequired"], json!({ "target_currency": ["rate", "amount"] }));
+
+    let call = |arguments| genai::chat::ToolCall {
+        call_id: "transfer-1".to_string(),
+        fn_name: "transfer_funds".to_string(),
+        fn_arguments: arguments,
+    };
+
+    let response = registry
+        .execute_call(&call(json!({ "amount": 10.0, "target_currency": "EUR", "rate": 0.5 })))
+        .await
+        .unwrap();
+    assert_eq!(response.content, "5.0");
+
+    let error = registry
+        .execute_call(&call(json!({ "amount": 10.0, "target_currency": "EUR" })))
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        error.downcast_ref::<RegistryError>(),
+        Some(RegistryError::InputValidation { .. })
+    ));
+}
+
+#[derive(Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ShippingMethod {
+    /// Collect the order in store
+    Pickup,
+    /// Deliver the order to an address
+    Delivery,
+}
+
+#[derive(Deserialize, JsonSchema)]
+pub struct ShipParams {
+    /// How the order is handed over
+    pub method: ShippingMethod,
+    /// Where to deliver the order
+    pub address: Option<String>,
+}
+
+#[tool_function(description = "Ship an order")]
+pub async fn ship_order(params: ShipParams) -> Result<String, IntegrationError> {
+    Ok(match params.method {
+        ShippingMethod::Pickup => "ready for pickup".to_string(),
+        ShippingMethod::Delivery => format!("shipping to {}", params.address.unwrap_or_default()),
+    })
+}
+
+#[tokio::test]
+async fn test_add_conditional() {
+    let mut registry = ToolRegistry::new()
+        .with_input_validation(true)
+        .with_schema_transform(|mut schema| {
+            if schema["title"] == "ShipParams" {
+                genai_tools::schema::add_conditional(&mut schema, "method", json!("delivery"), &["address"]);
+                genai_tools::schema::add_conditional(&mut schema, "method", json!("pickup"), &[]);
+            }
+            schema
+        });
+    registry.register_function(ship_order_tool());
+
+    let schema = registry.get_tools()[0].schema.clone().unwrap();
+    assert_eq!(
+        schema["if"],
+        json!({ "properties": { "method": { "const": "delivery" } }, "required": ["method"] })
+    );
+    assert_eq!(schema["then"], json!({ "required": ["address"] }));
+    assert_eq!(
+        schema["allOf"],
+        json!([{
+            "if": { "properties": { "method": { "const": "pickup" } }, "required": ["method"] },
+            "then": { "required": [] }
+        }])
+    );
+
+    let call = |arguments| genai::chat::ToolCall {
+        call_id: "ship-1".to_string(),
+        fn_name: "ship_order".to_string(),
+        fn_arguments: arguments,
+    };
+
+    let response = registry.execute_call(&call(json!({ "method": "pickup" }))).await.unwrap();
+    assert_eq!(response.content, "\"ready for pickup\"");
+    let response = registry
+        .execute_call(&call(json!({ "method": "delivery", "address": "1 Main St" })))
+        .await
+        .unwrap();
+    assert_eq!(response.content, "\"shipping to 1 Main St\"");
+
+    let error = registry
+        .execute_call(&call(json!({ "method": "delivery" })))
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        error.downcast_ref::<RegistryError>(),
+        Some(RegistryError::InputValidation { .. })
+    ));
+}
+
+#[test]
+fn test_precompute_schemas() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    let transforms = Arc::new(AtomicUsize::new(0));
+    let counter = transforms.clone();
+    let mut registry = ToolRegistry::new().with_schema_transform(move |schema| {
+        counter.fetch_add(1, Ordering::SeqCst);
+        schema
+    });
+    registry.register_function(minimal_tool_tool());
+    registry.register_function(add_numbers_tool());
+
+    assert_eq!(registry.precompute_schemas().unwrap(), 2);
+    assert_eq!(transforms.load(Ordering::SeqCst), 2);
+
+    assert_eq!(registry.precompute_schemas().unwrap(), 2);
+    registry.get_tools();
+    assert_eq!(transforms.load(Ordering::SeqCst), 2);
+
+    registry.register_function(BrokenSchemaTool);
+    assert!(matches!(registry.precompute_schemas(), Err(RegistryError::Schema { .. })));
+}
+
+#[test]
+fn test_validate_all_schemas() {
+    let mut registry = ToolRegistry::new();
+    registry.register_function(minimal_tool_tool());
+    registry.register_function(add_numbers_tool());
+    assert_eq!(registry.validate_all_schemas(), Ok(()));
+
+    registry.register_function(BrokenSchemaTool);
+    registry.register_function_with_schema(report_count_tool(), json!(["not", "a", "schema"]));
+    registry.register_function_with_schema(
+        get_weather_tool(),
+        json!({ "type": "object", "properties": { "city": { "type": "text" } } }),
+    );
+
+    let problems = registry.validate_all_schemas().unwrap_err();
+    let names: Vec<&str> = problems.iter().map(|(name, _)| name.as_str()).collect();
+    assert_eq!(names, ["broken_schema", "get_weather", "report_count"]);
+    assert!(problems[1].1.starts_with("/properties/city/type"), "{}", problems[1].1);
+    assert!(problems[2].1.contains("not a JSON object"), "{}", problems[2].1);
+}
+
+#[derive(Deserialize, JsonSchema)]
+pub struct CityLookupParams {
+    /// The city to look up
+    #[serde(rename = "city_name", alias = "town")]
+    #[schemars(rename = "city")]
+    pub city: String,
+    /// Maximum number of results
+    #[serde(default)]
+    pub limit: Option<u32>,
+}
+
+#[tool_function(description = "Look up a city")]
+pub async fn lookup_city(params: CityLookupParams) -> Result<String, IntegrationError> {
+    Ok(params.city)
+}
+
+#[test]
+fn test_check_schema_serde_consistency() {
+    let mut registry = ToolRegistry::new();
+    registry.register_function(add_numbers_tool());
+    registry.register_function(integration_test_tool_tool());
+    registry.register_function(render_report_tool());
+    assert_eq!(registry.check_schema_serde_consistency(), Ok(()));
+
+    registry.register_function(lookup_city_tool());
+    registry.register_function_with_schema(
+        report_count_tool(),
+        json!({ "type": "object", "properties": { "value": { "type": "integer" }, "total": { "type": "integer" } } }),
+    );
+
+    let problems = registry.check_schema_serde_consistency().unwrap_err();
+    let names: Vec<&str> = problems.iter().map(|(name, _)| name.as_str()).collect();
+    assert_eq!(names, ["lookup_city", "report_count"]);
+    assert!(problems[0].1.starts_with("schema property 'city' is not a field of"), "{}", problems[0].1);
+    assert!(problems[0].1.contains(r#"["city_name", "town", "limit"]"#), "{}", problems[0].1);
+    assert!(problems[1].1.contains("'total'"), "{}", problems[1].1);
+}
+
+#[derive(Deserialize, JsonSchema)]
+pub enum TemperatureUnit {
+    /// Degrees Celsius
+    #[serde(rename = "C")]
+    Celsius,
+    /// Degrees Fahrenheit
+    #[serde(rename = "F")]
+    Fahrenheit,
+}
+
+#[derive(Deserialize, JsonSchema)]
+pub struct WeatherParams {
+    /// The city name
+    pub city: String,
+    /// Temperature unit
+    pub unit: TemperatureUnit,
+}
+
+#[tool_function(name = "get_weather", description = "Get the current weather for a location")]
+pub async fn get_weather(params: WeatherParams) -> Result<String, IntegrationError> {
+    let unit = match params.unit {
+        TemperatureUnit::Celsius => "°C",
+        TemperatureUnit::Fahrenheit => "°F",
+    };
+    Ok(format!("22 {} in {}", unit, params.city))
+}
+
+#[tokio::test]
+async fn test_enum_variant_descriptions_are_advertised() {
+    let mut registry = ToolRegistry::new();
+    registry.register_function(get_weather_tool());
+
+    let schema = registry.get_tools()[0].schema.clone().unwrap();
+    assert_eq!(
+        schema["$defs"]["TemperatureUnit"]["oneOf"],
+        json!([
+            { "description": "Degrees Celsius", "type": "string", "const": "C" },
+            { "description": "Degrees Fahrenheit", "type": "string", "const": "F" }
+        ])
+    );
+
+    let tool_call = genai::chat::ToolCall {
+        call_id: "weather-1".to_string(),
+        fn_name: "get_weather".to_string(),
+        fn_arguments: json!({ "city": "Oslo", "unit": "F" }),
+    };
+    let response = registry.execute_call(&tool_call).await.unwrap();
+    assert_eq!(response.content, json!("22 °F in Oslo").to_string());
+}
+
+#[tokio::test]
+async fn test_result_envelope_wraps_successful_results() {
+    let mut registry = ToolRegistry::new()
+        .with_result_envelope(|name, result| json!({ "tool": name, "result": result }));
+    registry.register_function(add_numbers_tool());
+
+    let call = |arguments| genai::chat::ToolCall {
+        call_id: "envelope-1".to_string(),
+        fn_name: "add_numbers".to_string(),
+        fn_arguments: arguments,
+    };
+
+    let response = registry.execute_call(&call(json!({ "a": 2, "b": 3 }))).await.unwrap();
+    let content: serde_json::Value = serde_json::from_str(&response.content).unwrap();
+    assert_eq!(content, json!({ "tool": "add_numbers", "result": 5 }));
+
+    assert!(registry.execute_call(&call(json!({ "a": "two" }))).await.is_err());
+}
+
+#[derive(Serialize)]
+pub struct Connection {
+    pub host: String,
+    #[serde(rename = "Password")]
+    pub password: String,
+}
+
+#[derive(Serialize)]
+pub struct ServiceInfo {
+    pub name: String,
+    pub token: String,
+    pub connections: Vec<Connection>,
+}
+
+#[tool_function(description = "Describe a service and how to reach it", flatten)]
+pub async fn describe_service(name: String) -> Result<ServiceInfo, IntegrationError> {
+    Ok(ServiceInfo {
+        name,
+        token: "tok-123".to_string(),
+        connections: vec![Connection { host: "db.internal".to_string(), password: "hunter2".to_string() }],
+    })
+}
+
+#[tokio::test]
+async fn test_result_redactor_scrubs_nested_secrets() {
+    let call = genai::chat::ToolCall {
+        call_id: "redact-1".to_string(),
+        fn_name: "describe_service".to_string(),
+        fn_arguments: json!({ "name": "billing" }),
+    };
+
+    let policy = genai_tools::RedactionPolicy::mask(["password", "token"]);
+    let mut registry = ToolRegistry::new().with_result_redactor(move |result| policy.redact(result));
+    registry.register_function(describe_service_tool());
+    let content: serde_json::Value =
+        serde_json::from_str(&registry.execute_call(&call).await.unwrap().content).unwrap();
+    assert_eq!(
+        content,
+        json!({
+            "name": "billing",
+            "token": "[REDACTED]",
+            "connections": [{ "host": "db.internal", "Password": "[REDACTED]" }]
+        })
+    );
+
+    let policy = genai_tools::RedactionPolicy::remove(["password", "token"]);
+    let mut registry = ToolRegistry::new()
+        .with_result_redactor(move |result| policy.redact(result))
+        .with_result_envelope(|_, result| json!({ "result": result }));
+    registry.register_function(describe_service_tool());
+    let content: serde_json::Value =
+        serde_json::from_str(&registry.execute_call(&call).await.unwrap().content).unwrap();
+    assert_eq!(
+        content,
+        json!({ "result": { "name": "billing", "connections": [{ "host": "db.internal" }] } })
+    );
+}
+
+tool_set! {
+    /// Tools exercised by the tool set test.
+    pub enum MathTools { add_numbers, sum_samples }
+}
+
+mod catalog {
+    genai_tools::tool_set!(super::echo_text, super::get_weather);
+}
+
+#[tokio::test]
+async fn test_tool_set_enum() {
+    assert_eq!(MathTools::ALL, &[MathTools::AddNumbers, MathTools::SumSamples]);
+    assert_eq!(MathTools::AddNumbers.name(), "add_numbers");
+    assert_eq!(MathTools::from_name("sum_samples"), Some(MathTools::SumSamples));
+    assert_eq!(MathTools::from_name("echo_text"), None);
+    assert_eq!(MathTools::SumSamples.handler().name(), "sum_samples");
+
+    let registry = MathTools::registry();
+    assert_eq!(registry.len(), 2);
+
+    let tool_call = genai::chat::ToolCall {
+        call_id: "set-1".to_string(),
+        fn_name: "add_numbers".to_string(),
+        fn_arguments: json!({ "a": 1, "b": 2 }),
+    };
+    let description = match MathTools::from_name(&tool_call.fn_name).unwrap() {
+        MathTools::AddNumbers => "addition",
+        MathTools::SumSamples => "summation",
+    };
+    assert_eq!(description, "addition");
+    assert_eq!(registry.execute_call(&tool_call).await.unwrap().content, "3");
+
+    let mut registry = ToolRegistry::new();
+    catalog::ToolSet::register_all(&mut registry);
+    let mut names = registry.tool_names();
+    names.sort();
+    assert_eq!(names, vec!["echo_text", "get_weather"]);
+    assert_eq!(catalog::ToolSet::from_name("echo_text"), Some(catalog::ToolSet::EchoText));
+    assert_eq!(catalog::ToolSet::GetWeather.name(), "get_weather");
+}
+
+#[derive(Deserialize, Serialize, JsonSchema)]
+pub struct MessageParams {
+    /// Where to deliver the message
+    pub channel: String,
+    /// The message text
+    pub text: String,
+}
+
+#[tool_function(description = "Send a message")]
+pub async fn send_message(params: MessageParams) -> Result<MessageParams, IntegrationError> {
+    Ok(params)
+}
+
+#[tokio::test]
+async fn test_register_with_defaults() {
+    let mut registry = ToolRegistry::new().with_input_validation(true);
+    registry
+        .register_with_defaults(send_message_tool(), "send_to_slack", json!({ "channel": "slack" }))
+        .register_with_defaults(send_message_tool(), "send_to_email", json!({ "channel": "email" }));
+
+    let tools = registry.get_tools();
+    let slack = tools.iter().find(|tool| tool.name == "send_to_slack").unwrap();
+    let schema = slack.schema.as_ref().unwrap();
+    assert!(schema["properties"].get("channel").is_none());
+    assert_eq!(schema["required"], json!(["text"]));
+    assert_eq!(slack.description.as_deref(), Some("Send a message"));
+
+    let call = |name: &str, arguments| genai::chat::ToolCall {
+        call_id: "bound-1".to_string(),
+        fn_name: name.to_string(),
+        fn_arguments: arguments,
+    };
+
+    let response = registry.execute_call(&call("send_to_email", json!({ "text": "hi" }))).await.unwrap();
+    let sent: serde_json::Value = serde_json::from_str(&response.content).unwrap();
+    assert_eq!(sent, json!({ "channel": "email", "text": "hi" }));
+
+    // Bound fields can't be overridden by the model
+    let response = registry
+        .execute_call(&call("send_to_slack", json!({ "text": "hi", "channel": "email" })))
+        .await
+        .unwrap();
+    let sent: serde_json::Value = serde_json::from_str(&response.content).unwrap();
+    assert_eq!(sent["channel"], json!("slack"));
+}
+
+#[test]
+fn test_apply_to_request_keeps_existing_tools() {
+    use genai::chat::{ChatRequest, Tool};
+
+    let mut registry = ToolRegistry::new();
+    registry.register_function(add_numbers_tool());
+
+    let request = ChatRequest::from_user("What is 2 + 3?").append_tool(Tool::new("existing"));
+    let request = registry.apply_to_request(request);
+    let names: Vec<_> = request.tools.unwrap().into_iter().map(|tool| tool.name).collect();
+    assert_eq!(names, vec!["existing", "add_numbers"]);
+}
+
+#[tokio::test]
+async fn test_run_until_done_respects_round_limit() {
+    let mut registry = ToolRegistry::new();
+    registry.register_function(add_numbers_tool());
+
+    let client = genai::Client::default();
+    let request = genai::chat::ChatRequest::from_user("What is 2 + 3?");
+    let error = registry.run_until_done(&client, "gpt-4", request, 0).await.unwrap_err();
+    match error.downcast_ref::<RegistryError>() {
+        Some(RegistryError::RoundLimit { max_rounds }) => assert_eq!(*max_rounds, 0),
+        other => panic!("expected RoundLimit error, got {:?}", other),
+    }
+}
+
+#[derive(Deserialize, JsonSchema)]
+pub struct Pagination {
+    /// Page number, starting at 1
+    pub page: u32,
+    /// Results per page
+    pub per_page: Option<u32>,
+}
+
+#[derive(Deserialize, JsonSchema)]
+pub struct SearchParams {
+    /// Search query
+    pub query: String,
+    #[serde(flatten)]
+    pub pagination: Pagination,
+}
+
+#[tool_function(description = "Search with pagination")]
+pub async fn paged_search(params: SearchParams) -> Result<String, IntegrationError> {
+    Ok(format!(
+        "{} p{} x{}",
+        params.query,
+        params.pagination.page,
+        params.pagination.per_page.unwrap_or(10)
+    ))
+}
+
+#[tokio::test]
+async fn test_flattened_params_schema() {
+    let mut registry = ToolRegistry::new().with_input_validation(true);
+    registry.register_function(paged_search_tool());
+
+    // Fields of the flattened struct are merged into the top level, including `required`
+    let schema = registry.get_tools()[0].schema.clone().unwrap();
+    let properties = schema["properties"].as_object().unwrap();
+    let mut names: Vec<_> = properties.keys().map(String::as_str).collect();
+    names.sort();
+    assert_eq!(names, vec!["page", "per_page", "query"]);
+    assert_eq!(schema["properties"]["page"]["description"], json!("Page number, starting at 1"));
+    let required: Vec<&str> = schema["required"].as_array().unwrap().iter().map(|v| v.as_str().unwrap()).collect();
+    assert_eq!(required, vec!["query", "page"]);
+
+    let call = |arguments| genai::chat::ToolCall {
+        call_id: "search-1".to_string(),
+        fn_name: "paged_search".to_string(),
+        fn_arguments: arguments,
+    };
+    let response = registry.execute_call(&call(json!({ "query": "rust", "page": 2 }))).await.unwrap();
+    assert_eq!(response.content, json!("rust p2 x10").to_string());
+
+    let error = registry.execute_call(&call(json!({ "query": "rust" }))).await.unwrap_err();
+    assert!(matches!(
+        error.downcast_ref::<RegistryError>(),
+        Some(RegistryError::InputValidation { .. })
+    ));
+}
+
+#[tokio::test]
+async fn test_argument_errors_name_the_params_type() {
+    assert!(get_weather_tool().params_type_name().ends_with("WeatherParams"));
+
+    let mut registry = ToolRegistry::new();
+    registry.register_function(get_weather_tool());
+
+    let tool_call = genai::chat::ToolCall {
+        call_id: "weather-2".to_string(),
+        fn_name: "get_weather".to_string(),
+        fn_arguments: json!({ "city": "Oslo", "unit": "K" }),
+    };
+    let error = registry.execute_call(&tool_call).await.unwrap_err();
+    assert!(error.to_string().contains("WeatherParams"), "{}", error);
+    match error.downcast_ref::<RegistryError>() {
+        Some(RegistryError::InvalidArguments { tool, params_type, .. }) => {
+            assert_eq!(tool, "get_weather");
+            assert_eq!(*params_type, get_weather_tool().params_type_name());
+        }
+        other => panic!("expected InvalidArguments error, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn test_execute_call_as_genai_reports_errors_as_content() {
+    let mut registry = ToolRegistry::new();
+    registry.register_function(add_numbers_tool());
+
+    let call = |name: &str, arguments| genai::chat::ToolCall {
+        call_id: "genai-1".to_string(),
+        fn_name: name.to_string(),
+        fn_arguments: arguments,
+    };
+
+    let response = registry.execute_call_as_genai(&call("add_numbers", json!({ "a": 1, "b": 1 }))).await;
+    assert_eq!(response.content, "2");
+
+    let response = registry.execute_call_as_genai(&call("add_numbers", json!({ "a": "one" }))).await;
+    assert_eq!(response.call_id, "genai-1");
+    let content: serde_json::Value = serde_json::from_str(&response.content).unwrap();
+    assert!(content["error"]["message"]
+        .as_str()
+        .unwrap()
+        .contains("Invalid arguments for tool 'add_numbers'"));
+
+    let response = registry.execute_call_as_genai(&call("missing", json!({}))).await;
+    let content: serde_json::Value = serde_json::from_str(&response.content).unwrap();
+    assert_eq!(content["error"]["message"], json!("Tool 'missing' not found in registry"));
+}
+
+#[tokio::test]
+async fn test_error_envelope_shape() {
+    let mut registry = ToolRegistry::new();
+    registry.register_function(add_numbers_tool());
+    registry.register_function(integration_test_tool_tool());
+
+    let call = |name: &str, arguments| genai::chat::ToolCall {
+        call_id: "envelope-1".to_string(),
+        fn_name: name.to_string(),
+        fn_arguments: arguments,
+    };
+    let envelope = |response: genai::chat::ToolResponse| -> serde_json::Value {
+        serde_json::from_str(&response.content).unwrap()
+    };
+
+    let content = envelope(registry.execute_call_as_genai(&call("add_numbers", json!({ "a": "one" }))).await);
+    let error = content["error"].as_object().unwrap();
+    let mut keys: Vec<_> = error.keys().map(String::as_str).collect();
+    keys.sort_unstable();
+    assert_eq!(keys, ["message", "recoverable", "type"]);
+    assert_eq!(error["type"], "invalid_arguments");
+    assert_eq!(error["recoverable"], true);
+
+    let content = envelope(registry.execute_call_as_genai(&call("missing", json!({}))).await);
+    assert_eq!(
+        content,
+        json!({
+            "error": {
+                "type": "tool_not_found",
+                "message": "Tool 'missing' not found in registry",
+                "recoverable": true
+            }
+        })
+    );
+
+    let arguments = json!({ "name": "", "items": [], "status": "active" });
+    let content = envelope(registry.execute_call_as_genai(&call("integration_test_tool", arguments)).await);
+    assert_eq!(
+        content,
+        json!({
+            "error": {
+                "type": "tool_error",
+                "message": "Validation failed: Name cannot be empty",
+                "recoverable": true
+            }
+        })
+    );
+
+    let payload = genai_tools::ToolErrorPayload::from_error(&RegistryError::NotInitialized {
+        tool: "db".to_string(),
+    });
+    assert_eq!(payload.kind, "not_initialized");
+    assert!(!payload.recoverable);
+}
+
+#[tool_function(description = "Find a user by id or by name", one_of("by_id", "by_name"))]
+pub async fn find_user(
+    /// The user's id
+    by_id: Option<u64>,
+    /// The user's display name
+    by_name: Option<String>,
+) -> Result<String, IntegrationError> {
+    Ok(match (by_id, by_name) {
+        (Some(id), _) => format!("user #{}", id),
+        (_, Some(name)) => format!("user {}", name),
+        _ => "nobody".to_string(),
+    })
+}
+
+#[tool_function(description = "Find an account by id or by handle", one_of("id", "handle"))]
+pub async fn find_account(
+    #[serde(rename = "id")] account_id: Option<u64>,
+    #[serde(rename = "handle")] account_handle: Option<String>,
+) -> Result<String, IntegrationError> {
+    Ok(format!("{:?} {:?}", account_id, account_handle))
+}
+
+#[derive(Deserialize, JsonSchema)]
+pub struct LookupParams {
+    pub key: Option<String>,
+}
+
+#[tool_function(description = "Lookup with a misspelled one_of group", one_of("key", "kee"))]
+pub async fn misspelled_lookup(params: LookupParams) -> Result<Option<String>, IntegrationError> {
+    Ok(params.key)
+}
+
+#[tokio::test]
+async fn test_one_of_groups() {
+    let mut registry = ToolRegistry::new().with_input_validation(true);
+    registry.register_function(find_user_tool());
+
+    let schema = registry.get_tools()[0].schema.clone().unwrap();
+    assert_eq!(schema["oneOf"], json!([{ "required": ["by_id"] }, { "required": ["by_name"] }]));
+
+    let call = |arguments| genai::chat::ToolCall {
+        call_id: "user-1".to_string(),
+        fn_name: "find_user".to_string(),
+        fn_arguments: arguments,
+    };
+
+    let response = registry.execute_call(&call(json!({ "by_name": "ada" }))).await.unwrap();
+    assert_eq!(response.content, json!("user ada").to_string());
+
+    for arguments in [json!({ "by_id": 1, "by_name": "ada" }), json!({})] {
+        let error = registry.execute_call(&call(arguments)).await.unwrap_err();
+        assert!(matches!(
+            error.downcast_ref::<RegistryError>(),
+            Some(RegistryError::InputValidation { .. })
+        ));
+    }
+
+    // Groups name the renamed arguments
+    let schema = find_account_tool().try_schema().unwrap();
+    assert_eq!(schema["oneOf"], json!([{ "required": ["id"] }, { "required": ["handle"] }]));
+
+    // Unknown field names in a non-flattened tool are caught when the schema is generated
+    match ToolRegistry::new().try_register_function(misspelled_lookup_tool()) {
+        Err(RegistryError::Schema { source, .. }) => assert!(source.to_string().contains("'kee'")),
+        other => panic!("expected Schema error, got {:?}", other.map(|_| ())),
+    }
+}
+
+#[test]
+fn test_contains_all_and_missing() {
+    let mut registry = ToolRegistry::new();
+    registry.register_function(add_numbers_tool());
+    registry.register_function(echo_text_tool());
+
+    assert!(registry.contains_all(&["add_numbers", "echo_text"]));
+    assert!(registry.contains_all(&[]));
+    assert!(!registry.contains_all(&["add_numbers", "get_weather"]));
+    assert_eq!(registry.missing(&["get_weather", "add_numbers", "find_user"]), vec!["get_weather", "find_user"]);
+    assert!(registry.missing(&["echo_text"]).is_empty());
+}
+
+fn sample_message() -> MessageParams {
+    MessageParams {
+        channel: "slack".to_string(),
+        text: "Deploy finished".to_string(),
+    }
+}
+
+#[tool_function(description = "Send a message, with an example", example_fn = sample_message)]
+pub async fn send_example_message(params: MessageParams) -> Result<MessageParams, IntegrationError> {
+    Ok(params)
+}
+
+fn sample_sum() -> AddTwoParams {
+    AddTwoParams { left: 2, right: 3 }
+}
+
+#[tool_function(description = "Add two numbers, with an example", example_fn = sample_sum)]
+pub async fn add_two(left: i64, right: i64) -> Result<i64, IntegrationError> {
+    Ok(left + right)
+}
+
+#[test]
+fn test_example_fn_adds_schema_examples() {
+    let schema = send_example_message_tool().schema();
+    assert_eq!(schema["examples"], json!([{ "channel": "slack", "text": "Deploy finished" }]));
+
+    let schema = add_two_tool().schema();
+    assert_eq!(schema["examples"], json!([{ "left": 2, "right": 3 }]));
+}
+
+#[test]
+fn test_tool_examples() {
+    use genai_tools::ToolHandler;
+
+    let message = json!({ "channel": "slack", "text": "Deploy finished" });
+    assert_eq!(ToolHandler::examples(&send_example_message_tool()), vec![message.clone()]);
+    assert!(ToolHandler::examples(&add_numbers_tool()).is_empty());
+
+    let mut registry = ToolRegistry::new().with_tool_examples(true);
+    registry
+        .register_function(send_example_message_tool())
+        .register_with_defaults(send_example_message_tool(), "send_to_slack", json!({ "channel": "slack" }))
+        .register_function(add_numbers_tool());
+
+    let definitions = registry.export_definitions().unwrap();
+    assert!(definitions[0].examples.is_empty());
+    assert_eq!(definitions[1].examples, vec![message.clone()]);
+    // Bound fields are dropped from the examples like from the schema
+    assert_eq!(definitions[2].examples, [json!({ "text": "Deploy finished" })]);
+
+    let tools = registry.get_tools();
+    let tool = tools.iter().find(|tool| tool.name == "send_example_message").unwrap();
+    assert_eq!(tool.config, Some(json!({ "input_examples": [message] })));
+    let tool = tools.iter().find(|tool| tool.name == "add_numbers").unwrap();
+    assert_eq!(tool.config, None);
+
+    let mut registry = ToolRegistry::new();
+    registry.register_function(send_example_message_tool());
+    assert_eq!(registry.get_tools()[0].config, None);
+}
+
+#[derive(Serialize, Deserialize, JsonSchema)]
+pub struct StaleExampleParams {
+    #[serde(skip_serializing)]
+    pub query: String,
+}
+
+fn stale_example() -> StaleExampleParams {
+    StaleExampleParams { query: "rust".to_string() }
+}
+
+#[tool_function(description = "Search with an example that can't round-trip", example_fn = stale_example)]
+pub async fn search_stale(params: StaleExampleParams) -> Result<String, IntegrationError> {
+    Ok(params.query)
+}
+
+#[test]
+#[should_panic(expected = "does not deserialize into")]
+fn test_tool_examples_must_deserialize() {
+    ToolRegistry::new().register_function(search_stale_tool());
+}
+
+#[test]
+fn test_with_capacity() {
+    let mut registry = ToolRegistry::with_capacity(64);
+    assert!(registry.is_empty());
+
+    registry.register_function(add_numbers_tool());
+    assert_eq!(registry.len(), 1);
+    assert_eq!(registry.get_tools()[0].name, "add_numbers");
+}
+
+#[tool_function(description = "Render a report", cost = "expensive", flatten)]
+pub async fn render_report(title: String) -> Result<String, IntegrationError> {
+    Ok(title)
+}
+
+#[tool_function(description = "Query the warehouse", cost = "1500ms", flatten)]
+pub async fn query_warehouse(sql: String) -> Result<String, IntegrationError> {
+    Ok(sql)
+}
+
+#[tool_function(description = "Format a report", cost = "2s", flatten)]
+pub async fn format_report(text: String) -> Result<String, IntegrationError> {
+    Ok(text)
+}
+
+#[test]
+fn test_cost_hints() {
+    use genai_tools::{CostHint, ToolHandler};
+    use std::time::Duration;
+
+    assert_eq!(ToolHandler::cost_hint(&render_report_tool()), CostHint::Expensive);
+    assert_eq!(ToolHandler::cost_hint(&query_warehouse_tool()), CostHint::Estimated(Duration::from_millis(1500)));
+    assert_eq!(ToolHandler::cost_hint(&add_numbers_tool()), CostHint::Unknown);
+
+    let mut registry = ToolRegistry::new();
+    registry.register_pipeline(
+        "warehouse_report",
+        "Query the warehouse and format the result",
+        vec![Box::new(query_warehouse_tool()), Box::new(format_report_tool())],
+    );
+    let (_, pipeline) = registry.iter().next().unwrap();
+    assert_eq!(pipeline.cost_hint(), CostHint::Estimated(Duration::from_millis(3500)));
+}
+
+#[tool_function(description = "Get the weather forecast", meta(icon = "cloud", group = "weather"), flatten)]
+pub async fn forecast(city: String) -> Result<String, IntegrationError> {
+    Ok(city)
+}
+
+#[test]
+fn test_tool_metadata() {
+    use genai_tools::ToolHandler;
+    use std::collections::{BTreeMap, HashMap};
+
+    let expected = HashMap::from([
+        ("icon".to_string(), "cloud".to_string()),
+        ("group".to_string(), "weather".to_string()),
+    ]);
+    assert_eq!(ToolHandler::metadata(&forecast_tool()), expected);
+    assert!(ToolHandler::metadata(&add_numbers_tool()).is_empty());
+
+    // Wrappers keep the metadata
+    let mut weather = ToolRegistry::new();
+    weather.register_function(forecast_tool());
+    let mut registry = ToolRegistry::new();
+    registry.merge_prefixed("weather_", weather).unwrap();
+    registry.register_function(add_numbers_tool());
+    let (_, renamed) = registry.iter().find(|(name, _)| *name == "weather_forecast").unwrap();
+    assert_eq!(renamed.metadata(), expected);
+
+    let definitions = registry.export_definitions().unwrap();
+    assert!(definitions[0].metadata.is_empty());
+    assert_eq!(
+        definitions[1].metadata,
+        BTreeMap::from([
+            ("group".to_string(), "weather".to_string()),
+            ("icon".to_string(), "cloud".to_string()),
+        ])
+    );
+    let manifest = serde_json::to_value(&definitions).unwrap();
+    assert!(manifest[0].get("metadata").is_none());
+    assert_eq!(manifest[1]["metadata"], json!({ "group": "weather", "icon": "cloud" }));
+}
+
+#[tool_function(description = "Process items, reporting progress", progress, flatten)]
+pub async fn process_items(
+    count: u32,
+    progress: genai_tools::ProgressReporter,
+) -> Result<u32, IntegrationError> {
+    for done in 1..=count {
+        progress.report(100.0 * done as f32 / count as f32, format!("Processed {} of {}", done, count));
+    }
+    Ok(count)
+}
+
+#[tokio::test]
+async fn test_execute_call_with_progress() {
+    use futures::StreamExt;
+
+    let mut registry = ToolRegistry::new();
+    registry.register_function(process_items_tool());
+    registry.register_function(add_numbers_tool());
+
+    let call = |name: &str, arguments| genai::chat::ToolCall {
+        call_id: "progress-1".to_string(),
+        fn_name: name.to_string(),
+        fn_arguments: arguments,
+    };
+
+    let (tx, rx) = futures::channel::mpsc::channel(1);
+    let response = registry
+        .execute_call_with_progress(&call("process_items", json!({ "count": 4 })), tx)
+        .await
+        .unwrap();
+    assert_eq!(response.content, "4");
+    let updates: Vec<_> = rx.collect().await;
+    assert_eq!(updates.len(), 4);
+    assert_eq!(updates[1].percent, 50.0);
+    assert_eq!(updates[3].message, "Processed 4 of 4");
+
+    // Tools that don't report progress never send; plain calls ignore the reporter
+    let (tx, rx) = futures::channel::mpsc::channel(1);
+    registry
+        .execute_call_with_progress(&call("add_numbers", json!({ "a": 1, "b": 2 })), tx)
+        .await
+        .unwrap();
+    assert!(rx.collect::<Vec<_>>().await.is_empty());
+    assert_eq!(registry.execute_call(&call("process_items", json!({ "count": 2 }))).await.unwrap().content, "2");
+}
+
+#[derive(Deserialize, JsonSchema)]
+pub struct AccountParams {
+    /// Server-assigned account id
+    #[schemars(extend("readOnly" = true))]
+    pub id: Option<u64>,
+    /// The account password
+    #[schemars(extend("writeOnly" = true))]
+    pub password: String,
+    /// Display name
+    pub name: String,
+}
+
+#[tool_function(description = "Update an account")]
+pub async fn update_account(params: AccountParams) -> Result<String, IntegrationError> {
+    Ok(params.name)
+}
+
+#[test]
+fn test_read_only_and_write_only_annotations() {
+    let schema = update_account_tool().schema();
+    assert_eq!(schema["properties"]["id"]["readOnly"], json!(true));
+    assert_eq!(schema["properties"]["password"]["writeOnly"], json!(true));
+    assert!(schema["properties"]["name"].get("readOnly").is_none());
+
+    let mut schema = add_numbers_tool().schema();
+    genai_tools::schema::mark_read_only(&mut schema, &["a", "missing"]);
+    genai_tools::schema::mark_write_only(&mut schema, &["b"]);
+    assert_eq!(schema["properties"]["a"]["readOnly"], json!(true));
+    assert_eq!(schema["properties"]["b"]["writeOnly"], json!(true));
+    assert!(schema["properties"].get("missing").is_none());
+}
+
+#[tool_function(description = "Report the time in a city", register_fn = "clock", flatten)]
+pub async fn get_city_time(city: String) -> Result<String, IntegrationError> {
+    Ok(format!("12:00 in {}", city))
+}
+
+tool_set! {
+    pub enum ClockTools { get_city_time }
+}
+
+#[tokio::test]
+async fn test_register_fn_overrides_constructor_name() {
+    let mut registry = ToolRegistry::new();
+    registry.register_function(clock());
+    assert!(registry.has_tool("get_city_time"));
+    assert_eq!(ClockTools::registry().tool_names(), vec!["get_city_time"]);
+
+    let tool_call = genai::chat::ToolCall {
+        call_id: "clock-1".to_string(),
+        fn_name: "get_city_time".to_string(),
+        fn_arguments: json!({ "city": "Lima" }),
+    };
+    let response = registry.execute_call(&tool_call).await.unwrap();
+    assert_eq!(response.content, json!("12:00 in Lima").to_string());
+}
+
+#[tokio::test]
+async fn test_max_result_chars_truncates_results() {
+    let mut registry = ToolRegistry::new().with_max_result_chars(80);
+    registry.register_function(send_message_tool());
+    registry.register_function(sum_samples_tool());
+    registry.register_function(process_items_tool());
+
+    let call = |name: &str, arguments| genai::chat::ToolCall {
+        call_id: "long-1".to_string(),
+        fn_name: name.to_string(),
+        fn_arguments: arguments,
+    };
+
+    // Long strings are shortened and the result stays valid JSON
+    let text = "lorem ipsum ".repeat(50);
+    let response = registry
+        .execute_call(&call("send_message", json!({ "channel": "email", "text": text })))
+        .await
+        .unwrap();
+    assert!(response.content.chars().count() <= 80, "{}", response.content);
+    let result: serde_json::Value = serde_json::from_str(&response.content).unwrap();
+    assert_eq!(result["_truncated"], json!(true));
+    assert_eq!(result["channel"], json!("email"));
+    assert!(result["text"].as_str().unwrap().ends_with("...[truncated]"));
+
+    // Small results are untouched
+    let response = registry.execute_call(&call("process_items", json!({ "count": 3 }))).await.unwrap();
+    assert_eq!(response.content, "3");
+
+    // Without strings to shorten, the text itself is cut
+    let mut registry = ToolRegistry::new().with_max_result_chars(40);
+    registry.register_function(echo_numbers_tool());
+    let response = registry.execute_call(&call("echo_numbers", json!({ "count": 100 }))).await.unwrap();
+    assert!(response.content.chars().count() <= 40, "{}", response.content);
+    assert!(response.content.starts_with("[0,1,2"));
+    assert!(response.content.ends_with(" chars]"));
+
+    // Even a limit shorter than the marker is respected
+    let mut registry = ToolRegistry::new().with_max_result_chars(5);
+    registry.register_function(echo_numbers_tool());
+    let response = registry.execute_call(&call("echo_numbers", json!({ "count": 100 }))).await.unwrap();
+    assert_eq!(response.content, "...[t");
+}
+
+#[tool_function(description = "List the numbers below count", flatten)]
+pub async fn echo_numbers(count: u32) -> Result<Vec<u32>, IntegrationError> {
+    Ok((0..count).collect())
+}
+
+#[tokio::test]
+async fn test_register_handler_and_shared_handler() {
+    use genai_tools::ToolHandler;
+    use std::sync::Arc;
+
+    let plugin_tools: Vec<Box<dyn ToolHandler>> = vec![Box::new(add_numbers_tool()), Box::new(echo_text_tool())];
+    let shared: Arc<dyn ToolHandler> = Arc::new(find_user_tool());
+
+    let mut registry = ToolRegistry::new().with_input_validation(true);
+    for tool in plugin_tools {
+        registry.register_handler(tool);
+    }
+    registry.register_shared_handler(shared.clone());
+
+    let mut names = registry.tool_names();
+    names.sort();
+    assert_eq!(names, vec!["add_numbers", "echo_text", "find_user"]);
+    assert_eq!(registry.get_tools().len(), 3);
+
+    let call = |name: &str, arguments| genai::chat::ToolCall {
+        call_id: "plugin-1".to_string(),
+        fn_name: name.to_string(),
+        fn_arguments: arguments,
+    };
+    assert_eq!(registry.execute_call(&call("add_numbers", json!({ "a": 4, "b": 4 }))).await.unwrap().content, "8");
+
+    // Shared handlers keep their schema constraints and stay usable by their owner
+    assert!(registry.execute_call(&call("find_user", json!({}))).await.is_err());
+    let response = registry.execute_call(&call("find_user", json!({ "by_id": 7 }))).await.unwrap();
+    assert_eq!(response.content, json!("user #7").to_string());
+    assert_eq!(shared.call_json(json!({ "by_id": 8 })).await.unwrap(), json!("user #8"));
+}
+
+#[test]
+fn test_canonical_json_ignores_key_order() {
+    use genai_tools::schema::canonical_json;
+
+    let a: serde_json::Value = serde_json::from_str(r#"{"b": [1, {"z": null, "y": "é\n"}], "a": {"d": 1.5, "c": true}}"#).unwrap();
+    let b: serde_json::Value = serde_json::from_str(r#"{"a": {"c": true, "d": 1.5}, "b": [1, {"y": "é\n", "z": null}]}"#).unwrap();
+
+    assert_eq!(canonical_json(&a), canonical_json(&b));
+    assert_eq!(canonical_json(&a), r#"{"a":{"c":true,"d":1.5},"b":[1,{"y":"é\n","z":null}]}"#);
+    assert_eq!(serde_json::from_str::<serde_json::Value>(&canonical_json(&a)).unwrap(), a);
+
+    // Array order is significant
+    assert_ne!(canonical_json(&json!([1, 2])), canonical_json(&json!([2, 1])));
+}
+
+#[tokio::test]
+async fn test_wrap_tool_middleware_order_and_short_circuit() {
+    use std::sync::{Arc, Mutex};
+
+    let mut registry = ToolRegistry::new();
+    registry.register_function(add_numbers_tool());
+    registry.register_function(echo_text_tool());
+
+    let log = Arc::new(Mutex::new(Vec::new()));
+    for label in ["outer", "inner"] {
+        let log = log.clone();
+        registry
+            .wrap_tool("add_numbers", move |invocation, next| {
+                let log = log.clone();
+                Box::pin(async move {
+                    log.lock().unwrap().push(format!("{} before", label));
+                    let result = next.run(invocation).await;
+                    log.lock().unwrap().push(format!("{} after", label));
+                    result
+                })
+            })
+            .unwrap();
+    }
+
+    let call = |name: &str, arguments| genai::chat::ToolCall {
+        call_id: "mw".to_string(),
+        fn_name: name.to_string(),
+        fn_arguments: arguments,
+    };
+    let response = registry.execute_call(&call("add_numbers", json!({ "a": 1, "b": 2 }))).await.unwrap();
+    assert_eq!(response.content, "3");
+    assert_eq!(
+        *log.lock().unwrap(),
+        ["outer before", "inner before", "inner after", "outer after"]
+    );
+
+    // Other tools are unaffected
+    log.lock().unwrap().clear();
+    let response = registry.execute_call(&call("echo_text", json!({ "text": "hi" }))).await.unwrap();
+    assert_eq!(response.content, "\"hi\"");
+    assert!(log.lock().unwrap().is_empty());
+
+    // A middleware can rewrite arguments or answer without reaching the tool
+    registry
+        .wrap_tool("echo_text", |mut invocation, next| {
+            Box::pin(async move {
+                if invocation.arguments["text"] == "blocked" {
+                    return Ok(json!("refused"));
+                }
+                invocation.arguments["text"] = json!("rewritten");
+                next.run(invocation).await
+            })
+        })
+        .unwrap();
+    let response = registry.execute_call(&call("echo_text", json!({ "text": "blocked" }))).await.unwrap();
+    assert_eq!(response.content, "\"refused\"");
+    let response = registry.execute_call(&call("echo_text", json!({ "text": "hi" }))).await.unwrap();
+    assert_eq!(response.content, "\"rewritten\"");
+
+    assert!(matches!(
+        registry.wrap_tool("missing", |invocation, next| next.run(invocation)),
+        Err(RegistryError::ToolNotFound { .. })
+    ));
+}
+
+#[tokio::test]
+async fn test_execute_call_result_decodes_results_and_envelopes() {
+    use genai_tools::ToolCallError;
+
+    let mut registry = ToolRegistry::new();
+    registry.register_function(add_numbers_tool());
+
+    let call = |name: &str, arguments| genai::chat::ToolCall {
+        call_id: "typed".to_string(),
+        fn_name: name.to_string(),
+        fn_arguments: arguments,
+    };
+
+    let sum: i64 = registry
+        .execute_call_result(&call("add_numbers", json!({ "a": 2, "b": 3 })))
+        .await
+        .unwrap();
+    assert_eq!(sum, 5);
+
+    match registry.execute_call_result::<i64>(&call("missing", json!({}))).await {
+        Err(ToolCallError::Tool(payload)) => {
+            assert_eq!(payload.kind, "tool_not_found");
+            assert!(payload.recoverable);
+        }
+        other => panic!("expected an error envelope, got {:?}", other),
+    }
+
+    let error = registry
+        .execute_call_result::<String>(&call("add_numbers", json!({ "a": 2 })))
+        .await
+        .unwrap_err();
+    assert!(matches!(error, ToolCallError::Decode { ref tool, .. } if tool == "add_numbers"));
+}
+
+#[tool_function(description = "Parse an integer")]
+pub async fn parse_integer(
+    /// The text to parse
+    text: String,
+    /// The radix to parse in
+    radix: u32,
+) -> Result<i64, Box<dyn std::error::Error + Send + Sync>> {
+    Ok(i64::from_str_radix(&text, radix)?)
+}
+
+#[tokio::test]
+async fn test_boxed_error_type() {
+    let tool = parse_integer_tool();
+    let params = ParseIntegerParams { text: "ff".to_string(), radix: 16 };
+    assert_eq!(tool.call(params).await.unwrap(), 255);
+
+    let mut registry = ToolRegistry::new();
+    registry.register_function(parse_integer_tool());
+    let call = genai::chat::ToolCall {
+        call_id: "parse".to_string(),
+        fn_name: "parse_integer".to_string(),
+        fn_arguments: json!({ "text": "zz", "radix": 10 }),
+    };
+    let error = registry.execute_call(&call).await.unwrap_err();
+    assert_eq!(error.to_string(), "invalid digit found in string");
+    assert!(error.downcast_ref::<std::num::ParseIntError>().is_some());
+}
+
+#[derive(Debug, Deserialize, JsonSchema, PartialEq)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum RobotCommand {
+    /// Move to a position
+    Move {
+        /// Horizontal position
+        x: i32,
+        /// Vertical position
+        y: i32,
+    },
+    /// Say something out loud
+    Say {
+        /// What to say
+        text: String,
+    },
+    /// Stop all motion
+    Stop,
+}
+
+#[tool_function(description = "Send a command to the robot")]
+pub async fn command_robot(command: RobotCommand) -> Result<String, IntegrationError> {
+    Ok(format!("{:?}", command))
+}
+
+#[tokio::test]
+async fn test_internally_tagged_enum_params() {
+    let schema = command_robot_tool().schema();
+    let variants = schema["oneOf"].as_array().unwrap();
+    let tags: Vec<_> = variants.iter().map(|variant| &variant["properties"]["action"]["const"]).collect();
+    assert_eq!(tags, [&json!("move"), &json!("say"), &json!("stop")]);
+    for variant in variants {
+        assert!(variant["required"].as_array().unwrap().contains(&json!("action")));
+    }
+    assert_eq!(variants[0]["properties"]["x"]["description"], json!("Horizontal position"));
+
+    let mut registry = ToolRegistry::new().with_input_validation(true);
+    registry.register_function(command_robot_tool());
+    let call = |arguments| genai::chat::ToolCall {
+        call_id: "robot".to_string(),
+        fn_name: "command_robot".to_string(),
+        fn_arguments: arguments,
+    };
+
+    let response = registry
+        .execute_call(&call(json!({ "action": "move", "x": 1, "y": 2 })))
+        .await
+        .unwrap();
+    assert_eq!(response.content, json!("Move { x: 1, y: 2 }").to_string());
+    let response = registry.execute_call(&call(json!({ "action": "stop" }))).await.unwrap();
+    assert_eq!(response.content, json!("Stop").to_string());
+
+    // The tag is required and must name a variant
+    for arguments in [json!({ "x": 1, "y": 2 }), json!({ "action": "jump" })] {
+        let error = registry.execute_call(&call(arguments)).await.unwrap_err();
+        assert!(matches!(
+            error.downcast_ref::<RegistryError>(),
+            Some(RegistryError::InputValidation { .. })
+        ));
+    }
+    let result = command_robot_tool().call_json(json!({ "action": "say", "text": "hi" })).await.unwrap();
+    assert_eq!(result, json!("Say { text: \"hi\" }"));
+}
+
+#[test]
+fn test_snapshot_schemas_is_deterministic() {
+    let build = |reverse: bool| {
+        let mut registry = ToolRegistry::new().with_schema_transform(|mut schema| {
+            schema["x-added"] = json!({ "z": 1, "a": 2 });
+            schema
+        });
+        if reverse {
+            registry.register_function(ship_order_tool()).register_function(add_numbers_tool());
+        } else {
+            registry.register_function(add_numbers_tool()).register_function(ship_order_tool());
+        }
+        registry.snapshot_schemas()
+    };
+
+    let snapshot = build(false);
+    assert_eq!(snapshot.keys().collect::<Vec<_>>(), ["add_numbers", "ship_order"]);
+    assert_eq!(snapshot["ship_order"]["x-added"], json!({ "a": 2, "z": 1 }));
+    assert_eq!(snapshot, build(true));
+
+    // Keys serialize in sorted order at every level
+    let text = serde_json::to_string(&snapshot["add_numbers"]).unwrap();
+    assert_eq!(text, genai_tools::schema::canonical_json(&snapshot["add_numbers"]));
+}
+
+#[test]
+fn test_register_named_returns_the_tool_name() {
+    let mut registry = ToolRegistry::new();
+    assert_eq!(registry.register_named(add_numbers_tool()), "add_numbers");
+    let name = registry.register_named(clock());
+    assert_eq!(name, "get_city_time");
+    assert!(registry.has_tool(&name));
+    assert_eq!(registry.len(), 2);
+}
+
+#[test]
+fn test_register_functions_accepts_any_iterable() {
+    let mut registry = ToolRegistry::new();
+    registry
+        .register_functions([add_numbers_tool()])
+        .register_functions(None::<EchoTextTool>)
+        .register_functions(Some(echo_text_tool()))
+        .register_functions(std::iter::once(clock()));
+    assert_eq!(registry.len(), 3);
+    assert!(registry.has_tool("echo_text"));
+}
+
+#[tokio::test]
+async fn test_vec_length_constraints_in_schema() {
+    let schema = integration_test_tool_tool().schema();
+    assert_eq!(schema["properties"]["items"]["maxItems"], json!(10));
+    assert!(schema["properties"]["items"].get("minItems").is_none());
+
+    let mut registry = ToolRegistry::new().with_input_validation(true);
+    registry.register_function(integration_test_tool_tool());
+    let advertised = registry.get_tools()[0].schema.clone().unwrap();
+    assert_eq!(advertised["properties"]["items"]["maxItems"], json!(10));
+
+    // The model gets the limit as a schema violation before the tool runs
+    let call = genai::chat::ToolCall {
+        call_id: "too-many".to_string(),
+        fn_name: "integration_test_tool".to_string(),
+        fn_arguments: json!({ "name": "x", "items": vec!["item"; 11], "status": "active" }),
+    };
+    let error = registry.execute_call(&call).await.unwrap_err();
+    assert!(matches!(
+        error.downcast_ref::<RegistryError>(),
+        Some(RegistryError::InputValidation { .. })
+    ));
+}
+
+#[tokio::test]
+async fn test_jsonrpc_dispatch() {
+    let mut registry = ToolRegistry::new();
+    registry.register_function(add_numbers_tool());
+    registry.register_function(parse_integer_tool());
+
+    let methods = registry.to_jsonrpc_methods().unwrap();
+    let names: Vec<_> = methods.iter().map(|method| method["name"].clone()).collect();
+    assert_eq!(names, [json!("add_numbers"), json!("parse_integer")]);
+    assert_eq!(methods[0]["description"], json!("Add two numbers"));
+    assert_eq!(methods[0]["params"]["title"], json!("AddNumbersParams"));
+
+    let response = registry
+        .handle_jsonrpc(json!({ "jsonrpc": "2.0", "method": "add_numbers", "params": { "a": 2, "b": 3 }, "id": 1 }))
+        .await
+        .unwrap();
+    assert_eq!(response, json!({ "jsonrpc": "2.0", "result": 5, "id": 1 }));
+
+    let response = registry
+        .handle_jsonrpc(json!({ "jsonrpc": "2.0", "method": "add_numbers", "params": [4, 5], "id": "pos" }))
+        .await
+        .unwrap();
+    assert_eq!(response["result"], json!(9));
+    assert_eq!(response["id"], json!("pos"));
+
+    let code = |response: Option<serde_json::Value>| response.unwrap()["error"]["code"].clone();
+    let request = |method: &str, params: serde_json::Value| {
+        json!({ "jsonrpc": "2.0", "method": method, "params": params, "id": 7 })
+    };
+    assert_eq!(code(registry.handle_jsonrpc(request("missing", json!({}))).await), json!(-32601));
+    assert_eq!(code(registry.handle_jsonrpc(request("add_numbers", json!({ "a": "x" }))).await), json!(-32602));
+    assert_eq!(code(registry.handle_jsonrpc(request("add_numbers", json!([1, 2, 3]))).await), json!(-32602));
+    assert_eq!(
+        code(registry.handle_jsonrpc(request("parse_integer", json!({ "text": "z", "radix": 10 }))).await),
+        json!(-32000)
+    );
+    assert_eq!(code(registry.handle_jsonrpc(json!({ "method": "add_numbers", "id": 1 })).await), json!(-32600));
+    assert_eq!(code(registry.handle_jsonrpc(json!(42)).await), json!(-32600));
+
+    let response = registry.handle_jsonrpc(request("missing", json!({}))).await.unwrap();
+    assert_eq!(response["id"], json!(7));
+    assert_eq!(response["error"]["data"]["type"], json!("tool_not_found"));
+
+    // Notifications get no response, and batches answer only the calls with an id
+    let notification = json!({ "jsonrpc": "2.0", "method": "add_numbers", "params": { "a": 1 } });
+    assert_eq!(registry.handle_jsonrpc(notification.clone()).await, None);
+    let batch = registry
+        .handle_jsonrpc(json!([notification, request("add_numbers", json!({ "a": 1, "b": 1 }))]))
+        .await
+        .unwrap();
+    assert_eq!(batch, json!([{ "jsonrpc": "2.0", "result": 2, "id": 7 }]));
+}
+
+#[tool_function(description = "Answer with a preformatted response", flatten)]
+pub async fn preformatted(
+    /// The text to send back verbatim
+    text: String,
+) -> Result<genai::chat::ToolResponse, IntegrationError> {
+    Ok(genai::chat::ToolResponse::new("ignored", format!("<pre>{}</pre>", text)))
+}
+
+#[tokio::test]
+async fn test_register_response_function_sends_response_as_is() {
+    let mut registry = ToolRegistry::new()
+        .with_result_envelope(|tool, result| json!({ "tool": tool, "result": result }));
+    registry.register_response_function(preformatted_tool());
+    registry.register_function(add_numbers_tool());
+
+    let call = |name: &str, arguments| genai::chat::ToolCall {
+        call_id: "direct-1".to_string(),
+        fn_name: name.to_string(),
+        fn_arguments: arguments,
+    };
+
+    let response = registry.execute_call(&call("preformatted", json!({ "text": "a < b" }))).await.unwrap();
+    assert_eq!(response.call_id, "direct-1");
+    assert_eq!(response.content, "<pre>a < b</pre>");
+
+    // Other tools still go through the envelope
+    let response = registry.execute_call(&call("add_numbers", json!({ "a": 1, "b": 1 }))).await.unwrap();
+    assert_eq!(response.content, json!({ "tool": "add_numbers", "result": 2 }).to_string());
+}
+
+#[tokio::test]
+async fn test_execute_call_chunked() {
+    use genai_tools::ResultChunk;
+
+    let mut registry = ToolRegistry::new();
+    registry.register_function(echo_text_tool());
+    let text = "héllo wörld, this is a long result";
+    let call = genai::chat::ToolCall {
+        call_id: "chunked".to_string(),
+        fn_name: "echo_text".to_string(),
+        fn_arguments: json!({ "text": text }),
+    };
+
+    let responses = registry.execute_call_chunked(&call, 8).await.unwrap();
+    let full = json!(text).to_string();
+    assert_eq!(responses.len(), full.chars().count().div_ceil(8));
+
+    let chunks: Vec<ResultChunk> = responses
+        .iter()
+        .map(|response| serde_json::from_str(&response.content).unwrap())
+        .collect();
+    for (index, (response, chunk)) in responses.iter().zip(&chunks).enumerate() {
+        assert_eq!(response.call_id, "chunked");
+        assert_eq!(chunk.group, "chunked");
+        assert_eq!(chunk.index, index);
+        assert_eq!(chunk.total, responses.len());
+        assert!(chunk.data.chars().count() <= 8);
+    }
+
+    // Order doesn't matter when reassembling, but completeness does
+    let mut shuffled = responses.clone();
+    shuffled.reverse();
+    assert_eq!(ResultChunk::reassemble(&shuffled).unwrap(), full);
+    assert!(ResultChunk::reassemble(&responses[1..]).is_err());
+
+    let responses = registry.execute_call_chunked(&call, 1_000).await.unwrap();
+    assert_eq!(responses.len(), 1);
+    assert_eq!(ResultChunk::reassemble(&responses).unwrap(), full);
+
+    // The registry's truncation limit doesn't apply to chunked results
+    let registry = registry.with_max_result_chars(10);
+    assert_eq!(registry.execute_call(&call).await.unwrap().content.chars().count(), 10);
+    let responses = registry.execute_call_chunked(&call, 8).await.unwrap();
+    assert_eq!(ResultChunk::reassemble(&responses).unwrap(), full);
+}
+
+#[tool_function(description = "Show a chart to the user", terminal)]
+pub async fn display_chart(
+    /// Title of the chart
+    title: String,
+    /// The values to plot
+    values: Vec<f64>,
+) -> Result<String, IntegrationError> {
+    Ok(format!("{}: {} points", title, values.len()))
+}
+
+#[test]
+fn test_terminal_tools() {
+    assert!(ToolFunction::is_terminal(&display_chart_tool()));
+    assert!(!ToolFunction::is_terminal(&add_numbers_tool()));
+
+    let mut registry = ToolRegistry::new();
+    registry.register_function(display_chart_tool());
+    registry.register_function(add_numbers_tool());
+    registry.register_with_defaults(display_chart_tool(), "display_empty_chart", json!({ "values": [] }));
+
+    assert!(registry.is_terminal("display_chart"));
+    assert!(registry.is_terminal("display_empty_chart"));
+    assert!(!registry.is_terminal("add_numbers"));
+    assert!(!registry.is_terminal("missing"));
+
+    let terminal: Vec<_> = registry
+        .iter()
+        .filter(|(_, handler)| handler.is_terminal())
+        .map(|(name, _)| name)
+        .collect();
+    assert_eq!(terminal.len(), 2);
+}
+
+#[tokio::test]
+async fn test_arg_preprocessor_normalizes_arguments() {
+    fn trim_strings(value: serde_json::Value) -> serde_json::Value {
+        match value {
+            serde_json::Value::String(text) => json!(text.trim()),
+            serde_json::Value::Object(map) => {
+                serde_json::Value::Object(map.into_iter().map(|(key, value)| (key, trim_strings(value))).collect())
+            }
+            other => other,
+        }
+    }
+
+    let mut registry = ToolRegistry::new()
+        .with_input_validation(true)
+        .with_arg_preprocessor(|name, mut arguments| {
+            if name == "ship_order" {
+                // The model often capitalizes enum values
+                if let Some(method) = arguments["method"].as_str() {
+                    arguments["method"] = json!(method.to_lowercase());
+                }
+            }
+            trim_strings(arguments)
+        });
+    registry.register_function(ship_order_tool());
+    registry.register_function(echo_text_tool());
+
+    let call = |name: &str, arguments| genai::chat::ToolCall {
+        call_id: "pre".to_string(),
+        fn_name: name.to_string(),
+        fn_arguments: arguments,
+    };
+
+    let response = registry.execute_call(&call("ship_order", json!({ "method": " Pickup " }))).await.unwrap();
+    assert_eq!(response.content, "\"ready for pickup\"");
+    let response = registry.execute_call_owned(call("echo_text", json!({ "text": "  hi  " }))).await.unwrap();
+    assert_eq!(response.content, "\"hi\"");
+
+    let raw = serde_json::value::RawValue::from_string(r#"{ "text": " raw " }"#.to_string()).unwrap();
+    let response = registry.execute_raw("pre", "echo_text", &raw).await.unwrap();
+    assert_eq!(response.content, "\"raw\"");
+}
+
+#[test]
+fn test_export_definitions_round_trip() {
+    use genai_tools::ToolDefinition;
+
+    let mut registry = ToolRegistry::new().with_schema_transform(|mut schema| {
+        schema["x-exported"] = json!(true);
+        schema
+    });
+    registry.register_function(ship_order_tool());
+    registry.register_function(add_numbers_tool());
+
+    let definitions = registry.export_definitions().unwrap();
+    let names: Vec<_> = definitions.iter().map(|definition| definition.name.as_str()).collect();
+    assert_eq!(names, ["add_numbers", "ship_order"]);
+    assert_eq!(definitions[0].description, "Add two numbers");
+    assert_eq!(definitions[0].schema["x-exported"], json!(true));
+    assert_eq!(definitions[0].output_schema, None);
+
+    let manifest = serde_json::to_string(&definitions).unwrap();
+    let restored: Vec<ToolDefinition> = serde_json::from_str(&manifest).unwrap();
+    assert_eq!(restored, definitions);
+
+    let tool = restored[0].to_tool();
+    let live = registry.get_tools().into_iter().find(|tool| tool.name == "add_numbers").unwrap();
+    assert_eq!(tool.name, live.name);
+    assert_eq!(tool.description, live.description);
+    assert_eq!(tool.schema, live.schema);
+}
+
+#[tokio::test]
+async fn test_duplicate_call_ids() {
+    use genai_tools::{DuplicateCallIds, RegistryError};
+
+    let add_call = |call_id: &str| genai::chat::ToolCall {
+        call_id: call_id.to_string(),
+        fn_name: "add_numbers".to_string(),
+        fn_arguments: json!({ "a": 1, "b": 2 }),
+    };
+    // "call#2" was sent by the model, so the second "call" must skip it
+    let tool_calls = vec![add_call("call"), add_call("call"), add_call("call#2")];
+
+    let mut registry = ToolRegistry::new();
+    registry.register_function(add_numbers_tool());
+    let responses = registry.execute_calls(&tool_calls).await.unwrap();
+    let ids: Vec<_> = responses.iter().map(|response| response.call_id.as_str()).collect();
+    assert_eq!(ids, ["call", "call", "call#2"]);
+
+    let mut registry = ToolRegistry::new().with_duplicate_call_ids(DuplicateCallIds::Reject);
+    registry.register_function(add_numbers_tool());
+    let err = registry.execute_calls(&tool_calls).await.unwrap_err();
+    assert!(matches!(
+        err.downcast_ref::<RegistryError>(),
+        Some(RegistryError::DuplicateCallId { call_id }) if call_id == "call"
+    ));
+
+    let mut registry = ToolRegistry::new().with_duplicate_call_ids(DuplicateCallIds::Disambiguate);
+    registry.register_function(add_numbers_tool());
+    let responses = registry.execute_calls_owned(tool_calls.clone()).await.unwrap();
+    let ids: Vec<_> = responses.iter().map(|response| response.call_id.as_str()).collect();
+    assert_eq!(ids, ["call", "call#3", "call#2"]);
+    assert!(responses.iter().all(|response| response.content == "3"));
+}
+
+#[tool_function(description = "Delete a record", server_only("admin"))]
+pub async fn delete_record(id: u32, #[serde(default)] admin: bool) -> Result<String, IntegrationError> {
+    Ok(if admin { format!("deleted {}", id) } else { format!("refused to delete {}", id) })
+}
+
+#[tokio::test]
+async fn test_server_only_fields_are_ignored() {
+    let schema = delete_record_tool().schema();
+    assert!(schema["properties"].get("admin").is_none());
+    assert_eq!(schema["required"], json!(["id"]));
+
+    let mut registry = ToolRegistry::new().with_input_validation(true);
+    registry
+        .register_function(delete_record_tool())
+        .register_with_defaults(delete_record_tool(), "delete_record_as_admin", json!({ "admin": true }));
+
+    let call = |fn_name: &str, fn_arguments| genai::chat::ToolCall {
+        call_id: "delete-1".to_string(),
+        fn_name: fn_name.to_string(),
+        fn_arguments,
+    };
+    let response = registry
+        .execute_call(&call("delete_record", json!({ "id": 7, "admin": true })))
+        .await
+        .unwrap();
+    assert_eq!(response.content, "\"refused to delete 7\"");
+
+    let response = registry.execute_call(&call("delete_record", json!([7]))).await.unwrap();
+    assert_eq!(response.content, "\"refused to delete 7\"");
+
+    // The server can still supply the value
+    let response = registry
+        .execute_call(&call("delete_record_as_admin", json!({ "id": 7, "admin": false })))
+        .await
+        .unwrap();
+    assert_eq!(response.content, "\"deleted 7\"");
+}
+
+#[test]
+fn test_execute_calls_without_tokio() {
+    let mut registry = ToolRegistry::new();
+    registry.register_function(add_numbers_tool()).register_function(delete_record_tool());
+
+    let tool_calls = vec![
+        genai::chat::ToolCall {
+            call_id: "add".to_string(),
+            fn_name: "add_numbers".to_string(),
+            fn_arguments: json!({ "a": 1, "b": 2 }),
+        },
+        genai::chat::ToolCall {
+            call_id: "delete".to_string(),
+            fn_name: "delete_record".to_string(),
+            fn_arguments: json!({ "id": 3 }),
+        },
+    ];
+    let responses = futures::executor::block_on(registry.execute_calls(&tool_calls)).unwrap();
+    assert_eq!(responses[0].content, "3");
+    assert_eq!(responses[1].content, "\"refused to delete 3\"");
+}
+
+#[tool_function(description = "Reply with pong, without a constructor", no_register_fn, flatten)]
+pub async fn quiet_ping() -> Result<String, IntegrationError> {
+    Ok("pong".to_string())
+}
+
+#[test]
+fn test_no_register_fn_uses_struct() {
+    let mut registry = ToolRegistry::new();
+    registry.register_function(QuietPingTool);
+    assert_eq!(registry.tool_names(), ["quiet_ping"]);
+}
+
+#[test]
+fn test_description_overrides() {
+    let mut registry = ToolRegistry::new();
+    registry.register_function(get_weather_tool());
+    let original = registry.get_tools().remove(0);
+
+    registry
+        .set_description("get_weather", "Obtenir la météo actuelle")
+        .unwrap()
+        .set_tool_field_descriptions("get_weather", [("city", "Nom de la ville"), ("missing", "ignoré")])
+        .unwrap();
+    assert!(matches!(
+        registry.set_description("get_forecast", "Prévisions"),
+        Err(RegistryError::ToolNotFound { .. })
+    ));
+
+    let tool = registry.get_tools().remove(0);
+    assert_eq!(tool.description.as_deref(), Some("Obtenir la météo actuelle"));
+    let schema = tool.schema.unwrap();
+    assert_eq!(schema["properties"]["city"]["description"], "Nom de la ville");
+    assert_eq!(schema["properties"]["unit"], original.schema.unwrap()["properties"]["unit"]);
+    assert!(schema["properties"].get("missing").is_none());
+
+    // The handler itself is untouched
+    assert_eq!(get_weather_tool().description(), "Get the current weather for a location");
+    assert_eq!(registry.export_definitions().unwrap()[0].description, "Obtenir la météo actuelle");
+}
+
+#[test]
+fn test_schema_to_typescript() {
+    use genai_tools::schema;
+
+    let ts = schema::to_typescript(&integration_test_tool_tool().schema(), "IntegrationParams");
+    assert_eq!(
+        ts,
+        r#"export interface IntegrationParams {
+  /** An optional integer field */
+  count?: number | null;
+  /** A vector of strings */
+  items: string[];
+  /** A required string field */
+  name: string;
+  /** A nested enum */
+  status: Status;
+}
+
+export type Status = "active" | "inactive" | "pending";
+"#
+    );
+
+    let ts = schema::to_typescript(
+        &json!({
+            "type": "object",
+            "properties": {
+                "tags": { "type": "array", "items": { "anyOf": [{ "type": "string" }, { "type": "integer" }] } },
+                "content-type": { "const": "json" },
+                "meta": { "type": "object", "additionalProperties": { "type": "boolean" } }
+            },
+            "required": ["tags"]
+        }),
+        "Request",
+    );
+    assert!(ts.contains("  tags: (string | number)[];\n"));
+    assert!(ts.contains("  \"content-type\"?: \"json\";\n"));
+    assert!(ts.contains("  meta?: Record<string, boolean>;\n"));
+}
+
+#[tool_function(description = "Reply after a short delay", flatten)]
+pub async fn slow_echo(text: String) -> Result<String, IntegrationError> {
+    tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+    Ok(text)
+}
+
+#[tokio::test]
+async fn test_shared_registry_updates_during_calls() {
+    use genai_tools::SharedToolRegistry;
+
+    let shared = SharedToolRegistry::new(ToolRegistry::new().with_input_validation(true));
+    shared.register_function(slow_echo_tool());
+    let call = genai::chat::ToolCall {
+        call_id: "echo-1".to_string(),
+        fn_name: "slow_echo".to_string(),
+        fn_arguments: json!({ "text": "still here" }),
+    };
+
+    // The call takes its snapshot before the tool is removed
+    let (response, removed) = tokio::join!(shared.execute_call(&call), async {
+        tokio::task::yield_now().await;
+        shared.register_function(add_numbers_tool());
+        shared.remove_tool("slow_echo")
+    });
+    assert_eq!(response.unwrap().content, "\"still here\"");
+    assert!(removed);
+
+    let err = shared.execute_call(&call).await.unwrap_err();
+    assert!(matches!(err.downcast_ref::<RegistryError>(), Some(RegistryError::ToolNotFound { .. })));
+    let names: Vec<_> = shared.get_tools().into_iter().map(|tool| tool.name).collect();
+    assert_eq!(names, ["add_numbers"]);
+
+    // Options survive the copy-on-write
+    let snapshot = shared.snapshot();
+    let bad_call = genai::chat::ToolCall {
+        call_id: "add-1".to_string(),
+        fn_name: "add_numbers".to_string(),
+        fn_arguments: json!({ "a": "one", "b": 2 }),
+    };
+    let err = snapshot.execute_call(&bad_call).await.unwrap_err();
+    assert!(matches!(err.downcast_ref::<RegistryError>(), Some(RegistryError::InputValidation { .. })));
+}
+
+#[test]
+fn test_full_schema() {
+    use genai_tools::ToolHandler;
+
+    let tool = add_numbers_tool();
+    let full = ToolHandler::full_schema(&tool);
+    assert_eq!(full["name"], "add_numbers");
+    assert_eq!(full["description"], "Add two numbers");
+    assert_eq!(full["parameters"], ToolHandler::schema(&tool));
+
+    let mut registry = ToolRegistry::new().with_schema_transform(|mut schema| {
+        schema["x-transformed"] = json!(true);
+        schema
+    });
+    registry.register_function(add_numbers_tool());
+    registry.set_description("add_numbers", "Sum two integers").unwrap();
+    let full = registry.full_schema("add_numbers").unwrap();
+    assert_eq!(full["description"], "Sum two integers");
+    assert_eq!(full["parameters"], registry.get_tools()[0].schema.clone().unwrap());
+    assert_eq!(full["parameters"]["x-transformed"], json!(true));
+    assert!(matches!(registry.full_schema("missing"), Err(RegistryError::ToolNotFound { .. })));
+}
+
+#[tool_function(description = "Multiply two numbers without async fn")]
+pub fn multiply(a: i64, b: i64) -> impl std::future::Future<Output = Result<i64, IntegrationError>> {
+    std::future::ready(Ok(a * b))
+}
+
+#[tool_function(description = "Negate a number through a boxed future", flatten)]
+pub fn negate(value: i64) -> genai_tools::BoxFuture<'static, Result<i64, IntegrationError>> {
+    Box::pin(async move { Ok(-value) })
+}
+
+#[tokio::test]
+async fn test_functions_returning_futures() {
+    let mut registry = ToolRegistry::new();
+    registry.register_function(multiply_tool()).register_function(negate_tool());
+
+    let call = |fn_name: &str, fn_arguments| genai::chat::ToolCall {
+        call_id: "future-1".to_string(),
+        fn_name: fn_name.to_string(),
+        fn_arguments,
+    };
+    let response = registry.execute_call(&call("multiply", json!({ "a": 6, "b": 7 }))).await.unwrap();
+    assert_eq!(response.content, "42");
+    let response = registry.execute_call(&call("negate", json!({ "value": 5 }))).await.unwrap();
+    assert_eq!(response.content, "-5");
+}
+
+#[tool_function(description = "Look up a cached price", output_schema, flatten)]
+pub async fn cached_price(symbol: String) -> Result<genai_tools::WithWarnings<f64>, IntegrationError> {
+    let output = genai_tools::WithWarnings::new(101.5);
+    Ok(match symbol.as_str() {
+        "STALE" => output.with_warning("price is from a stale cache"),
+        _ => output,
+    })
+}
+
+#[tokio::test]
+async fn test_results_with_warnings() {
+    use genai_tools::WithWarnings;
+
+    let mut registry = ToolRegistry::new().with_output_validation(true);
+    registry.register_function(cached_price_tool());
+    let call = |symbol: &str| genai::chat::ToolCall {
+        call_id: "price-1".to_string(),
+        fn_name: "cached_price".to_string(),
+        fn_arguments: json!({ "symbol": symbol }),
+    };
+
+    let response = registry.execute_call(&call("STALE")).await.unwrap();
+    let content: serde_json::Value = serde_json::from_str(&response.content).unwrap();
+    assert_eq!(content, json!({ "result": 101.5, "warnings": ["price is from a stale cache"] }));
+
+    let response = registry.execute_call(&call("FRESH")).await.unwrap();
+    assert_eq!(response.content, r#"{"result":101.5}"#);
+
+    let output: WithWarnings<f64> = registry.execute_call_result(&call("STALE")).await.unwrap();
+    assert_eq!(output.warnings, ["price is from a stale cache"]);
+
+    let output_schema = cached_price_tool().output_schema().unwrap();
+    assert_eq!(output_schema["required"], json!(["result"]));
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct TreeNode {
+    pub value: i64,
+    #[serde(default)]
+    pub children: Vec<TreeNode>,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct TreeParams {
+    /// The root of the tree
+    pub root: TreeNode,
+}
+
+#[tool_function(description = "Sum the values in a tree")]
+pub async fn sum_tree(params: TreeParams) -> Result<i64, IntegrationError> {
+    fn sum(node: &TreeNode) -> i64 {
+        node.value + node.children.iter().map(sum).sum::<i64>()
+    }
+    Ok(sum(&params.root))
+}
+
+#[tool_function(description = "Count the nodes in a tree")]
+pub async fn count_nodes(params: TreeNode) -> Result<usize, IntegrationError> {
+    fn count(node: &TreeNode) -> usize {
+        1 + node.children.iter().map(count).sum::<usize>()
+    }
+    Ok(count(&params))
+}
+
+#[tokio::test]
+async fn test_recursive_params() {
+    use genai_tools::schema;
+
+    let tree = json!({
+        "root": {
+            "value": 1,
+            "children": [
+                { "value": 2, "children": [{ "value": 3 }] },
+                { "value": 4 }
+            ]
+        }
+    });
+    let schema = sum_tree_tool().schema();
+    assert_eq!(schema["properties"]["root"]["$ref"], "#/$defs/TreeNode");
+    assert_eq!(schema["$defs"]["TreeNode"]["properties"]["children"]["items"]["$ref"], "#/$defs/TreeNode");
+    assert_eq!(count_nodes_tool().schema()["properties"]["children"]["items"]["$ref"], "#");
+
+    // Inlining keeps the reference that closes the cycle
+    let mut inlined = schema.clone();
+    schema::inline_refs(&mut inlined);
+    let root = &inlined["properties"]["root"];
+    assert_eq!(root["description"], "The root of the tree");
+    assert_eq!(root["type"], "object");
+    assert_eq!(root["properties"]["children"]["items"]["$ref"], "#/$defs/TreeNode");
+    assert_eq!(inlined["$defs"], schema["$defs"]);
+
+    for registry_schema in [schema, inlined] {
+        let mut registry = ToolRegistry::new()
+            .with_input_validation(true)
+            .with_schema_transform(move |_| registry_schema.clone());
+        registry.register_function(sum_tree_tool());
+        let call = |fn_arguments| genai::chat::ToolCall {
+            call_id: "tree-1".to_string(),
+            fn_name: "sum_tree".to_string(),
+            fn_arguments,
+        };
+
+        let response = registry.execute_call(&call(tree.clone())).await.unwrap();
+        assert_eq!(response.content, "10");
+
+        // A bad value three levels down is caught by the recursive schema
+        let mut bad_tree = tree.clone();
+        bad_tree["root"]["children"][0]["children"][0]["value"] = json!("three");
+        let err = registry.execute_call(&call(bad_tree)).await.unwrap_err();
+        assert!(matches!(err.downcast_ref::<RegistryError>(), Some(RegistryError::InputValidation { .. })));
+    }
+
+    let mut registry = ToolRegistry::new().with_input_validation(true);
+    registry.register_function(count_nodes_tool());
+    let response = registry
+        .execute_call(&genai::chat::ToolCall {
+            call_id: "count-1".to_string(),
+            fn_name: "count_nodes".to_string(),
+            fn_arguments: tree["root"].clone(),
+        })
+        .await
+        .unwrap();
+    assert_eq!(response.content, "4");
+
+    let ts = schema::to_typescript(&count_nodes_tool().schema(), "TreeNode");
+    assert!(ts.contains("  children?: TreeNode[];\n"));
+}
+
+#[test]
+fn test_inline_refs_without_recursion() {
+    use genai_tools::schema;
+
+    let mut inlined = get_weather_tool().schema();
+    schema::inline_refs(&mut inlined);
+    assert!(inlined.get("$defs").is_none());
+    assert_eq!(inlined["properties"]["unit"]["description"], "Temperature unit");
+    assert!(inlined["properties"]["unit"]["oneOf"].is_array());
+}
+
+/// A tool call decoded from somewhere other than genai, e.g. a job queue.
+struct QueuedCall {
+    id: String,
+    tool: String,
+    arguments: serde_json::Value,
+}
+
+impl genai_tools::ToolCallLike for QueuedCall {
+    fn from_parts(id: String, tool: String, arguments: serde_json::Value) -> Self {
+        Self { id, tool, arguments }
+    }
+
+    fn call_id(&self) -> &str {
+        &self.id
+    }
+
+    fn fn_name(&self) -> &str {
+        &self.tool
+    }
+
+    fn fn_arguments(&self) -> &serde_json::Value {
+        &self.arguments
+    }
+
+    fn into_parts(self) -> (String, String, serde_json::Value) {
+        (self.id, self.tool, self.arguments)
+    }
+}
+
+#[tokio::test]
+async fn test_calls_through_the_adapter() {
+    use genai_tools::{ToolCallLike, ToolResponseLike};
+
+    let mut registry = ToolRegistry::new();
+    registry.register_function(add_numbers_tool());
+
+    let call = genai::chat::ToolCall::from_parts("adapter-1".to_string(), "add_numbers".to_string(), json!({ "a": 2, "b": 3 }));
+    let response = registry.execute_call(&call).await.unwrap();
+    assert_eq!(response.call_id(), "adapter-1");
+    assert_eq!(response.content(), "5");
+
+    let queued = vec![
+        QueuedCall::from_parts("job-1".to_string(), "add_numbers".to_string(), json!({ "a": 1, "b": 1 })),
+        QueuedCall::from_parts("job-2".to_string(), "add_numbers".to_string(), json!([4, 5])),
+    ];
+    let responses = registry.execute_calls(&queued).await.unwrap();
+    let contents: Vec<_> = responses.iter().map(|response| (response.call_id(), response.content())).collect();
+    assert_eq!(contents, [("job-1", "2"), ("job-2", "9")]);
+
+    let response = registry.execute_call_owned(queued.into_iter().next().unwrap()).await.unwrap();
+    assert_eq!(response.content(), "2");
+}
+
+#[tool_function(description = "Wait for the given number of milliseconds", flatten)]
+pub async fn wait_for(millis: u64) -> Result<u64, IntegrationError> {
+    tokio::time::sleep(std::time::Duration::from_millis(millis)).await;
+    Ok(millis)
+}
+
+#[tokio::test]
+async fn test_execute_calls_within_returns_partial_results() {
+    let mut registry = ToolRegistry::new();
+    registry.register_function(add_numbers_tool()).register_function(wait_for_tool());
+
+    let call = |call_id: &str, fn_name: &str, fn_arguments| genai::chat::ToolCall {
+        call_id: call_id.to_string(),
+        fn_name: fn_name.to_string(),
+        fn_arguments,
+    };
+    let tool_calls = vec![
+        call("fast", "wait_for", json!({ "millis": 1 })),
+        call("slow", "wait_for", json!({ "millis": 10_000 })),
+        call("bad", "add_numbers", json!({ "a": "one" })),
+    ];
+
+    let started = std::time::Instant::now();
+    let budget = std::time::Duration::from_millis(100);
+    let results = registry.execute_calls_within(&tool_calls, budget).await.unwrap();
+    assert!(started.elapsed() < std::time::Duration::from_secs(5));
+
+    assert_eq!(results[0].as_ref().unwrap().content, "1");
+    let timeout = results[1].as_ref().unwrap_err();
+    assert!(matches!(
+        timeout.downcast_ref::<RegistryError>(),
+        Some(RegistryError::Timeout { tool, .. }) if tool == "wait_for"
+    ));
+    assert_eq!(genai_tools::ToolErrorPayload::from_error(timeout.as_ref()).kind, "timeout");
+    assert!(matches!(
+        results[2].as_ref().unwrap_err().downcast_ref::<RegistryError>(),
+        Some(RegistryError::InvalidArguments { .. })
+    ));
+}
+
+#[tokio::test]
+async fn test_execute_call_timed() {
+    let mut registry = ToolRegistry::new();
+    registry.register_function(wait_for_tool());
+
+    let tool_call = genai::chat::ToolCall {
+        call_id: "timed".to_string(),
+        fn_name: "wait_for".to_string(),
+        fn_arguments: json!({ "millis": 20 }),
+    };
+    let started = std::time::Instant::now();
+    let (response, elapsed) = registry.execute_call_timed(&tool_call).await.unwrap();
+    assert_eq!(response.content, "20");
+    assert!(elapsed >= std::time::Duration::from_millis(20));
+    assert!(elapsed <= started.elapsed());
+
+    let missing = genai::chat::ToolCall {
+        fn_name: "wait_for_it".to_string(),
+        ..tool_call
+    };
+    assert!(registry.execute_call_timed(&missing).await.is_err());
+}
+
+#[test]
+fn test_property_ordering_follows_declaration_order() {
+    let ordering = |registry: &ToolRegistry, name: &str| {
+        let tools = registry.get_tools();
+        let tool = tools.iter().find(|tool| tool.name == name).unwrap();
+        tool.schema.as_ref().unwrap().get("propertyOrdering").cloned()
+    };
+
+    let mut registry = ToolRegistry::new().with_property_ordering(true);
+    registry
+        .register_function(integration_test_tool_tool())
+        .register_function(delete_record_tool());
+    // From the params struct's Deserialize impl
+    assert_eq!(
+        ordering(&registry, "integration_test_tool"),
+        Some(json!(["name", "count", "items", "status"]))
+    );
+    // Captured by the macro; server-only fields are not advertised
+    assert_eq!(ordering(&registry, "delete_record"), Some(json!(["id"])));
+
+    let mut registry = ToolRegistry::new();
+    registry.register_function(integration_test_tool_tool());
+    assert_eq!(ordering(&registry, "integration_test_tool"), None);
+
+    let mut schema = add_numbers_tool().schema();
+    genai_tools::schema::with_property_ordering(&mut schema, &["b", "missing"]);
+    assert_eq!(schema["propertyOrdering"], json!(["b", "a"]));
+}
+
+#[tokio::test]
+async fn test_sub_registry_dispatches_on_op() {
+    let mut math = ToolRegistry::new().with_input_validation(true);
+    math.register_functions([add_numbers_tool()]).register_function(negate_tool());
+    let mut registry = ToolRegistry::new().with_input_validation(true);
+    registry.register_sub_registry("math", "Arithmetic operations", math);
+    assert_eq!(registry.tool_names(), ["math"]);
+
+    let schema = registry.get_tools()[0].schema.clone().unwrap();
+    assert_eq!(schema["properties"]["op"]["enum"], json!(["add_numbers", "negate"]));
+    assert_eq!(schema["oneOf"][0]["properties"]["op"]["const"], json!("add_numbers"));
+    assert_eq!(schema["oneOf"][0]["required"][0], json!("op"));
+    assert!(schema["oneOf"][1]["properties"]["value"].is_object());
+
+    let call = |fn_arguments| genai::chat::ToolCall {
+        call_id: "math-1".to_string(),
+        fn_name: "math".to_string(),
+        fn_arguments,
+    };
+    let response = registry.execute_call(&call(json!({ "op": "add_numbers", "a": 2, "b": 3 }))).await.unwrap();
+    assert_eq!(response.content, "5");
+    let response = registry.execute_call(&call(json!({ "op": "negate", "value": 4 }))).await.unwrap();
+    assert_eq!(response.content, "-4");
+
+    let mut lenient = ToolRegistry::new();
+    let mut math = ToolRegistry::new();
+    math.register_functions([add_numbers_tool()]).register_function(negate_tool());
+    lenient.register_sub_registry("math", "Arithmetic operations", math);
+    let error = lenient.execute_call(&call(json!({ "op": "divide", "a": 1 }))).await.unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "Tool 'math' has no operation 'divide'; valid operations: add_numbers, negate"
+    );
+    let error = lenient.execute_call(&call(json!({ "a": 1 }))).await.unwrap_err();
+    assert!(matches!(
+        error.downcast_ref::<RegistryError>(),
+        Some(RegistryError::UnknownOperation { operation: None, .. })
+    ));
+
+    // Operations are advertised and called by the sub-tools' advertised names
+    let mut math = ToolRegistry::new();
+    math.register_function_as(add_numbers_tool(), "sum").unwrap().register_function(negate_tool());
+    let mut registry = ToolRegistry::new();
+    registry.register_sub_registry("math", "Arithmetic operations", math);
+    let schema = registry.get_tools()[0].schema.clone().unwrap();
+    assert_eq!(schema["properties"]["op"]["enum"], json!(["negate", "sum"]));
+    assert_eq!(schema["oneOf"][1]["properties"]["op"]["const"], json!("sum"));
+    let response = registry.execute_call(&call(json!({ "op": "sum", "a": 2, "b": 3 }))).await.unwrap();
+    assert_eq!(response.content, "5");
+}
+
+/// An arithmetic expression: a number, or the sum of sub-expressions.
+#[derive(Deserialize, JsonSchema)]
+#[serde(untagged)]
+pub enum Expr {
+    Number(f64),
+    Sum(Vec<Expr>),
+}
+
+impl Expr {
+    fn value(&self) -> f64 {
+        match self {
+            Expr::Number(value) => *value,
+            Expr::Sum(terms) => terms.iter().map(Expr::value).sum(),
+        }
+    }
+}
+
+#[tool_function(description = "Evaluate an expression", flatten)]
+pub async fn evaluate(expr: Expr) -> Result<f64, IntegrationError> {
+    Ok(expr.value())
+}
+
+#[test]
+fn test_schema_summary() {
+    use genai_tools::schema::summarize;
+
+    assert_eq!(
+        summarize(&integration_test_tool_tool().schema()),
+        "IntegrationParams{count?: integer|null, items: string[], name: string, status: enum[active,inactive,pending]}"
+    );
+    // Recursion stops at the definition being expanded
+    assert_eq!(
+        summarize(&sum_tree_tool().schema()),
+        "TreeParams{root: TreeNode{children?: TreeNode[], value: integer}}"
+    );
+    assert_eq!(summarize(&evaluate_tool().schema()), "EvaluateParams{expr: number|Expr[]}");
+
+    let mut registry = ToolRegistry::new();
+    registry.register_function(negate_tool()).register_function(add_numbers_tool());
+    assert_eq!(
+        registry.describe_compact(),
+        "add_numbers: AddNumbersParams{a: integer, b?: integer}\nnegate: NegateParams{value: integer}"
+    );
+    registry.register_function(evaluate_tool());
+    assert!(registry.describe_compact().contains("evaluate: EvaluateParams{expr: number|Expr[]}"));
+}
+
+#[tokio::test]
+async fn test_register_function_with_schema() {
+    let schema = json!({
+        "type": "object",
+        "properties": {
+            "a": { "type": "integer", "description": "Left operand" },
+            "b": { "type": "integer", "description": "Right operand" }
+        },
+        "required": ["a", "b"]
+    });
+    let mut registry = ToolRegistry::new();
+    registry.register_function_with_schema(add_numbers_tool(), schema.clone());
+    assert_eq!(registry.get_tools()[0].schema, Some(schema));
+    assert_eq!(registry.get_tools()[0].description.as_deref(), Some("Add two numbers"));
+
+    let call = |fn_arguments| genai::chat::ToolCall {
+        call_id: "override-1".to_string(),
+        fn_name: "add_numbers".to_string(),
+        fn_arguments,
+    };
+    let response = registry.execute_call(&call(json!({ "a": 2, "b": 3 }))).await.unwrap();
+    assert_eq!(response.content, "5");
+
+    // An override that disagrees with the params type only fails at call time
+    let mut registry = ToolRegistry::new();
+    registry.register_function_with_schema(
+        add_numbers_tool(),
+        json!({ "type": "object", "properties": { "a": { "type": "string" } } }),
+    );
+    let error = registry.execute_call(&call(json!({ "a": "two" }))).await.unwrap_err();
+    assert!(matches!(
+        error.downcast_ref::<RegistryError>(),
+        Some(RegistryError::InvalidArguments { .. })
+    ));
+}
+
+#[tokio::test]
+async fn test_tool_not_found_suggests_close_names() {
+    let mut registry = ToolRegistry::new();
+    registry
+        .register_function(get_weather_tool())
+        .register_function(add_numbers_tool())
+        .register_function(negate_tool());
+
+    let call = |fn_name: &str| genai::chat::ToolCall {
+        call_id: "typo-1".to_string(),
+        fn_name: fn_name.to_string(),
+        fn_arguments: json!({}),
+    };
+    let error = registry.execute_call(&call("get_wether")).await.unwrap_err();
+    assert_eq!(error.to_string(), "Tool 'get_wether' not found; did you mean 'get_weather'?");
+    assert!(matches!(
+        error.downcast_ref::<RegistryError>(),
+        Some(RegistryError::ToolNotFound { suggestions, .. }) if suggestions == &["get_weather"]
+    ));
+
+    let error = registry.execute_call(&call("send_email")).await.unwrap_err();
+    assert_eq!(error.to_string(), "Tool 'send_email' not found in registry");
+
+    let allowed = std::collections::HashSet::from(["add_numbers".to_string()]);
+    let error = registry.view(&allowed).execute_call(&call("get_weathr")).await.unwrap_err();
+    assert_eq!(error.to_string(), "Tool 'get_weathr' not found in registry");
+}
+
+#[tokio::test]
+async fn test_execute_calls_ndjson_streams_in_completion_order() {
+    use futures::StreamExt;
+
+    let mut registry = ToolRegistry::new();
+    registry.register_function(add_numbers_tool()).register_function(wait_for_tool());
+
+    let call = |call_id: &str, fn_name: &str, fn_arguments| genai::chat::ToolCall {
+        call_id: call_id.to_string(),
+        fn_name: fn_name.to_string(),
+        fn_arguments,
+    };
+    let tool_calls = vec![
+        call("slow", "wait_for", json!({ "millis": 50 })),
+        call("sum", "add_numbers", json!({ "a": 2, "b": 3 })),
+    ];
+
+    let lines: Vec<String> = registry.execute_calls_ndjson(&tool_calls).unwrap().collect().await;
+    assert!(lines.iter().all(|line| line.ends_with('\n')));
+    let lines: Vec<serde_json::Value> = lines.iter().map(|line| serde_json::from_str(line).unwrap()).collect();
+    assert_eq!(lines, [json!({ "call_id": "sum", "result": 5 }), json!({ "call_id": "slow", "result": 50 })]);
+
+    let failing = [call("missing", "no_such_tool", json!({}))];
+    let lines: Vec<String> = registry.execute_calls_ndjson(&failing).unwrap().collect().await;
+    let line: serde_json::Value = serde_json::from_str(&lines[0]).unwrap();
+    assert_eq!(line["call_id"], json!("missing"));
+    assert_eq!(line["error"]["type"], json!("tool_not_found"));
+}
+
+#[test]
+fn test_error_type_name() {
+    assert_eq!(
+        ToolFunction::error_type_name(&integration_test_tool_tool()),
+        std::any::type_name::<IntegrationError>()
+    );
+
+    let mut registry = ToolRegistry::new();
+    registry
+        .register_with_defaults(add_numbers_tool(), "add_two", json!({ "b": 2 }))
+        .register_pipeline("double_negate", "Negate twice", vec![Box::new(negate_tool()), Box::new(negate_tool())]);
+    let names: std::collections::HashMap<_, _> =
+        registry.iter().map(|(name, handler)| (name, handler.error_type_name())).collect();
+    assert_eq!(names["add_two"], std::any::type_name::<IntegrationError>());
+    assert_eq!(names["double_negate"], std::any::type_name::<RegistryError>());
+}
+
+/// Where to connect: a host name, or an explicit host and port.
+#[derive(Debug, Deserialize, JsonSchema, PartialEq)]
+#[serde(untagged)]
+pub enum Target {
+    Host(String),
+    Address { host: String, port: u16 },
+}
+
+#[tool_function(description = "Connect to a server", flatten)]
+pub async fn connect(target: Target) -> Result<String, IntegrationError> {
+    Ok(match target {
+        Target::Host(host) => format!("{}:443", host),
+        Target::Address { host, port } => format!("{}:{}", host, port),
+    })
+}
+
+#[tokio::test]
+async fn test_untagged_enum_params() {
+    let schema = connect_tool().schema();
+    let variants = schema["$defs"]["Target"]["anyOf"].as_array().unwrap();
+    assert_eq!(variants[0]["type"], json!("string"));
+    assert_eq!(variants[1]["required"], json!(["host", "port"]));
+
+    // Each variant is tried in declaration order, from both the `Value` and raw paths
+    let mut registry = ToolRegistry::new();
+    registry.register_function(connect_tool());
+    let validating = {
+        let mut registry = ToolRegistry::new().with_input_validation(true);
+        registry.register_function(connect_tool());
+        registry
+    };
+    let call = |fn_arguments| genai::chat::ToolCall {
+        call_id: "connect-1".to_string(),
+        fn_name: "connect".to_string(),
+        fn_arguments,
+    };
+    for registry in [&registry, &validating] {
+        let host = registry.execute_call(&call(json!({ "target": "example.com" }))).await.unwrap();
+        assert_eq!(host.content, json!("example.com:443").to_string());
+        let address = call(json!({ "target": { "host": "db", "port": 5432 } }));
+        assert_eq!(registry.execute_call(&address).await.unwrap().content, json!("db:5432").to_string());
+    }
+    let raw = serde_json::value::RawValue::from_string(r#"{"target":{"host":"db","port":5432}}"#.to_string()).unwrap();
+    let response = registry.execute_raw("connect-2", "connect", &raw).await.unwrap();
+    assert_eq!(response.content, json!("db:5432").to_string());
+
+    // A value matching no variant is rejected, before the call with validation
+    let out_of_range = call(json!({ "target": { "host": "db", "port": 70000 } }));
+    let error = registry.execute_call(&out_of_range).await.unwrap_err();
+    assert!(error.to_string().contains("did not match any variant of untagged enum Target"));
+    assert!(matches!(
+        validating.execute_call(&out_of_range).await.unwrap_err().downcast_ref::<RegistryError>(),
+        Some(RegistryError::InputValidation { .. })
+    ));
+}
+
+#[tool_function(flatten)]
+pub async fn undocumented_ping() -> Result<String, IntegrationError> {
+    Ok("pong".to_string())
+}
+
+#[test]
+fn test_require_descriptions() {
+    let mut registry = ToolRegistry::new().with_require_descriptions(true);
+    registry.register_function(add_numbers_tool());
+    assert!(matches!(
+        registry.try_register_function(undocumented_ping_tool()),
+        Err(RegistryError::MissingDescription { tool }) if tool == "undocumented_ping"
+    ));
+    assert!(!registry.has_tool("undocumented_ping"));
+
+    // Opt-in, and satisfied by an override set before registration
+    ToolRegistry::new().register_function(undocumented_ping_tool());
+    let mut registry = ToolRegistry::new();
+    registry.register_function(undocumented_ping_tool());
+    registry.set_description("undocumented_ping", "Check that the server is up").unwrap();
+    let mut strict = ToolRegistry::new().with_require_descriptions(true);
+    strict.merge(registry);
+    assert!(strict.has_tool("undocumented_ping"));
+
+    // Prefixed merges import nothing if a tool lacks a description
+    let mut other = ToolRegistry::new();
+    other.register_function(add_numbers_tool()).register_function(undocumented_ping_tool());
+    assert!(matches!(
+        strict.merge_prefixed("net_", other),
+        Err(RegistryError::MissingDescription { tool }) if tool == "net_undocumented_ping"
+    ));
+    assert!(!strict.has_tool("net_add_numbers"));
+}
+
+#[test]
+#[should_panic(expected = "Tool 'undocumented_ping' has no description")]
+fn test_require_descriptions_panics_in_merge() {
+    let mut other = ToolRegistry::new();
+    other.register_function(undocumented_ping_tool());
+    ToolRegistry::new().with_require_descriptions(true).merge(other);
+}
+
+#[test]
+#[should_panic(expected = "Tool 'undocumented_ping' has no description")]
+fn test_require_descriptions_panics_in_register_function() {
+    let mut registry = ToolRegistry::new().with_require_descriptions(true);
+    registry.register_function(undocumented_ping_tool());
+}
+
+#[tokio::test]
+async fn test_execute_call_with_deadline() {
+    use std::time::{Duration, Instant};
+
+    let mut registry = ToolRegistry::new();
+    registry.register_function(wait_for_tool());
+    let call = |millis: u64| genai::chat::ToolCall {
+        call_id: "deadline-1".to_string(),
+        fn_name: "wait_for".to_string(),
+        fn_arguments: json!({ "millis": millis }),
+    };
+
+    let deadline = Instant::now() + Duration::from_secs(5);
+    let response = registry.execute_call_with_deadline(&call(1), deadline).await.unwrap();
+    assert_eq!(response.content, "1");
+
+    let started = Instant::now();
+    let error = registry
+        .execute_call_with_deadline(&call(10_000), started + Duration::from_millis(50))
+        .await
+        .unwrap_err();
+    assert!(started.elapsed() < Duration::from_secs(5));
+    assert!(matches!(error.downcast_ref::<RegistryError>(), Some(RegistryError::Timeout { .. })));
+
+    // An expired deadline fails without running the tool
+    let error = registry.execute_call_with_deadline(&call(1), started).await.unwrap_err();
+    assert!(matches!(
+        error.downcast_ref::<RegistryError>(),
+        Some(RegistryError::Timeout { budget, .. }) if budget.is_zero()
+    ));
+}
+
+/// Assert that `schema` only uses what Gemini's function declarations accept.
+fn assert_gemini_compatible(schema: &serde_json::Value, path: &str) {
+    const KEYWORDS: &[&str] = &[
+        "type", "format", "title", "description", "nullable", "enum", "items", "minItems", "maxItems",
+        "properties", "required", "propertyOrdering", "minProperties", "maxProperties", "minimum",
+        "maximum", "minLength", "maxLength", "pattern", "anyOf",
+    ];
+    let object = schema.as_object().unwrap_or_else(|| panic!("{path}: not an object"));
+    for (key, value) in object {
+        assert!(KEYWORDS.contains(&key.as_str()), "{path}: unsupported keyword {key}");
+        match key.as_str() {
+            "type" => assert!(
+                ["string", "number", "integer", "boolean", "array", "object"].contains(&value.as_str().unwrap()),
+                "{path}: bad type {value}"
+            ),
+            "enum" => {
+                assert_eq!(object["type"], json!("string"), "{path}: enum without string type");
+                assert!(value.as_array().unwrap().iter().all(|v| v.is_string()), "{path}: non-string enum");
+            }
+            "format" => assert!(
+                ["enum", "date-time", "int32", "int64", "float", "double"].contains(&value.as_str().unwrap()),
+                "{path}: unsupported format {value}"
+            ),
+            "properties" => {
+                for (name, property) in value.as_object().unwrap() {
+                    assert_gemini_compatible(property, &format!("{path}.{name}"));
+                }
+            }
+            "items" => assert_gemini_compatible(value, &format!("{path}[]")),
+            "anyOf" => {
+                for variant in value.as_array().unwrap() {
+                    assert_gemini_compatible(variant, &format!("{path}|"));
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+#[test]
+fn test_to_gemini_tools() {
+    let mut registry = ToolRegistry::new();
+    registry
+        .register_function(integration_test_tool_tool())
+        .register_function(connect_tool())
+        .register_function(sum_tree_tool())
+        .register_function(QuietPingTool)
+        .register_function(wait_for_tool());
+
+    let declarations = registry.to_gemini_tools().unwrap();
+    let names: Vec<_> = declarations.iter().map(|d| d["name"].as_str().unwrap()).collect();
+    assert_eq!(names, ["connect", "integration_test_tool", "quiet_ping", "sum_tree", "wait_for"]);
+    for declaration in &declarations {
+        assert!(declaration["description"].is_string());
+        if let Some(parameters) = declaration.get("parameters") {
+            assert_eq!(parameters["type"], json!("object"));
+            assert_gemini_compatible(parameters, declaration["name"].as_str().unwrap());
+        }
+    }
+
+    let by_name = |name: &str| declarations.iter().find(|d| d["name"] == name).unwrap();
+    // Parameterless tools omit `parameters`
+    assert!(by_name("quiet_ping").get("parameters").is_none());
+    let params = &by_name("integration_test_tool")["parameters"];
+    assert_eq!(params["properties"]["count"]["type"], json!("integer"));
+    assert_eq!(params["properties"]["count"]["nullable"], json!(true));
+    assert_eq!(params["properties"]["count"]["format"], json!("int32"));
+    assert_eq!(params["properties"]["status"]["enum"], json!(["active", "inactive", "pending"]));
+    // `u64` has no Gemini format
+    assert!(by_name("wait_for")["parameters"]["properties"]["millis"].get("format").is_none());
+    // Untagged unions become `anyOf`, recursion ends in a plain object
+    let target = &by_name("connect")["parameters"]["properties"]["target"];
+    assert_eq!(target["anyOf"][0], json!({ "type": "string" }));
+    let children = &by_name("sum_tree")["parameters"]["properties"]["root"]["properties"]["children"];
+    assert_eq!(children["items"]["type"], json!("object"));
+
+    // Type lists are only nullable if they include `null`
+    let mut registry = ToolRegistry::new();
+    registry.register_function_with_schema(
+        echo_text_tool(),
+        json!({
+            "type": "object",
+            "properties": {
+                "text": { "type": ["string", "integer"] },
+                "note": { "type": ["string", "integer", "null"] }
+            }
+        }),
+    );
+    let properties = &registry.to_gemini_tools().unwrap()[0]["parameters"]["properties"];
+    assert_eq!(properties["text"]["anyOf"], json!([{ "type": "string" }, { "type": "integer" }]));
+    assert!(properties["text"].get("nullable").is_none());
+    assert_eq!(properties["note"]["nullable"], json!(true));
+}
+
+static TEXT_SCHEMA_BUILDS: std::sync::atomic::AtomicUsize = std::sync::atomic::AtomicUsize::new(0);
+
+/// Params whose schema counts how often it is generated.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TextParams {
+    pub text: String,
+}
+
+impl JsonSchema for TextParams {
+    fn schema_name() -> std::borrow::Cow<'static, str> {
+        "TextParams".into()
+    }
+
+    fn json_schema(_: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        TEXT_SCHEMA_BUILDS.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+        schemars::json_schema!({
+            "type": "object",
+            "properties": { "text": { "type": "string" } },
+            "required": ["text"]
+        })
+    }
+}
+
+fn sample_text() -> TextParams {
+    TextParams { text: "hello".to_string() }
+}
+
+#[tool_function(description = "Upper-case some text")]
+pub async fn shout(params: TextParams) -> Result<String, IntegrationError> {
+    Ok(params.text.to_uppercase())
+}
+
+#[tool_function(description = "Count the characters in some text")]
+pub async fn count_chars(params: TextParams) -> Result<usize, IntegrationError> {
+    Ok(params.text.chars().count())
+}
+
+#[tool_function(description = "Reverse some text", example_fn = sample_text)]
+pub async fn reverse_text(params: TextParams) -> Result<String, IntegrationError> {
+    Ok(params.text.chars().rev().collect())
+}
+
+#[tokio::test]
+async fn test_shared_params_schema_is_generated_once() {
+    let mut registry = ToolRegistry::new();
+    registry
+        .register_function(shout_tool())
+        .register_function(count_chars_tool())
+        .register_function(reverse_text_tool());
+    let schemas = registry.snapshot_schemas();
+    assert_eq!(TEXT_SCHEMA_BUILDS.load(std::sync::atomic::Ordering::SeqCst), 1);
+
+    // Per-tool adjustments stay with their tool
+    assert_eq!(schemas["shout"], schemas["count_chars"]);
+    assert!(schemas["shout"].get("examples").is_none());
+    assert_eq!(schemas["reverse_text"]["examples"], json!([{ "text": "hello" }]));
+
+    let call = |fn_name: &str| genai::chat::ToolCall {
+        call_id: "text-1".to_string(),
+        fn_name: fn_name.to_string(),
+        fn_arguments: json!({ "text": "abc" }),
+    };
+    assert_eq!(registry.execute_call(&call("shout")).await.unwrap().content, json!("ABC").to_string());
+    assert_eq!(registry.execute_call(&call("count_chars")).await.unwrap().content, "3");
+}
+
+#[tool_function(description = "The integration tool, for a strict provider", schema_meta = false)]
+pub async fn strict_integration(params: IntegrationParams) -> Result<IntegrationResult, IntegrationError> {
+    integration_test_tool(params).await
+}
+
+#[test]
+fn test_schema_meta_can_be_dropped_per_tool() {
+    let full = integration_test_tool_tool().schema();
+    assert!(full.get("$schema").is_some());
+    assert_eq!(full["title"], "IntegrationParams");
+
+    let strict = strict_integration_tool().schema();
+    assert!(strict.get("$schema").is_none());
+    assert!(strict.get("title").is_none());
+    // Everything else is kept
+    let mut expected = full.clone();
+    genai_tools::schema::remove_meta(&mut expected);
+    assert_eq!(strict, expected);
+    assert_eq!(strict["properties"], full["properties"]);
+
+    // The shared schema of the params type is untouched
+    assert_eq!(integration_test_tool_tool().schema(), full);
+}
+
+#[tool_function(description = "Download a text file", flatten)]
+pub async fn download_file(path: String) -> Result<genai_tools::BinaryOutput, IntegrationError> {
+    Ok(genai_tools::BinaryOutput::new("text/plain", format!("contents of {}", path).into_bytes()))
+}
+
+#[tokio::test]
+async fn test_execute_call_bytes() {
+    use genai_tools::RegistryError;
+
+    let mut registry = ToolRegistry::new();
+    registry.register_function(download_file_tool()).register_function(add_numbers_tool());
+    registry.register_with_defaults(download_file_tool(), "download_readme", json!({ "path": "README.md" }));
+
+    let call = |fn_name: &str, fn_arguments| genai::chat::ToolCall {
+        call_id: "download-1".to_string(),
+        fn_name: fn_name.to_string(),
+        fn_arguments,
+    };
+
+    let (mime_type, bytes) = registry
+        .execute_call_bytes(&call("download_file", json!({ "path": "notes.txt" })))
+        .await
+        .unwrap();
+    assert_eq!(mime_type, "text/plain");
+    assert_eq!(&bytes[..], b"contents of notes.txt");
+
+    // Wrappers pass the raw payload through
+    let (_, bytes) = registry.execute_call_bytes(&call("download_readme", json!({}))).await.unwrap();
+    assert_eq!(&bytes[..], b"contents of README.md");
+
+    // The model only sees a summary
+    let response = registry
+        .execute_call(&call("download_file", json!({ "path": "notes.txt" })))
+        .await
+        .unwrap();
+    assert_eq!(
+        serde_json::from_str::<serde_json::Value>(&response.content).unwrap(),
+        json!({ "mime_type": "text/plain", "size": 21 })
+    );
+
+    let error = registry
+        .execute_call_bytes(&call("add_numbers", json!({ "a": 1, "b": 2 })))
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        error.downcast_ref::<RegistryError>(),
+        Some(RegistryError::NotBinary { tool }) if tool == "add_numbers"
+    ));
+    let error = registry.execute_call_bytes(&call("download_file", json!({}))).await.unwrap_err();
+    assert!(matches!(error.downcast_ref::<RegistryError>(), Some(RegistryError::InvalidArguments { .. })));
+}
+
+#[tool_function(description = "Summarize sensor readings", flatten)]
+pub async fn sensor_summary(sensor: String) -> Result<serde_json::Value, IntegrationError> {
+    Ok(json!({
+        "sensor": sensor,
+        "count": 3,
+        "mean": 22.499999999,
+        "readings": [21.0, 22.333333333, -24.166666667],
+        "range": { "min": 21.004, "max": 24.166666667, "samples": 1_000_000_000_000u64 },
+    }))
+}
+
+#[tokio::test]
+async fn test_float_precision_rounds_nested_floats() {
+    let mut registry = ToolRegistry::new().with_float_precision(2);
+    registry.register_function(sensor_summary_tool());
+
+    let tool_call = genai::chat::ToolCall {
+        call_id: "sensor-1".to_string(),
+        fn_name: "sensor_summary".to_string(),
+        fn_arguments: json!({ "sensor": "attic" }),
+    };
+    let response = registry.execute_call(&tool_call).await.unwrap();
+    assert_eq!(
+        serde_json::from_str::<serde_json::Value>(&response.content).unwrap(),
+        json!({
+            "sensor": "attic",
+            "count": 3,
+            "mean": 22.5,
+            "readings": [21.0, 22.33, -24.17],
+            "range": { "min": 21.0, "max": 24.17, "samples": 1_000_000_000_000u64 },
+        })
+    );
+    assert!(response.content.contains("\"count\":3,"));
+
+    let mut registry = ToolRegistry::new();
+    registry.register_function(sensor_summary_tool());
+    let response = registry.execute_call(&tool_call).await.unwrap();
+    assert!(response.content.contains("22.499999999"));
+}
+
+#[tokio::test]
+async fn test_registry_from_config() {
+    use genai_tools::{RegistryConfig, RegistryError, ToolHandler};
+    use std::collections::HashMap;
+
+    let available = || {
+        HashMap::from([
+            ("add_numbers".to_string(), Box::new(add_numbers_tool()) as Box<dyn ToolHandler>),
+            ("wait_for".to_string(), Box::new(wait_for_tool())),
+            ("weather".to_string(), Box::new(forecast_tool())),
+            ("get_weather".to_string(), Box::new(get_weather_tool())),
+        ])
+    };
+    let config: RegistryConfig = serde_json::from_value(json!({
+        "tools": {
+            "add_numbers": { "description": "Sum two integers" },
+            "wait_for": { "timeout_ms": 50 },
+            "weather": { "rate_limit": { "max_calls": 2, "per_secs": 60 } },
+        }
+    }))
+    .unwrap();
+    let registry = ToolRegistry::from_config(config, available()).unwrap();
+
+    let mut names = registry.tool_names();
+    names.sort_unstable();
+    assert_eq!(names, ["add_numbers", "wait_for", "weather"]);
+    assert_eq!(registry.full_schema("add_numbers").unwrap()["description"], "Sum two integers");
+
+    let call = |fn_name: &str, fn_arguments| genai::chat::ToolCall {
+        call_id: "config-1".to_string(),
+        fn_name: fn_name.to_string(),
+        fn_arguments,
+    };
+    let error = registry.execute_call(&call("wait_for", json!({ "millis": 10_000 }))).await.unwrap_err();
+    assert!(matches!(error.downcast_ref::<RegistryError>(), Some(RegistryError::Timeout { .. })));
+    assert!(registry.execute_call(&call("wait_for", json!({ "millis": 1 }))).await.is_ok());
+    // Concurrent calls each get their own budget from the tool's timer
+    let slow = call("wait_for", json!({ "millis": 10_000 }));
+    let fast = call("wait_for", json!({ "millis": 1 }));
+    let results = futures::future::join_all([&slow, &fast, &slow, &fast].map(|call| registry.execute_call(call))).await;
+    let timed_out: Vec<bool> = results.iter().map(Result::is_err).collect();
+    assert_eq!(timed_out, [true, false, true, false]);
+
+    // `forecast` is exposed under its config name
+    let weather = call("weather", json!({ "city": "Oslo" }));
+    assert_eq!(registry.execute_call(&weather).await.unwrap().content, "\"Oslo\"");
+    assert!(registry.execute_call(&weather).await.is_ok());
+    let error = registry.execute_call(&weather).await.unwrap_err();
+    assert!(matches!(
+        error.downcast_ref::<RegistryError>(),
+        Some(RegistryError::RateLimited { tool, max_calls: 2, .. }) if tool == "weather"
+    ));
+    assert_eq!(genai_tools::ToolErrorPayload::from_error(error.as_ref()).kind, "unavailable");
+
+    let config: RegistryConfig = serde_json::from_value(json!({ "tools": { "add_number": {} } })).unwrap();
+    let error = ToolRegistry::from_config(config, available()).err().unwrap();
+    assert_eq!(error.to_string(), "Tool 'add_number' not found; did you mean 'add_numbers'?");
+
+    let typo = serde_json::from_value::<RegistryConfig>(json!({ "tools": { "wait_for": { "timeout": 5 } } }));
+    assert!(typo.is_err());
+}
+
+#[test]
+fn test_registry_fingerprint() {
+    let build = |reversed: bool| {
+        let mut registry = ToolRegistry::new();
+        if reversed {
+            registry.register_function(get_weather_tool()).register_function(add_numbers_tool());
+        } else {
+            registry.register_function(add_numbers_tool()).register_function(get_weather_tool());
+        }
+        registry
+    };
+
+    let fingerprint = build(false).fingerprint();
+    assert_eq!(fingerprint.len(), 16);
+    assert!(fingerprint.chars().all(|c| c.is_ascii_hexdigit()));
+    assert_eq!(build(true).fingerprint(), fingerprint);
+    assert_ne!(ToolRegistry::new().fingerprint(), fingerprint);
+
+    let mut described = build(false);
+    described.set_description("add_numbers", "Sum two integers").unwrap();
+    assert_ne!(described.fingerprint(), fingerprint);
+
+    let mut removed = build(false);
+    removed.remove_tool("get_weather");
+    assert_ne!(removed.fingerprint(), fingerprint);
+
+    let transformed = build(false).with_schema_transform(|mut schema| {
+        schema["additionalProperties"] = json!(false);
+        schema
+    });
+    assert_ne!(transformed.fingerprint(), fingerprint);
+}
+
+#[test]
+fn test_to_openapi_paths() {
+    fn refs(value: &serde_json::Value, found: &mut Vec<String>) {
+        match value {
+            serde_json::Value::Object(map) => {
+                for (key, item) in map {
+                    match item.as_str() {
+                        Some(reference) if key == "$ref" => found.push(reference.to_string()),
+                        _ => refs(item, found),
+                    }
+                }
+            }
+            serde_json::Value::Array(items) => items.iter().for_each(|item| refs(item, found)),
+            _ => {}
+        }
+    }
+
+    let mut registry = ToolRegistry::new();
+    registry
+        .register_function(integration_test_tool_tool())
+        .register_function(sum_tree_tool())
+        .register_function(report_count_tool());
+    registry.set_description("sum_tree", "Add up a tree of numbers").unwrap();
+
+    let paths = registry.to_openapi_paths().unwrap();
+    let names: Vec<_> = paths.as_object().unwrap().keys().map(String::as_str).collect();
+    assert_eq!(names, ["/integration_test_tool", "/report_count", "/sum_tree"]);
+
+    let operation = &paths["/sum_tree"]["post"];
+    assert_eq!(operation["operationId"], "sum_tree");
+    assert_eq!(operation["summary"], "Add up a tree of numbers");
+    assert_eq!(operation["requestBody"]["required"], json!(true));
+    let body = &operation["requestBody"]["content"]["application/json"]["schema"];
+    assert!(body.get("$schema").is_none());
+    assert_eq!(body["type"], "object");
+    assert_eq!(
+        operation["responses"]["default"]["content"]["application/json"]["schema"]["required"],
+        json!(["error"])
+    );
+    // Tools without an output schema still document a result
+    assert!(operation["responses"]["200"].get("content").is_none());
+    assert!(paths["/report_count"]["post"]["responses"]["200"]["content"]["application/json"]["schema"]["properties"]
+        .is_object());
+
+    // Recursive references point into the document
+    let document = json!({ "openapi": "3.1.0", "paths": paths });
+    let mut found = Vec::new();
+    refs(&document, &mut found);
+    assert!(!found.is_empty());
+    for reference in found {
+        let target = reference.strip_prefix('#').unwrap();
+        assert!(document.pointer(target).is_some(), "dangling {}", reference);
+    }
+}
+
+#[derive(Deserialize, JsonSchema)]
+pub struct PatchNicknameParams {
+    pub user_id: u32,
+    /// Omit to keep the nickname, `null` to clear it
+    #[serde(default)]
+    pub nickname: genai_tools::Maybe<String>,
+}
+
+#[tool_function(description = "Change or clear a user's nickname")]
+pub async fn patch_nickname(params: PatchNicknameParams) -> Result<serde_json::Value, IntegrationError> {
+    let mut nickname = Some("ada".to_string());
+    let change = match &params.nickname {
+        genai_tools::Maybe::Absent => "kept",
+        genai_tools::Maybe::Null => "cleared",
+        genai_tools::Maybe::Present(_) => "set",
+    };
+    params.nickname.apply(&mut nickname);
+    Ok(json!({ "user_id": params.user_id, "change": change, "nickname": nickname }))
+}
+
+#[tokio::test]
+async fn test_maybe_distinguishes_absent_null_and_present() {
+    use genai_tools::{Maybe, ToolHandler};
+
+    let tool: Box<dyn ToolHandler> = Box::new(patch_nickname_tool());
+    let schema = tool.schema();
+    assert_eq!(schema["required"], json!(["user_id"]));
+    assert_eq!(schema["properties"]["nickname"]["type"], json!(["string", "null"]));
+
+    let result = tool.call_json(json!({ "user_id": 7 })).await.unwrap();
+    assert_eq!(result, json!({ "user_id": 7, "change": "kept", "nickname": "ada" }));
+    let result = tool.call_json(json!({ "user_id": 7, "nickname": null })).await.unwrap();
+    assert_eq!(result, json!({ "user_id": 7, "change": "cleared", "nickname": null }));
+    let result = tool.call_json(json!({ "user_id": 7, "nickname": "countess" })).await.unwrap();
+    assert_eq!(result, json!({ "user_id": 7, "change": "set", "nickname": "countess" }));
+
+    let error = tool.call_json(json!({ "user_id": 7, "nickname": 3 })).await.unwrap_err();
+    assert!(error.to_string().contains("expected a string"), "{}", error);
+
+    // Without `#[serde(default)]`, omitting the field is an error rather than `Null`
+    #[derive(Debug, Deserialize)]
+    struct Strict {
+        #[allow(dead_code)]
+        nickname: Maybe<String>,
+    }
+    assert!(serde_json::from_value::<Strict>(json!({})).is_err());
+    assert_eq!(serde_json::to_value(Maybe::<String>::Absent).unwrap(), json!(null));
+}
+
+#[tokio::test]
+async fn test_register_function_as() {
+    let mut registry = ToolRegistry::new();
+    registry
+        .register_function(echo_text_tool())
+        .register_function_as(add_numbers_tool(), "sum_values")
+        .unwrap();
+
+    let mut names: Vec<_> = registry.get_tools().into_iter().map(|tool| tool.name).collect();
+    names.sort();
+    assert_eq!(names, ["echo_text", "sum_values"]);
+    let declarations = registry.to_gemini_tools().unwrap();
+    assert_eq!(declarations[1]["name"], "sum_values");
+    let definitions = registry.export_definitions().unwrap();
+    assert_eq!(definitions[1].name, "sum_values");
+    assert!(registry.to_openapi_paths().unwrap().get("/sum_values").is_some());
+    let mut plain = ToolRegistry::new();
+    plain.register_function(echo_text_tool()).register_function(add_numbers_tool());
+    assert_ne!(registry.fingerprint(), plain.fingerprint());
+    // The handler keeps its own name everywhere else
+    assert!(registry.has_tool("add_numbers"));
+    registry.set_description("add_numbers", "Add two integers").unwrap();
+
+    let call = |name: &str| genai::chat::ToolCall {
+        call_id: "call-1".to_string(),
+        fn_name: name.to_string(),
+        fn_arguments: json!({ "a": 2, "b": 3 }),
+    };
+    let sum: i64 = registry.execute_call_result(&call("sum_values")).await.unwrap();
+    assert_eq!(sum, 5);
+    let sum: i64 = registry.execute_call_result(&call("add_numbers")).await.unwrap();
+    assert_eq!(sum, 5);
+
+    assert!(matches!(
+        registry.register_function_as(repeat_word_tool(), "echo_text"),
+        Err(RegistryError::DuplicateTool { tool }) if tool == "echo_text"
+    ));
+    assert!(matches!(
+        registry.register_function_as(repeat_word_tool(), "sum_values"),
+        Err(RegistryError::DuplicateTool { .. })
+    ));
+
+    // Raw calls under the advertised name go through the tool's middleware
+    registry
+        .wrap_tool("add_numbers", |_invocation, _next| Box::pin(async { Ok(json!("blocked")) }))
+        .unwrap();
+    let raw = serde_json::value::RawValue::from_string(r#"{ "a": 1, "b": 2 }"#.to_string()).unwrap();
+    for name in ["add_numbers", "sum_values"] {
+        assert_eq!(registry.execute_raw("raw", name, &raw).await.unwrap().content, r#""blocked""#);
+    }
+
+    // Registering the tool again under its own name drops the advertised one
+    registry.register_function(add_numbers_tool());
+    assert!(registry.get_tools().iter().any(|tool| tool.name == "add_numbers"));
+    assert!(registry.execute_call(&call("sum_values")).await.is_err());
+}
+
+#[tokio::test]
+async fn test_merge_drops_clashing_advertised_names() {
+    let mut renamed = ToolRegistry::new();
+    renamed.register_function_as(add_numbers_tool(), "echo_text").unwrap();
+
+    let mut registry = ToolRegistry::new();
+    registry.register_function(echo_text_tool());
+    registry.merge(renamed);
+    let mut names: Vec<_> = registry.get_tools().into_iter().map(|tool| tool.name).collect();
+    names.sort();
+    assert_eq!(names, ["add_numbers", "echo_text"]);
+    let call = genai::chat::ToolCall {
+        call_id: "call-1".to_string(),
+        fn_name: "echo_text".to_string(),
+        fn_arguments: json!({ "text": "hi" }),
+    };
+    let echoed: String = registry.execute_call_result(&call).await.unwrap();
+    assert_eq!(echoed, "hi");
+
+    let mut renamed = ToolRegistry::new();
+    renamed.register_function_as(add_numbers_tool(), "echo_text").unwrap();
+    let mut prefixed = ToolRegistry::new();
+    prefixed.register_function(echo_text_tool());
+    let mut registry = ToolRegistry::new();
+    registry.merge_prefixed("x_", prefixed).unwrap();
+    assert!(matches!(
+        registry.merge_prefixed("x_", renamed),
+        Err(RegistryError::DuplicateTool { tool }) if tool == "x_echo_text"
+    ));
+    assert!(!registry.has_tool("x_add_numbers"));
+
+    // Prefixed names may not take an advertised name either
+    let mut registry = ToolRegistry::new();
+    registry.register_function_as(add_numbers_tool(), "x_echo_text").unwrap();
+    let mut prefixed = ToolRegistry::new();
+    prefixed.register_function(echo_text_tool());
+    assert!(matches!(
+        registry.merge_prefixed("x_", prefixed),
+        Err(RegistryError::DuplicateTool { tool }) if tool == "x_echo_text"
+    ));
+    assert_eq!(registry.get_tools()[0].name, "x_echo_text");
+    assert_eq!(registry.len(), 1);
+}
+
+#[derive(Deserialize, JsonSchema)]
+pub struct ArchiveParams {
+    /// The document to archive
+    pub document_id: String,
+    /// Filled in by the server, never by the model
+    #[serde(skip)]
+    pub requested_by: Option<String>,
+    #[serde(skip)]
+    pub attempts: u32,
+}
+
+#[tool_function(description = "Archive a document")]
+pub async fn archive_document(params: ArchiveParams) -> Result<serde_json::Value, IntegrationError> {
+    Ok(json!({
+        "document_id": params.document_id,
+        "requested_by": params.requested_by,
+        "attempts": params.attempts,
+    }))
+}
+
+#[tool_function(description = "Tag a document")]
+pub async fn tag_document(document_id: String, #[serde(skip)] audit: bool, tag: String) -> Result<String, IntegrationError> {
+    Ok(format!("{}:{}:{}", document_id, tag, audit))
+}
+
+#[tokio::test]
+async fn test_serde_skip_fields_are_not_advertised() {
+    use genai_tools::ToolHandler;
+
+    let tool: Box<dyn ToolHandler> = Box::new(archive_document_tool());
+    let schema = tool.schema();
+    assert_eq!(schema["properties"].as_object().unwrap().keys().collect::<Vec<_>>(), ["document_id"]);
+    assert_eq!(schema["required"], json!(["document_id"]));
+    let result = tool.call_json(json!({ "document_id": "doc-1" })).await.unwrap();
+    assert_eq!(result, json!({ "document_id": "doc-1", "requested_by": null, "attempts": 0 }));
+    // Sent anyway, a skipped field is ignored like any unknown field
+    let result = tool.call_json(json!({ "document_id": "doc-1", "attempts": 5 })).await.unwrap();
+    assert_eq!(result["attempts"], 0);
+
+    let mut registry = ToolRegistry::new().with_input_validation(true);
+    registry.register_function(archive_document_tool()).register_function(tag_document_tool());
+    assert!(registry.check_schema_serde_consistency().is_ok());
+
+    // Skipped flattened arguments are left out of positional arguments too
+    let tool: Box<dyn ToolHandler> = Box::new(tag_document_tool());
+    assert_eq!(tool.positional_fields(), Some(&["document_id", "tag"][..]));
+    assert_eq!(tool.schema()["required"], json!(["document_id", "tag"]));
+    let result = tool.call_json(json!(["doc-1", "urgent"])).await.unwrap();
+    assert_eq!(result, json!("doc-1:urgent:false"));
+}
+
+#[tokio::test]
+async fn test_register_async() {
+    use genai_tools::SharedToolRegistry;
+
+    let mut registry = ToolRegistry::new();
+    registry
+        .register_async(|| async {
+            // Stands in for fetching the tool's configuration
+            tokio::task::yield_now().await;
+            lookup_entry_tool()
+        })
+        .await
+        .register_function(add_numbers_tool());
+    assert!(registry.contains_all(&["lookup_entry", "add_numbers"]));
+
+    // Tools built asynchronously still go through `init_all`
+    let tool_call = genai::chat::ToolCall {
+        call_id: "init-1".to_string(),
+        fn_name: "lookup_entry".to_string(),
+        fn_arguments: json!({ "index": 1 }),
+    };
+    assert!(registry.execute_call(&tool_call).await.is_err());
+    registry.init_all().await.unwrap();
+    assert_eq!(registry.execute_call(&tool_call).await.unwrap().content, r#""beta""#);
+
+    let shared = SharedToolRegistry::new(ToolRegistry::new());
+    shared.register_async(|| async { echo_text_tool() }).await;
+    assert!(shared.snapshot().has_tool("echo_text"));
+}
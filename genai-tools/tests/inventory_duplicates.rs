@@ -0,0 +1,31 @@
+use genai_tools::{tool_function, RegistryError, ToolRegistry};
+
+#[derive(Debug, thiserror::Error)]
+#[error("never fails")]
+pub struct NeverFails;
+
+mod first {
+    use super::*;
+
+    #[tool_function(name = "lookup", description = "First lookup implementation", collect)]
+    pub async fn lookup() -> Result<u32, NeverFails> {
+        Ok(1)
+    }
+}
+
+mod second {
+    use super::*;
+
+    #[tool_function(name = "lookup", description = "Second lookup implementation", collect)]
+    pub async fn lookup() -> Result<u32, NeverFails> {
+        Ok(2)
+    }
+}
+
+#[test]
+fn test_from_inventory_rejects_duplicate_names() {
+    match ToolRegistry::from_inventory() {
+        Err(RegistryError::DuplicateTool { tool }) => assert_eq!(tool, "lookup"),
+        other => panic!("expected DuplicateTool error, got {:?}", other),
+    }
+}
@@ -0,0 +1,79 @@
+#![cfg(feature = "testing")]
+
+use genai::chat::ToolCall;
+use genai_tools::testing::MockRegistry;
+use genai_tools::{tool_function, ToolRegistry};
+use serde_json::json;
+
+#[tool_function(description = "Get the current weather for a city", flatten)]
+pub async fn get_weather(city: String) -> Result<String, std::io::Error> {
+    panic!("the mock must not run the tool body for {}", city)
+}
+
+fn call(call_id: &str, name: &str, arguments: serde_json::Value) -> ToolCall {
+    ToolCall {
+        call_id: call_id.to_string(),
+        fn_name: name.to_string(),
+        fn_arguments: arguments,
+    }
+}
+
+#[tokio::test]
+async fn test_mock_registry_records_calls() {
+    let mut real = ToolRegistry::new();
+    real.register_function(get_weather_tool());
+
+    let mock = MockRegistry::mirror(&real)
+        .unwrap()
+        .with_response("get_weather", json!("sunny"))
+        .with_error("book_flight", "no seats left");
+    assert_eq!(mock.get_tools().len(), 2);
+    let weather = mock.get_tools().into_iter().find(|tool| tool.name == "get_weather").unwrap();
+    let original = &real.get_tools()[0];
+    assert_eq!((weather.description, weather.schema), (original.description.clone(), original.schema.clone()));
+
+    let response = mock.execute_call(&call("call-1", "get_weather", json!({ "city": "Paris" }))).await.unwrap();
+    assert_eq!(response.content, r#""sunny""#);
+    let response = mock
+        .registry()
+        .execute_call(&call("call-2", "get_weather", json!({ "city": "Oslo" })))
+        .await
+        .unwrap();
+    assert_eq!(response.content, r#""sunny""#);
+    let error = mock.execute_call(&call("call-3", "book_flight", json!({ "to": "Rome" }))).await.unwrap_err();
+    assert_eq!(error.to_string(), "no seats left");
+
+    let calls = mock.calls();
+    let ids: Vec<_> = calls.iter().map(|call| call.call_id.as_str()).collect();
+    assert_eq!(ids, ["call-1", "call-2", "call-3"]);
+    assert_eq!(calls[2].tool, "book_flight");
+    assert_eq!(calls[2].arguments, json!({ "to": "Rome" }));
+    assert_eq!(mock.calls_to("get_weather"), [json!({ "city": "Paris" }), json!({ "city": "Oslo" })]);
+
+    mock.clear_calls();
+    assert!(mock.calls().is_empty());
+}
+
+#[tokio::test]
+async fn test_mock_registry_replaces_responses() {
+    let mock = MockRegistry::new()
+        .with_response("roll_dice", json!(4))
+        .with_response("roll_dice", json!(6));
+
+    let response = mock.execute_call(&call("call-1", "roll_dice", json!({}))).await.unwrap();
+    assert_eq!(response.content, "6");
+    // Each call is recorded once, by the current stand-in
+    assert_eq!(mock.calls().len(), 1);
+}
+
+#[tokio::test]
+async fn test_mock_registry_mirrors_advertised_names() {
+    let mut real = ToolRegistry::new();
+    real.register_function_as(get_weather_tool(), "lookup_forecast").unwrap();
+
+    let mock = MockRegistry::mirror(&real).unwrap().with_response("lookup_forecast", json!("rainy"));
+    assert_eq!(mock.get_tools()[0].name, "lookup_forecast");
+    let response = mock.execute_call(&call("call-1", "lookup_forecast", json!({ "city": "Lima" }))).await.unwrap();
+    assert_eq!(response.content, r#""rainy""#);
+    assert_eq!(mock.calls_to("lookup_forecast"), [json!({ "city": "Lima" })]);
+}